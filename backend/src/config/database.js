@@ -1,5 +1,13 @@
 require('dotenv').config({ path: '../../.env' });
 
+// Connection pool settings shared by every environment
+const pool = {
+  max: parseInt(process.env.DB_POOL_MAX, 10) || 5,
+  min: parseInt(process.env.DB_POOL_MIN, 10) || 0,
+  acquire: parseInt(process.env.DB_POOL_ACQUIRE, 10) || 30000,
+  idle: parseInt(process.env.DB_POOL_IDLE, 10) || 10000
+};
+
 module.exports = {
   development: {
     username: process.env.DB_USER || 'postgres',
@@ -8,7 +16,8 @@ module.exports = {
     host: process.env.DB_HOST || 'localhost',
     port: process.env.DB_PORT || 5432,
     dialect: process.env.DB_DIALECT || 'postgres',
-    logging: false
+    logging: false,
+    pool
   },
   test: {
     username: process.env.DB_USER || 'postgres',
@@ -17,7 +26,8 @@ module.exports = {
     host: process.env.DB_HOST || 'localhost',
     port: process.env.DB_PORT || 5432,
     dialect: process.env.DB_DIALECT || 'postgres',
-    logging: false
+    logging: false,
+    pool
   },
   production: {
     username: process.env.DB_USER,
@@ -27,11 +37,6 @@ module.exports = {
     port: process.env.DB_PORT,
     dialect: process.env.DB_DIALECT || 'postgres',
     logging: false,
-    pool: {
-      max: 5,
-      min: 0,
-      acquire: 30000,
-      idle: 10000
-    }
+    pool
   }
 };
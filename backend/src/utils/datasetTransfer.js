@@ -0,0 +1,109 @@
+// Dataset transfer utility for backing up tables and moving them between environments
+// Rows are streamed as JSON Lines with a SHA-256 checksum of the exported bytes
+
+const crypto = require('crypto');
+const readline = require('readline');
+
+const DEFAULT_BATCH_SIZE = 500;
+
+/**
+ * Write a line to a stream and wait until it is flushed, so large exports don't buffer in memory
+ */
+function writeLine(output, line) {
+  return new Promise((resolve, reject) => {
+    output.write(line, (error) => (error ? reject(error) : resolve()));
+  });
+}
+
+/**
+ * Export every row of a model to a writable stream as JSON Lines
+ * @param {Model} Model - Sequelize model (e.g. Prediction)
+ * @param {Writable} output - Destination stream (a file, or an upload stream for S3 or IPFS)
+ * @param {Object} options - { where, batchSize }
+ * @returns {Promise<Object>} - { table, rows, sha256 } to keep alongside the file
+ */
+async function exportModel(Model, output, options = {}) {
+  const batchSize = options.batchSize || DEFAULT_BATCH_SIZE;
+  const hash = crypto.createHash('sha256');
+  let rows = 0;
+
+  for (let offset = 0; ; offset += batchSize) {
+    const batch = await Model.findAll({
+      where: options.where,
+      order: [[Model.primaryKeyAttribute, 'ASC']],
+      limit: batchSize,
+      offset
+    });
+
+    for (const row of batch) {
+      // get() instead of toJSON() so fields hidden from API responses (password hashes) are kept
+      const line = `${JSON.stringify(row.get({ plain: true }))}\n`;
+      hash.update(line);
+      await writeLine(output, line);
+      rows++;
+    }
+
+    if (batch.length < batchSize) {
+      break;
+    }
+  }
+
+  return { table: Model.getTableName().toString(), rows, sha256: hash.digest('hex') };
+}
+
+/**
+ * Import JSON Lines produced by exportModel into a model
+ * Rows are inserted in one transaction, which is rolled back when the checksum does not match
+ * @param {Model} Model - Sequelize model
+ * @param {Readable} input - Source stream
+ * @param {Object} options - { sha256, batchSize, updateOnDuplicate }
+ * @returns {Promise<Object>} - { table, rows, sha256 }
+ */
+async function importModel(Model, input, options = {}) {
+  const batchSize = options.batchSize || DEFAULT_BATCH_SIZE;
+  const hash = crypto.createHash('sha256');
+  let rows = 0;
+
+  // Rows are stored as exported: hooks such as password hashing must not run again
+  const insert = (batch, transaction) => Model.bulkCreate(batch, {
+    transaction,
+    hooks: false,
+    validate: false,
+    updateOnDuplicate: options.updateOnDuplicate
+  });
+
+  await Model.sequelize.transaction(async (transaction) => {
+    const lines = readline.createInterface({ input, crlfDelay: Infinity });
+    let batch = [];
+
+    for await (const line of lines) {
+      if (!line.trim()) {
+        continue;
+      }
+      hash.update(`${line}\n`);
+      batch.push(JSON.parse(line));
+      rows++;
+
+      if (batch.length >= batchSize) {
+        await insert(batch, transaction);
+        batch = [];
+      }
+    }
+
+    if (batch.length > 0) {
+      await insert(batch, transaction);
+    }
+
+    const sha256 = hash.copy().digest('hex');
+    if (options.sha256 && options.sha256 !== sha256) {
+      throw new Error(`Checksum mismatch: expected ${options.sha256}, got ${sha256}`);
+    }
+  });
+
+  return { table: Model.getTableName().toString(), rows, sha256: hash.digest('hex') };
+}
+
+module.exports = {
+  exportModel,
+  importModel
+};
@@ -0,0 +1,86 @@
+const { PassThrough, Readable } = require('stream');
+const { exportModel, importModel } = require('../src/utils/datasetTransfer');
+
+// Minimal stand-in for a Sequelize model backed by an array
+function createModel(rows = []) {
+  const stored = [...rows];
+  const transactions = [];
+
+  const Model = {
+    primaryKeyAttribute: 'id',
+    stored,
+    transactions,
+    getTableName: () => 'Predictions',
+    findAll: jest.fn(async ({ limit, offset }) => stored
+      .slice(offset, offset + limit)
+      .map((row) => ({ get: () => ({ ...row }) }))),
+    bulkCreate: jest.fn(async (batch, { transaction }) => {
+      transaction.pending.push(...batch);
+    }),
+    sequelize: {
+      transaction: async (work) => {
+        const transaction = { pending: [] };
+        transactions.push(transaction);
+        const result = await work(transaction);
+        stored.push(...transaction.pending);
+        return result;
+      }
+    }
+  };
+  return Model;
+}
+
+const collect = (stream) => new Promise((resolve) => {
+  let text = '';
+  stream.on('data', (chunk) => {
+    text += chunk;
+  });
+  stream.on('end', () => resolve(text));
+});
+
+describe('Dataset Transfer', () => {
+  const rows = [
+    { id: 'a', horizon: 3, predictions: [1, 2, 3] },
+    { id: 'b', horizon: 1, predictions: [4] },
+    { id: 'c', horizon: 2, predictions: [5, 6] }
+  ];
+
+  test('should export rows as JSON Lines in batches', async () => {
+    const Model = createModel(rows);
+    const output = new PassThrough();
+    const text = collect(output);
+
+    const summary = await exportModel(Model, output, { batchSize: 2 });
+    output.end();
+
+    expect(summary.table).toBe('Predictions');
+    expect(summary.rows).toBe(3);
+    expect(summary.sha256).toMatch(/^[0-9a-f]{64}$/);
+    expect(Model.findAll).toHaveBeenCalledTimes(2);
+    expect((await text).trim().split('\n').map((line) => JSON.parse(line))).toEqual(rows);
+  });
+
+  test('should import an export and verify its checksum', async () => {
+    const source = createModel(rows);
+    const output = new PassThrough();
+    const text = collect(output);
+    const exported = await exportModel(source, output);
+    output.end();
+
+    const target = createModel();
+    const summary = await importModel(target, Readable.from([await text]), { sha256: exported.sha256, batchSize: 2 });
+
+    expect(summary).toEqual(exported);
+    expect(target.stored).toEqual(rows);
+    expect(target.bulkCreate).toHaveBeenCalledTimes(2);
+    expect(target.bulkCreate.mock.calls[0][1]).toMatchObject({ hooks: false });
+  });
+
+  test('should roll back when the checksum does not match', async () => {
+    const target = createModel();
+    const input = Readable.from([`${JSON.stringify(rows[0])}\n`]);
+
+    await expect(importModel(target, input, { sha256: '0'.repeat(64) })).rejects.toThrow('Checksum mismatch');
+    expect(target.stored).toHaveLength(0);
+  });
+});
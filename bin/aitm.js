@@ -0,0 +1,7 @@
+#!/usr/bin/env node
+require('dotenv').config();
+const { run } = require('../src/cli');
+
+run(process.argv.slice(2)).then((code) => {
+  process.exitCode = code;
+});
@@ -0,0 +1,170 @@
+const { ToolRegistry } = require('./tools');
+
+/**
+ * Agent Class
+ * Runs a bounded plan-act-observe loop: the model either answers or calls
+ * tools, tool results are fed back, and the loop repeats until an answer,
+ * the step limit, or cancellation.
+ *
+ * Hooks (all optional, may be async; throwing from a hook stops the run):
+ * - onStep({ step, message })              - after each model response
+ * - onToolCall({ step, toolCall })         - before a tool is executed
+ * - onToolResult({ step, toolCall, result }) - after a tool is executed
+ * - onFinish({ output, steps })            - when the model answers
+ *
+ * With a PolicyEngine, the task is checked before the run starts and each
+ * tool call before it executes; denied calls are reported back to the model
+ * as tool errors. Calls the policy marks as needing approval wait for an
+ * ApprovalQueue decision when one is configured.
+ */
+class Agent {
+  /**
+   * @param {Object} options - Agent options
+   * @param {Object} options.client - AI client with chatWithTools (ChatGPT, Grok)
+   * @param {ToolRegistry} options.tools - Tools the agent may use
+   * @param {string} options.systemPrompt - Instructions for the agent
+   * @param {number} options.maxSteps - Maximum model calls per run (default 10)
+   * @param {boolean} options.memory - Keep messages between runs (default false)
+   * @param {Object} options.hooks - Step hooks
+   * @param {PolicyEngine} options.policy - Rules enforced on the task and on tool calls
+   * @param {ApprovalQueue} options.approvals - Queue asked about tool calls the policy holds for approval
+   */
+  constructor(options = {}) {
+    const { client } = options;
+    if (!client || typeof client.chatWithTools !== 'function') {
+      throw new Error('Agent requires a client with a chatWithTools(messages, tools, options) method');
+    }
+
+    this.client = client;
+    this.tools = options.tools || new ToolRegistry();
+    this.systemPrompt = options.systemPrompt || null;
+    this.maxSteps = options.maxSteps || 10;
+    this.memory = options.memory || false;
+    this.hooks = options.hooks || {};
+    this.policy = options.policy || null;
+    this.approvals = options.approvals || null;
+    this.messages = this._initialMessages();
+  }
+
+  /**
+   * Run the agent on a task
+   * @param {string} task - Task description or user message
+   * @param {Object} options - Run options
+   * @param {AbortSignal} options.signal - Signal used to cancel the run, passed on to the model calls
+   * @param {Object} options.requestOptions - Options passed to the client (model, temperature, etc.)
+   * @returns {Promise<Object>} - { output, steps, messages }
+   */
+  async run(task, options = {}) {
+    const { signal, requestOptions = {} } = options;
+    if (this.policy) {
+      await this.policy.enforcePrompt(task);
+    }
+
+    const messages = this.memory ? this.messages : this._initialMessages();
+    // Length of the messages that form complete steps; a step that fails part
+    // way is cut back to this so memory never keeps tool_calls without replies,
+    // and a run whose first step fails leaves no dangling user turn
+    let committed = messages.length;
+    messages.push({ role: 'user', content: task });
+
+    try {
+      for (let step = 1; step <= this.maxSteps; step++) {
+        if (signal && signal.aborted) {
+          throw new Error('Agent run cancelled');
+        }
+
+        const result = await this.client.chatWithTools(messages, this.tools, {
+          ...requestOptions,
+          signal,
+          autoExecute: false,
+          maxIterations: 1,
+        });
+        const message = result.messages[result.messages.length - 1];
+        messages.push(message);
+        await this._hook('onStep', { step, message });
+
+        if (result.toolCalls.length === 0) {
+          await this._hook('onFinish', { output: result.content, steps: step });
+          return { output: result.content, steps: step, messages: [...messages] };
+        }
+
+        for (const toolCall of result.toolCalls) {
+          if (signal && signal.aborted) {
+            throw new Error('Agent run cancelled');
+          }
+
+          await this._hook('onToolCall', { step, toolCall });
+          const toolResult = await this._executeTool(toolCall);
+          messages.push(toolResult);
+          await this._hook('onToolResult', { step, toolCall, result: toolResult.content });
+        }
+        committed = messages.length;
+      }
+    } catch (error) {
+      messages.length = committed;
+      throw error;
+    }
+
+    throw new Error(`Agent did not finish within ${this.maxSteps} steps`);
+  }
+
+  /**
+   * Forget messages from previous runs
+   */
+  reset() {
+    this.messages = this._initialMessages();
+  }
+
+  /**
+   * Capture the agent's memory for a Snapshot
+   * @returns {Object} - { messages }
+   */
+  snapshot() {
+    return { messages: this.messages.map((m) => ({ ...m })) };
+  }
+
+  /**
+   * Replace the agent's memory with a captured one
+   * @param {Object} state - Output of snapshot()
+   */
+  restore(state) {
+    this.messages = state.messages.map((m) => ({ ...m }));
+  }
+
+  async _executeTool(toolCall) {
+    if (this.policy) {
+      let args = {};
+      try {
+        args = toolCall.function.arguments ? JSON.parse(toolCall.function.arguments) : {};
+      } catch {
+        // Malformed arguments are reported by the tool registry
+      }
+
+      const { name } = toolCall.function;
+      const decision = await this.policy.checkToolCall(name, args);
+      if (!decision.allowed) {
+        if (!decision.approvalRequired || !this.approvals) {
+          return { role: 'tool', tool_call_id: toolCall.id, content: `Error: denied by policy: ${decision.reason}` };
+        }
+
+        const outcome = await this.approvals.request({ tool: name, args, reason: decision.reason });
+        if (!outcome.approved) {
+          return { role: 'tool', tool_call_id: toolCall.id, content: `Error: not approved: ${outcome.reason}` };
+        }
+      }
+    }
+    return this.tools.execute(toolCall);
+  }
+
+  _initialMessages() {
+    return this.systemPrompt ? [{ role: 'system', content: this.systemPrompt }] : [];
+  }
+
+  async _hook(name, event) {
+    if (typeof this.hooks[name] === 'function') {
+      await this.hooks[name](event);
+    }
+  }
+}
+
+module.exports = Agent;
@@ -0,0 +1,124 @@
+const crypto = require('crypto');
+
+/**
+ * PII patterns scrubbed by default, applied in order
+ */
+const DEFAULT_PATTERNS = [
+  { name: 'EMAIL', pattern: /[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}/g },
+  { name: 'CARD', pattern: /\b(?:\d[ -]?){13,16}\b/g },
+  { name: 'SSN', pattern: /\b\d{3}-\d{2}-\d{4}\b/g },
+  { name: 'PHONE', pattern: /(?:\+\d{1,3}[ .-]?)?\(?\d{3}\)?[ .-]?\d{3}[ .-]?\d{4}\b/g },
+  { name: 'IP', pattern: /\b(?:\d{1,3}\.){3}\d{1,3}\b/g },
+];
+
+/**
+ * Anonymizer Class
+ * Prepares logged conversations and metadata for export, e.g. as
+ * fine-tuning data:
+ * - scrub() replaces PII in text with placeholders such as [EMAIL]
+ * - scrubMessages() and toFineTuningJSONL() apply it to conversations
+ * - kAnonymize() suppresses records whose quasi-identifiers are rarer than k
+ * - addNoise() adds Laplace noise to aggregate counts
+ */
+class Anonymizer {
+  /**
+   * @param {Object} options - Anonymizer options
+   * @param {Array} options.patterns - Extra { name, pattern } entries (global regexes) scrubbed after the defaults
+   * @param {string} options.salt - Salt for pseudonymize(); without one, a random salt is used per instance
+   * @param {Function} options.random - Random source for addNoise() (for tests)
+   */
+  constructor(options = {}) {
+    this.patterns = [...DEFAULT_PATTERNS, ...(options.patterns || [])];
+    this.salt = options.salt || crypto.randomBytes(16).toString('hex');
+    this.random = options.random || Math.random;
+  }
+
+  /**
+   * Replace PII in text with placeholders
+   * @param {string} text - Text to scrub
+   * @returns {string} - Scrubbed text
+   */
+  scrub(text) {
+    if (typeof text !== 'string') {
+      return text;
+    }
+    return this.patterns.reduce((result, { name, pattern }) => result.replace(pattern, `[${name}]`), text);
+  }
+
+  /**
+   * Scrub the content of chat messages, including text parts of multimodal messages
+   * @param {Array} messages - Messages with role and content
+   * @returns {Array} - New scrubbed messages
+   */
+  scrubMessages(messages) {
+    return messages.map((message) => ({
+      ...message,
+      content: Array.isArray(message.content)
+        ? message.content.map((part) => (part.type === 'text' ? { ...part, text: this.scrub(part.text) } : part))
+        : this.scrub(message.content),
+    }));
+  }
+
+  /**
+   * Replace an identifier with a stable salted hash
+   * @param {string} value - Identifier such as a user id
+   * @returns {string} - Pseudonym
+   */
+  pseudonymize(value) {
+    return crypto.createHmac('sha256', this.salt).update(String(value)).digest('hex').slice(0, 16);
+  }
+
+  /**
+   * Export conversations as fine-tuning JSON Lines, scrubbed of PII
+   * @param {Array} conversations - Conversation instances or message arrays
+   * @returns {string} - One { messages } object per line
+   */
+  toFineTuningJSONL(conversations) {
+    return conversations
+      .map((conversation) => {
+        const messages = Array.isArray(conversation) ? conversation : conversation.messages;
+        return JSON.stringify({ messages: this.scrubMessages(messages) });
+      })
+      .join('\n');
+  }
+
+  /**
+   * Suppress records whose combination of quasi-identifiers occurs fewer than k times
+   * @param {Array<Object>} records - Metadata records
+   * @param {Array<string>} quasiIdentifiers - Fields that could identify someone in combination
+   * @param {number} k - Minimum group size
+   * @returns {Object} - { records, suppressed }
+   */
+  kAnonymize(records, quasiIdentifiers, k) {
+    const keyOf = (record) => JSON.stringify(quasiIdentifiers.map((field) => record[field]));
+    const counts = new Map();
+    for (const record of records) {
+      counts.set(keyOf(record), (counts.get(keyOf(record)) || 0) + 1);
+    }
+
+    const kept = records.filter((record) => counts.get(keyOf(record)) >= k);
+    return { records: kept, suppressed: records.length - kept.length };
+  }
+
+  /**
+   * Add Laplace noise to an aggregate for differential privacy
+   * @param {number} value - True aggregate (e.g. a count)
+   * @param {Object} options - Noise options
+   * @param {number} options.epsilon - Privacy budget; smaller means more noise (default 1)
+   * @param {number} options.sensitivity - Maximum change one person can cause (default 1)
+   * @returns {number} - Noisy value
+   */
+  addNoise(value, options = {}) {
+    const epsilon = options.epsilon || 1;
+    const sensitivity = options.sensitivity || 1;
+    let sample = this.random();
+    // random() may return exactly 0, which would make the noise infinite
+    while (sample === 0) {
+      sample = this.random();
+    }
+    const u = sample - 0.5;
+    return value - (sensitivity / epsilon) * Math.sign(u) * Math.log(1 - 2 * Math.abs(u));
+  }
+}
+
+module.exports = Anonymizer;
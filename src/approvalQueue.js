@@ -0,0 +1,115 @@
+const crypto = require('crypto');
+const { paginate } = require('./pagination');
+
+/**
+ * ApprovalQueue Class
+ * Parks actions that need a human decision. request() notifies approvers
+ * (for example by posting a Slack message with approve/reject buttons) and
+ * resolves once an authorized approver responds through approve() or
+ * reject(), or rejects the action when the timeout passes.
+ *
+ * Example:
+ *   const approvals = new ApprovalQueue({
+ *     approvers: ['U123'],
+ *     notify: (request) => chat.post(`Approve ${request.action.tool}? id=${request.id}`),
+ *   });
+ *   webhooks.on('webhook', ({ id, approver, approved }) => (
+ *     approved ? approvals.approve(id, approver) : approvals.reject(id, approver)
+ *   ));
+ */
+class ApprovalQueue {
+  /**
+   * @param {Object} options - Queue options
+   * @param {Array<string>} options.approvers - Identities allowed to decide (default: anyone)
+   * @param {Function} options.notify - Async (request) => void that sends the approval request
+   * @param {number} options.timeout - Milliseconds to wait for a decision (default 300000)
+   */
+  constructor(options = {}) {
+    if (typeof options.notify !== 'function') {
+      throw new Error('ApprovalQueue requires a notify(request) function');
+    }
+
+    this.approvers = options.approvers ? new Set(options.approvers) : null;
+    this.notify = options.notify;
+    this.timeout = options.timeout || 300000;
+    this.pending = new Map();
+  }
+
+  /**
+   * Ask for approval and wait for the decision
+   * @param {Object} action - Description of the action, e.g. { tool, args, reason }
+   * @returns {Promise<Object>} - { approved, approver, reason }
+   */
+  async request(action) {
+    const id = crypto.randomUUID();
+    const request = {
+      id,
+      action,
+      requestedAt: new Date().toISOString(),
+      expiresAt: new Date(Date.now() + this.timeout).toISOString(),
+    };
+
+    const decision = new Promise((resolve) => {
+      const timer = setTimeout(() => this._settle(id, { approved: false, approver: null, reason: 'timed out' }), this.timeout);
+      this.pending.set(id, { request, resolve, timer });
+    });
+
+    try {
+      await this.notify(request);
+    } catch (error) {
+      this._settle(id, { approved: false, approver: null, reason: `notification failed: ${error.message}` });
+    }
+
+    return decision;
+  }
+
+  /**
+   * Approve a pending request
+   * @param {string} id - Request id
+   * @param {string} approver - Identity of the approver
+   */
+  approve(id, approver) {
+    this._decide(id, approver, { approved: true, approver, reason: null });
+  }
+
+  /**
+   * Reject a pending request
+   * @param {string} id - Request id
+   * @param {string} approver - Identity of the approver
+   * @param {string} reason - Why it was rejected
+   */
+  reject(id, approver, reason = 'rejected') {
+    this._decide(id, approver, { approved: false, approver, reason });
+  }
+
+  /**
+   * List requests waiting for a decision
+   * @param {Object} options - { cursor, limit }
+   * @returns {Object} - Page { items, nextCursor } of pending requests
+   */
+  list(options = {}) {
+    return paginate(Array.from(this.pending.values()).map(({ request }) => request), options);
+  }
+
+  _decide(id, approver, decision) {
+    if (!this.pending.has(id)) {
+      throw new Error(`No pending approval request: ${id}`);
+    }
+    if (this.approvers && !this.approvers.has(approver)) {
+      throw new Error(`${approver} is not an authorized approver`);
+    }
+    this._settle(id, decision);
+  }
+
+  _settle(id, decision) {
+    const entry = this.pending.get(id);
+    if (!entry) {
+      return;
+    }
+    clearTimeout(entry.timer);
+    this.pending.delete(id);
+    entry.resolve(decision);
+  }
+}
+
+module.exports = ApprovalQueue;
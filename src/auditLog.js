@@ -0,0 +1,164 @@
+const crypto = require('crypto');
+const { MemoryJournal } = require('./recorder');
+const { stableStringify } = require('./utils');
+const { paginate } = require('./pagination');
+
+const GENESIS_HASH = '0'.repeat(64);
+
+/**
+ * AuditLog Class
+ * Append-only log of side effects (completions, tool calls, policy denials,
+ * messages sent) for compliance review. Each entry stores the hash of the
+ * previous one, so verify() detects entries that were edited, removed or
+ * reordered after the fact.
+ *
+ * Entries are kept in a journal (Recorder's MemoryJournal or FileJournal,
+ * or any store with async append(entry) and load()). The log can be used:
+ * - directly, with record({ component, action, tags, data })
+ * - as a client observer, recording every completion and failed call
+ * - as a journal itself, e.g. PolicyEngine's auditLog or a StreamTee journal
+ */
+class AuditLog {
+  /**
+   * @param {Object} options - Audit log options
+   * @param {Object} options.journal - Storage with async append(entry) and load() (default in-memory)
+   * @param {string} options.component - Component recorded for entries appended as a journal (default 'app')
+   */
+  constructor(options = {}) {
+    this.journal = options.journal || new MemoryJournal();
+    this.component = options.component || 'app';
+    this.lastHash = null;
+    // Appends are chained so concurrent records keep a consistent order
+    this.tail = Promise.resolve();
+  }
+
+  /**
+   * Append an entry to the log
+   * @param {Object} event - Event to record
+   * @param {string} event.component - Subsystem, e.g. 'openai', 'policy', 'github'
+   * @param {string} event.action - What happened, e.g. 'chat.completions'
+   * @param {Array<string>} event.tags - Labels for later queries
+   * @param {Object} event.data - Details of the event (must be JSON-serializable)
+   * @returns {Promise<Object>} - The stored entry, including its hash
+   */
+  record(event) {
+    const append = async () => {
+      if (this.lastHash === null) {
+        const entries = await this.journal.load();
+        this.lastHash = entries.length > 0 ? entries[entries.length - 1].hash : GENESIS_HASH;
+      }
+
+      // Hash the entry as a journal will store it, so Dates, undefined array
+      // items and the like read back exactly as they were hashed
+      const entry = JSON.parse(JSON.stringify({
+        timestamp: new Date().toISOString(),
+        component: event.component,
+        action: event.action,
+        tags: event.tags || [],
+        data: event.data || {},
+        prevHash: this.lastHash,
+      }));
+      entry.hash = AuditLog.hashOf(entry);
+
+      await this.journal.append(entry);
+      this.lastHash = entry.hash;
+      return entry;
+    };
+
+    const result = this.tail.then(append);
+    this.tail = result.catch(() => {});
+    return result;
+  }
+
+  /**
+   * Journal interface: record an arbitrary object under the default component
+   * @param {Object} data - Entry data
+   */
+  async append(data) {
+    await this.record({ component: this.component, action: data.type || 'event', data });
+  }
+
+  /**
+   * Journal interface: all entries in order
+   * @returns {Promise<Array<Object>>} - Entries
+   */
+  async load() {
+    await this.tail;
+    return this.journal.load();
+  }
+
+  /**
+   * Find entries by time range, component, action or tag
+   * @param {Object} filters - { from, to, component, action, tag } plus cursor and limit
+   * @returns {Promise<Object>} - Page { items, nextCursor } of matching entries in order
+   */
+  async query(filters = {}) {
+    const { cursor, limit } = filters;
+    const from = filters.from ? new Date(filters.from).getTime() : -Infinity;
+    const to = filters.to ? new Date(filters.to).getTime() : Infinity;
+
+    const entries = (await this.load()).filter((entry) => {
+      const time = Date.parse(entry.timestamp);
+      return time >= from && time <= to
+        && (!filters.component || entry.component === filters.component)
+        && (!filters.action || entry.action === filters.action)
+        && (!filters.tag || entry.tags.includes(filters.tag));
+    });
+    return paginate(entries, { cursor, limit });
+  }
+
+  /**
+   * Check that the hash chain is intact
+   * @returns {Promise<Object>} - { valid, entries, brokenAt } where brokenAt is the index of the first bad entry
+   */
+  async verify() {
+    const entries = await this.load();
+    let prevHash = GENESIS_HASH;
+
+    for (let i = 0; i < entries.length; i++) {
+      const { hash, ...entry } = entries[i];
+      if (entry.prevHash !== prevHash || AuditLog.hashOf(entry) !== hash) {
+        return { valid: false, entries: entries.length, brokenAt: i };
+      }
+      prevHash = hash;
+    }
+
+    return { valid: true, entries: entries.length, brokenAt: null };
+  }
+
+  /**
+   * Observer hook: record a completed client call
+   * @param {Object} event - Response event
+   */
+  onResponse(event) {
+    this._observe(event, { status: 'ok', usage: event.usage || null });
+  }
+
+  /**
+   * Observer hook: record a failed client call
+   * @param {Object} event - Error event
+   */
+  onError(event) {
+    this._observe(event, { status: 'error', error: event.error ? event.error.message : null });
+  }
+
+  /**
+   * Hash an entry without its own hash field
+   * @param {Object} entry - Entry, in its JSON-serialized form
+   * @returns {string} - Hex SHA-256
+   */
+  static hashOf(entry) {
+    return crypto.createHash('sha256').update(stableStringify(entry)).digest('hex');
+  }
+
+  _observe(event, details) {
+    this.record({
+      component: event.provider || 'unknown',
+      action: event.operation || 'request',
+      tags: event.model ? [event.model] : [],
+      data: { model: event.model || null, latencyMs: event.latencyMs, ...details },
+    }).catch((error) => console.error(`Audit log write failed: ${error.message}`));
+  }
+}
+
+module.exports = AuditLog;
@@ -0,0 +1,200 @@
+const { MemoryJournal } = require('./recorder');
+
+const sleep = (ms) => new Promise((resolve) => setTimeout(resolve, ms));
+
+/**
+ * BatchJob Class
+ * Runs a large set of completion requests offline. Every outcome is written
+ * to a journal as soon as it is known, so running the job again with the
+ * same journal (e.g. a FileJournal after a crash) skips the requests that
+ * already succeeded and retries the ones that failed.
+ *
+ * With native: true and a client that supports the OpenAI Batch API
+ * (ChatGPT), pending requests are submitted as one batch and the job polls
+ * until it finishes; the batch id is journaled, so a resumed job collects the
+ * batch it already submitted instead of paying for it twice. Otherwise the
+ * requests are sent through the client's conversation() with a cap on
+ * concurrency and an optional RateLimiter.
+ *
+ * Journal entries: { type: 'result', id, content }, { type: 'error', id, error },
+ * { type: 'batch', batchId, ids } and { type: 'batch_collected', batchId }.
+ */
+class BatchJob {
+  /**
+   * @param {Array<Object>} requests - { id, message or messages, options } entries with unique ids
+   * @param {Object} options - Job options
+   * @param {Object} options.client - Client with conversation(messages, options), and submitBatch for native mode
+   * @param {Object} options.journal - Storage with async append(entry) and load() (default in-memory)
+   * @param {boolean} options.native - Use the provider's batch API when the client has one (default false)
+   * @param {number} options.concurrency - Requests in flight when not using the batch API (default 4)
+   * @param {RateLimiter} options.rateLimiter - Optional limiter applied to each request
+   * @param {number} options.pollInterval - Milliseconds between batch status checks (default 60000)
+   * @param {Function} options.sleep - Async (ms) => void used between polls (for tests)
+   */
+  constructor(requests, options = {}) {
+    if (!options.client || typeof options.client.conversation !== 'function') {
+      throw new Error('BatchJob requires a client with a conversation(messages, options) method');
+    }
+
+    const ids = new Set();
+    this.requests = requests.map((request) => {
+      if (!request.id || ids.has(request.id)) {
+        throw new Error(`Batch request ids must be unique and non-empty: ${request.id}`);
+      }
+      ids.add(request.id);
+      return {
+        id: request.id,
+        messages: request.messages || [{ role: 'user', content: request.message }],
+        options: request.options || {},
+      };
+    });
+
+    this.client = options.client;
+    this.journal = options.journal || new MemoryJournal();
+    this.native = Boolean(options.native) && typeof this.client.submitBatch === 'function';
+    this.concurrency = options.concurrency || 4;
+    this.rateLimiter = options.rateLimiter || null;
+    this.pollInterval = options.pollInterval || 60000;
+    this.sleep = options.sleep || sleep;
+  }
+
+  /**
+   * Run the requests that have not completed yet
+   * @param {Object} options - Run options
+   * @param {ProgressHandle} options.progress - Advanced per finished request; cancelling it stops new requests
+   * @returns {Promise<Object>} - { results, completed, failed } with results in request order
+   */
+  async run(options = {}) {
+    const progress = options.progress || null;
+    const state = await this._loadState();
+    const pending = this.requests.filter((request) => !this._succeeded(state, request.id));
+
+    if (progress) {
+      progress.update({ total: this.requests.length });
+      progress.advance(this.requests.length - pending.length);
+    }
+
+    const record = async (entry) => {
+      await this.journal.append(entry);
+      state.outcomes.set(entry.id, entry);
+      if (progress) {
+        progress.advance(1, entry.id);
+      }
+    };
+
+    try {
+      if (this.native) {
+        await this._runNative(pending, state, record, progress);
+      } else {
+        await this._runScheduled(pending, record, progress);
+      }
+    } catch (error) {
+      if (progress) {
+        progress.fail(error);
+      }
+      throw error;
+    }
+
+    if (progress) {
+      progress.throwIfCancelled();
+    }
+
+    const results = this.requests.map(({ id }) => {
+      const outcome = state.outcomes.get(id);
+      if (!outcome) {
+        return { id, status: 'pending' };
+      }
+      return outcome.type === 'result'
+        ? { id, status: 'completed', content: outcome.content }
+        : { id, status: 'failed', error: outcome.error };
+    });
+    const summary = {
+      results,
+      completed: results.filter((result) => result.status === 'completed').length,
+      failed: results.filter((result) => result.status === 'failed').length,
+    };
+
+    if (progress) {
+      progress.complete();
+    }
+    return summary;
+  }
+
+  async _runScheduled(pending, record, progress) {
+    const queue = [...pending];
+
+    const worker = async () => {
+      while (queue.length > 0 && !(progress && progress.cancelled)) {
+        const request = queue.shift();
+        const send = () => this.client.conversation(request.messages, request.options);
+        try {
+          const content = await (this.rateLimiter ? this.rateLimiter.schedule(send) : send());
+          await record({ type: 'result', id: request.id, content });
+        } catch (error) {
+          await record({ type: 'error', id: request.id, error: error.message });
+        }
+      }
+    };
+
+    await Promise.all(Array.from({ length: Math.min(this.concurrency, queue.length) }, worker));
+  }
+
+  async _runNative(pending, state, record, progress) {
+    let { outstanding } = state;
+
+    if (!outstanding && pending.length > 0) {
+      const batch = await this.client.submitBatch(pending);
+      outstanding = { batchId: batch.id, ids: pending.map(({ id }) => id) };
+      await this.journal.append({ type: 'batch', ...outstanding });
+    }
+    if (!outstanding) {
+      return;
+    }
+
+    let status = await this.client.getBatch(outstanding.batchId);
+    while (!status.done) {
+      if (progress && progress.cancelled) {
+        // The batch keeps running on the provider; a resumed job collects it
+        return;
+      }
+      await this.sleep(this.pollInterval);
+      status = await this.client.getBatch(outstanding.batchId);
+    }
+
+    const results = new Map((await this.client.getBatchResults(outstanding.batchId)).map((result) => [result.id, result]));
+    for (const id of outstanding.ids) {
+      const result = results.get(id);
+      if (result && result.error === undefined) {
+        await record({ type: 'result', id, content: result.content });
+      } else {
+        await record({ type: 'error', id, error: result ? result.error : `batch ${status.status} without a result` });
+      }
+    }
+    await this.journal.append({ type: 'batch_collected', batchId: outstanding.batchId });
+  }
+
+  async _loadState() {
+    const outcomes = new Map();
+    const batches = new Map();
+
+    for (const entry of await this.journal.load()) {
+      if (entry.type === 'result' || entry.type === 'error') {
+        outcomes.set(entry.id, entry);
+      } else if (entry.type === 'batch') {
+        batches.set(entry.batchId, entry);
+      } else if (entry.type === 'batch_collected') {
+        batches.delete(entry.batchId);
+      }
+    }
+
+    const [outstanding = null] = batches.values();
+    return { outcomes, outstanding };
+  }
+
+  _succeeded(state, id) {
+    const outcome = state.outcomes.get(id);
+    return Boolean(outcome) && outcome.type === 'result';
+  }
+}
+
+module.exports = BatchJob;
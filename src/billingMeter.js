@@ -0,0 +1,115 @@
+const crypto = require('crypto');
+
+/**
+ * BillingMeter Class
+ * Turns usage into metered billing events per tenant, in the shape of
+ * Stripe meter events:
+ *
+ *   {
+ *     event_name: 'ai_tokens',
+ *     identifier: '<unique id, used by Stripe to deduplicate>',
+ *     timestamp: 1717000000,
+ *     payload: { stripe_customer_id: 'cus_123', value: '1500' }
+ *   }
+ *
+ * Events are buffered and handed to a sink in batches; the sink decides
+ * where they go (the Stripe API, a queue, a database table). Token usage
+ * is metered automatically once a UsageTracker is attached; other
+ * quantities can be metered with meter().
+ */
+class BillingMeter {
+  /**
+   * @param {Object} options - Meter options
+   * @param {Function} options.sink - Async (events) => void receiving each batch
+   * @param {string} options.tokenEvent - Event name for token usage (default 'ai_tokens')
+   * @param {string} options.customerField - Payload field holding the tenant (default 'stripe_customer_id')
+   * @param {number} options.batchSize - Flush automatically once this many events are buffered (default 100)
+   */
+  constructor(options = {}) {
+    if (typeof options.sink !== 'function') {
+      throw new Error('BillingMeter requires a sink function');
+    }
+
+    this.sink = options.sink;
+    this.tokenEvent = options.tokenEvent || 'ai_tokens';
+    this.customerField = options.customerField || 'stripe_customer_id';
+    this.batchSize = options.batchSize || 100;
+    this.buffer = [];
+  }
+
+  /**
+   * Meter token usage recorded by a tracker. Only entries recorded through
+   * a withTenant() view are billable; the rest are ignored.
+   * @param {UsageTracker} usageTracker - Tracker to follow
+   * @returns {Function} - Call to stop metering
+   */
+  attach(usageTracker) {
+    return usageTracker.onRecord((entry) => {
+      const tokens = entry.promptTokens + entry.completionTokens;
+      if (entry.tenant && tokens > 0) {
+        this.meter(entry.tenant, this.tokenEvent, tokens, new Date(entry.timestamp)).catch((error) => {
+          console.error(`Billing flush failed: ${error.message}`);
+        });
+      }
+    });
+  }
+
+  /**
+   * Record a metered quantity for a tenant
+   * @param {string} tenant - Tenant identifier (e.g. Stripe customer id)
+   * @param {string} eventName - Meter event name (e.g. 'storage_bytes')
+   * @param {number} value - Quantity to bill
+   * @param {Date} at - When the usage happened (default now)
+   * @returns {Promise<Object>} - The buffered event
+   */
+  async meter(tenant, eventName, value, at = new Date()) {
+    if (!tenant) {
+      throw new Error('Billing events require a tenant');
+    }
+    if (!Number.isFinite(value) || value < 0) {
+      throw new Error(`Invalid billing value for ${eventName}: ${value}`);
+    }
+
+    const event = {
+      event_name: eventName,
+      identifier: crypto.randomUUID(),
+      timestamp: Math.floor(at.getTime() / 1000),
+      payload: { [this.customerField]: tenant, value: String(value) },
+    };
+
+    this.buffer.push(event);
+    if (this.buffer.length >= this.batchSize) {
+      await this.flush();
+    }
+    return event;
+  }
+
+  /**
+   * Send buffered events to the sink. Events stay buffered if the sink fails.
+   * @returns {Promise<number>} - Number of events sent
+   */
+  async flush() {
+    if (this.buffer.length === 0) {
+      return 0;
+    }
+
+    const batch = this.buffer.splice(0, this.buffer.length);
+    try {
+      await this.sink(batch);
+    } catch (error) {
+      this.buffer.unshift(...batch);
+      throw error;
+    }
+    return batch.length;
+  }
+
+  /**
+   * Number of events waiting to be sent
+   * @returns {number} - Buffered event count
+   */
+  get pending() {
+    return this.buffer.length;
+  }
+}
+
+module.exports = BillingMeter;
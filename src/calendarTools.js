@@ -0,0 +1,375 @@
+const https = require('https');
+
+/**
+ * Calendar tools for scheduling assistants
+ *
+ * list_events, find_free_slots and create_event work on one Google Calendar
+ * or Outlook (Microsoft Graph) calendar. Access is through OAuth: pass an
+ * accessToken, or oauth { clientId, clientSecret, refreshToken } so access
+ * tokens are fetched and refreshed a minute before they expire. Times go in
+ * and out as ISO 8601 strings; Outlook times are handled in UTC.
+ *
+ * Example:
+ *   const tools = calendarTools({
+ *     provider: 'google',
+ *     oauth: { clientId, clientSecret, refreshToken },
+ *   });
+ *   // tools: [list_events, find_free_slots, create_event], each { name, description, parameters, handler }
+ */
+
+const PROVIDERS = {
+  google: {
+    baseUrl: 'https://www.googleapis.com/calendar/v3',
+    tokenUrl: () => 'https://oauth2.googleapis.com/token',
+    scope: null,
+  },
+  outlook: {
+    baseUrl: 'https://graph.microsoft.com/v1.0',
+    tokenUrl: (oauth) => `https://login.microsoftonline.com/${encodeURIComponent(oauth.tenant || 'common')}/oauth2/v2.0/token`,
+    scope: 'offline_access Calendars.ReadWrite',
+  },
+};
+
+const MAX_EVENTS = 50;
+
+/**
+ * Find the gaps of at least a given length between busy intervals
+ * @param {Array<Object>} busy - { start, end } intervals, in any order and possibly overlapping
+ * @param {string|number} start - Start of the search window
+ * @param {string|number} end - End of the search window
+ * @param {number} durationMinutes - Shortest slot worth returning
+ * @returns {Array<Object>} - Free { start, end } slots as ISO strings, earliest first
+ */
+function freeSlots(busy, start, end, durationMinutes) {
+  const windowStart = new Date(start).getTime();
+  const windowEnd = new Date(end).getTime();
+  const duration = durationMinutes * 60000;
+  const intervals = busy
+    .map((interval) => [new Date(interval.start).getTime(), new Date(interval.end).getTime()])
+    .sort((a, b) => a[0] - b[0]);
+
+  const slots = [];
+  let cursor = windowStart;
+  for (const [busyStart, busyEnd] of intervals) {
+    if (busyStart - cursor >= duration) {
+      slots.push({ start: cursor, end: Math.min(busyStart, windowEnd) });
+    }
+    cursor = Math.max(cursor, busyEnd);
+    if (cursor >= windowEnd) {
+      break;
+    }
+  }
+  if (windowEnd - cursor >= duration) {
+    slots.push({ start: cursor, end: windowEnd });
+  }
+
+  return slots
+    .filter((slot) => slot.end - slot.start >= duration)
+    .map((slot) => ({ start: new Date(slot.start).toISOString(), end: new Date(slot.end).toISOString() }));
+}
+
+/**
+ * Build list_events, find_free_slots and create_event tools for one calendar
+ * @param {Object} options - Tool options
+ * @param {string} options.provider - 'google' or 'outlook'
+ * @param {string} options.accessToken - OAuth access token (used as is, never refreshed)
+ * @param {Object} options.oauth - { clientId, clientSecret, refreshToken, tenant } for refreshed tokens (tenant is Outlook only)
+ * @param {string} options.calendarId - Calendar to use (default 'primary')
+ * @param {boolean} options.readOnly - Leave out create_event (default false)
+ * @param {ConnectionPool} options.pool - Optional keep-alive connection pool
+ * @returns {Array<Object>} - Tool definitions { name, description, parameters, handler }
+ */
+function calendarTools(options = {}) {
+  const provider = PROVIDERS[options.provider];
+  if (!provider) {
+    throw new Error(`Unsupported calendar provider: ${options.provider}. Use 'google' or 'outlook'.`);
+  }
+  const oauth = options.oauth || null;
+  if (!options.accessToken && !(oauth && oauth.clientId && oauth.clientSecret && oauth.refreshToken)) {
+    throw new Error('calendarTools requires an accessToken or oauth { clientId, clientSecret, refreshToken }');
+  }
+
+  const calendarId = options.calendarId || 'primary';
+  const pool = options.pool || null;
+  let token = options.accessToken ? { value: options.accessToken, expiresAt: Infinity } : null;
+
+  const authorization = async () => {
+    if (!token || token.expiresAt - Date.now() < 60000) {
+      const form = {
+        grant_type: 'refresh_token',
+        client_id: oauth.clientId,
+        client_secret: oauth.clientSecret,
+        refresh_token: oauth.refreshToken,
+      };
+      if (provider.scope) {
+        form.scope = provider.scope;
+      }
+      const result = await request('POST', provider.tokenUrl(oauth), { form, pool });
+      token = { value: result.access_token, expiresAt: Date.now() + (result.expires_in || 3600) * 1000 };
+    }
+    return `Bearer ${token.value}`;
+  };
+
+  const api = async (method, path, body = null, headers = {}) => request(method, provider.baseUrl + path, {
+    body,
+    headers: { ...headers, Authorization: await authorization() },
+    pool,
+  });
+
+  const calendar = options.provider === 'google'
+    ? googleCalendar(api, calendarId)
+    : outlookCalendar(api, calendarId);
+
+  const tools = [
+    {
+      name: 'list_events',
+      description: 'List calendar events between two times, earliest first.',
+      parameters: {
+        type: 'object',
+        properties: {
+          start: { type: 'string', description: 'ISO 8601 start time' },
+          end: { type: 'string', description: 'ISO 8601 end time' },
+          limit: { type: 'integer', description: `Most events returned (default and maximum ${MAX_EVENTS})` },
+        },
+        required: ['start', 'end'],
+      },
+      handler: async ({ start, end, limit }) => {
+        const range = timeRange(start, end);
+        return calendar.listEvents(range, Math.min(Number(limit) || MAX_EVENTS, MAX_EVENTS));
+      },
+    },
+    {
+      name: 'find_free_slots',
+      description: 'Find free periods of at least the given length between two times.',
+      parameters: {
+        type: 'object',
+        properties: {
+          start: { type: 'string', description: 'ISO 8601 start time' },
+          end: { type: 'string', description: 'ISO 8601 end time' },
+          durationMinutes: { type: 'integer', description: 'Length of the meeting in minutes' },
+        },
+        required: ['start', 'end', 'durationMinutes'],
+      },
+      handler: async ({ start, end, durationMinutes }) => {
+        const range = timeRange(start, end);
+        const minutes = Number(durationMinutes);
+        if (!Number.isFinite(minutes) || minutes <= 0) {
+          throw new Error('durationMinutes must be a positive number');
+        }
+        return freeSlots(await calendar.busy(range), range.start, range.end, minutes);
+      },
+    },
+    {
+      name: 'create_event',
+      description: 'Create a calendar event and invite the attendees.',
+      parameters: {
+        type: 'object',
+        properties: {
+          title: { type: 'string' },
+          start: { type: 'string', description: 'ISO 8601 start time' },
+          end: { type: 'string', description: 'ISO 8601 end time' },
+          description: { type: 'string' },
+          location: { type: 'string' },
+          attendees: { type: 'array', items: { type: 'string' }, description: 'Email addresses' },
+        },
+        required: ['title', 'start', 'end'],
+      },
+      handler: async ({ title, start, end, description, location, attendees = [] }) => {
+        if (!title) {
+          throw new Error('Event title is required');
+        }
+        const range = timeRange(start, end);
+        return calendar.createEvent({
+          title,
+          ...range,
+          description,
+          location,
+          attendees: Array.isArray(attendees) ? attendees.map(String) : [],
+        });
+      },
+    },
+  ];
+
+  return options.readOnly ? tools.filter((tool) => tool.name !== 'create_event') : tools;
+}
+
+/**
+ * Validate a model-supplied time range
+ * @param {string} start - ISO 8601 start time
+ * @param {string} end - ISO 8601 end time
+ * @returns {Object} - { start, end } as UTC ISO strings
+ */
+function timeRange(start, end) {
+  const from = new Date(start);
+  const to = new Date(end);
+  if (Number.isNaN(from.getTime()) || Number.isNaN(to.getTime())) {
+    throw new Error(`Invalid time range: ${start} to ${end}`);
+  }
+  if (to <= from) {
+    throw new Error('End time must be after start time');
+  }
+  return { start: from.toISOString(), end: to.toISOString() };
+}
+
+/**
+ * Google Calendar API v3 calls
+ * @param {Function} api - Authorized (method, path, body) request function
+ * @param {string} calendarId - Calendar id
+ * @returns {Object} - { listEvents, busy, createEvent }
+ */
+function googleCalendar(api, calendarId) {
+  const base = `/calendars/${encodeURIComponent(calendarId)}`;
+  const toEvent = (item) => ({
+    id: item.id,
+    title: item.summary || '',
+    start: item.start.dateTime || item.start.date,
+    end: item.end.dateTime || item.end.date,
+    location: item.location || null,
+    attendees: (item.attendees || []).map((attendee) => attendee.email),
+  });
+
+  return {
+    listEvents: async ({ start, end }, limit) => {
+      const query = new URLSearchParams({
+        timeMin: start,
+        timeMax: end,
+        singleEvents: 'true',
+        orderBy: 'startTime',
+        maxResults: String(limit),
+      });
+      const result = await api('GET', `${base}/events?${query}`);
+      return (result.items || []).map(toEvent);
+    },
+    busy: async ({ start, end }) => {
+      const result = await api('POST', '/freeBusy', { timeMin: start, timeMax: end, items: [{ id: calendarId }] });
+      const entry = (result.calendars || {})[calendarId] || {};
+      return entry.busy || [];
+    },
+    createEvent: async ({ title, start, end, description, location, attendees }) => {
+      const created = await api('POST', `${base}/events`, {
+        summary: title,
+        description,
+        location,
+        start: { dateTime: start },
+        end: { dateTime: end },
+        attendees: attendees.map((email) => ({ email })),
+      });
+      return toEvent(created);
+    },
+  };
+}
+
+/**
+ * Microsoft Graph calendar calls, with every time in UTC
+ * @param {Function} api - Authorized (method, path, body, headers) request function
+ * @param {string} calendarId - Calendar id, or 'primary' for the default calendar
+ * @returns {Object} - { listEvents, busy, createEvent }
+ */
+function outlookCalendar(api, calendarId) {
+  const base = calendarId === 'primary' ? '/me/calendar' : `/me/calendars/${encodeURIComponent(calendarId)}`;
+  const utc = { Prefer: 'outlook.timezone="UTC"' };
+  // Graph returns UTC times without an offset
+  const toISO = (time) => new Date(/Z|[+-]\d\d:\d\d$/.test(time.dateTime) ? time.dateTime : `${time.dateTime}Z`).toISOString();
+  const toEvent = (item) => ({
+    id: item.id,
+    title: item.subject || '',
+    start: toISO(item.start),
+    end: toISO(item.end),
+    location: (item.location && item.location.displayName) || null,
+    attendees: (item.attendees || []).map((attendee) => attendee.emailAddress.address),
+  });
+  const view = async ({ start, end }, limit) => {
+    const query = new URLSearchParams({
+      startDateTime: start,
+      endDateTime: end,
+      $orderby: 'start/dateTime',
+      $top: String(limit),
+    });
+    const result = await api('GET', `${base}/calendarView?${query}`, null, utc);
+    return result.value || [];
+  };
+
+  return {
+    listEvents: async (range, limit) => (await view(range, limit)).map(toEvent),
+    busy: async (range) => (await view(range, 1000))
+      .filter((item) => item.showAs !== 'free')
+      .map((item) => ({ start: toISO(item.start), end: toISO(item.end) })),
+    createEvent: async ({ title, start, end, description, location, attendees }) => {
+      const created = await api('POST', `${base}/events`, {
+        subject: title,
+        body: description ? { contentType: 'text', content: description } : undefined,
+        start: { dateTime: start.replace(/Z$/, ''), timeZone: 'UTC' },
+        end: { dateTime: end.replace(/Z$/, ''), timeZone: 'UTC' },
+        location: location ? { displayName: location } : undefined,
+        attendees: attendees.map((address) => ({ emailAddress: { address }, type: 'required' })),
+      }, utc);
+      return toEvent(created);
+    },
+  };
+}
+
+/**
+ * Make an HTTPS request with a JSON or form body and parse the JSON response
+ * @param {string} method - HTTP method
+ * @param {string} url - Absolute URL
+ * @param {Object} options - { body, form, headers, pool }
+ * @returns {Promise<Object>} - Parsed response
+ */
+function request(method, url, { body = null, form = null, headers = {}, pool = null } = {}) {
+  return new Promise((resolve, reject) => {
+    const target = new URL(url);
+    const bodyStr = form ? new URLSearchParams(form).toString() : (body ? JSON.stringify(body) : null);
+
+    const options = {
+      hostname: target.hostname,
+      port: target.port || 443,
+      path: target.pathname + target.search,
+      method,
+      agent: pool ? pool.agentFor(target.protocol) : undefined,
+      headers: { Accept: 'application/json', ...headers },
+    };
+    if (bodyStr) {
+      options.headers['Content-Type'] = form ? 'application/x-www-form-urlencoded' : 'application/json';
+      options.headers['Content-Length'] = Buffer.byteLength(bodyStr);
+    }
+
+    const req = https.request(options, (res) => {
+      let data = '';
+
+      res.on('data', (chunk) => {
+        data += chunk;
+      });
+
+      res.on('end', () => {
+        let parsed = null;
+        try {
+          parsed = data ? JSON.parse(data) : {};
+        } catch {
+          parsed = null;
+        }
+        if (res.statusCode >= 200 && res.statusCode < 300) {
+          resolve(parsed || { raw: data });
+          return;
+        }
+        // API errors are { error: { message } }; OAuth token errors are { error, error_description }
+        const message = parsed && (parsed.error_description || (parsed.error && parsed.error.message) || parsed.error);
+        reject(Object.assign(new Error(`Calendar API Error: ${res.statusCode} - ${message || data}`), { statusCode: res.statusCode }));
+      });
+    });
+
+    req.on('error', (error) => {
+      reject(new Error(`Calendar Request Error: ${error.message}`));
+    });
+
+    if (bodyStr) {
+      req.write(bodyStr);
+    }
+
+    req.end();
+  });
+}
+
+module.exports = {
+  calendarTools,
+  freeSlots,
+};
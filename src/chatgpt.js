@@ -1,75 +1,379 @@
 const OpenAI = require('openai');
+const { observe } = require('./observer');
+const { runWithTools } = require('./tools');
+const { visionMessage } = require('./images');
 require('dotenv').config();
 
 /**
  * ChatGPT Wrapper Class
- * Provides an interface to interact with OpenAI's ChatGPT API
+ * Provides an interface to interact with OpenAI's ChatGPT API, either
+ * directly or through an Azure OpenAI resource
  */
 class ChatGPT {
-  constructor(apiKey = null) {
+  /**
+   * @param {string} apiKey - OpenAI API key (falls back to OPENAI_API_KEY, or AZURE_OPENAI_API_KEY with azure)
+   * @param {Object} options - Client options
+   * @param {Object|boolean} options.azure - Azure OpenAI settings, or true to read them all from the environment
+   * @param {string} options.azure.endpoint - Resource endpoint (falls back to AZURE_OPENAI_ENDPOINT)
+   * @param {string} options.azure.deployment - Default deployment (falls back to AZURE_OPENAI_DEPLOYMENT)
+   * @param {string} options.azure.apiVersion - api-version query parameter (falls back to AZURE_OPENAI_API_VERSION)
+   * @param {Object} options.azure.deployments - Deployment names keyed by model, for routing per request
+   * @param {RateLimiter} options.rateLimiter - Optional limiter, may be shared with other clients
+   * @param {ConnectionPool} options.pool - Optional keep-alive connection pool
+   * @param {Object} options.observer - Optional hooks notified of every outbound call
+   * @param {UsageTracker} options.usageTracker - Optional token and cost tracker
+   * @param {ResponseCache} options.cache - Optional response cache
+   * @param {Recorder} options.recorder - Optional recorder for capturing or replaying calls
+   */
+  constructor(apiKey = null, options = {}) {
+    const azure = options.azure ? ChatGPT._azureConfig(options.azure) : null;
+
     // Use provided API key or fall back to environment variable
-    const key = apiKey || process.env.OPENAI_API_KEY;
+    const key = apiKey || (azure ? process.env.AZURE_OPENAI_API_KEY : process.env.OPENAI_API_KEY);
     
     if (!key) {
       throw new Error(
-        'OpenAI API key is required. Please set OPENAI_API_KEY in your .env file or pass it to the constructor.'
+        azure
+          ? 'Azure OpenAI API key is required. Please set AZURE_OPENAI_API_KEY in your .env file or pass it to the constructor.'
+          : 'OpenAI API key is required. Please set OPENAI_API_KEY in your .env file or pass it to the constructor.'
       );
     }
 
-    this.client = new OpenAI({
+    this.provider = azure ? 'azure-openai' : 'openai';
+    this.azure = azure;
+    this.clientOptions = {
       apiKey: key,
-    });
+      ...(options.pool ? { httpAgent: options.pool.httpsAgent } : {}),
+    };
+    this.deploymentClients = new Map();
+    this.client = azure ? this._deploymentClient(azure.deployment) : new OpenAI(this.clientOptions);
 
     // Default model for ChatGPT (using GPT-4 for Pro account features)
     this.model = 'gpt-4';
+    this.rateLimiter = options.rateLimiter || null;
+    this.observer = options.observer || null;
+    this.usageTracker = options.usageTracker || null;
+    this.cache = options.cache || null;
+    this.recorder = options.recorder || null;
   }
 
   /**
    * Send a message to ChatGPT and get a response
    * @param {string} message - The message to send
-   * @param {Object} options - Additional options (temperature, max_tokens, etc.)
+   * @param {Object} options - Additional options (temperature, max_tokens, etc.; cache: false skips the cache; signal cancels the request)
    * @returns {Promise<string>} - The response from ChatGPT
    */
   async chat(message, options = {}) {
-    try {
-      const response = await this.client.chat.completions.create({
-        model: options.model || this.model,
-        messages: [
-          {
-            role: 'user',
-            content: message,
-          },
-        ],
-        temperature: options.temperature || 0.7,
-        max_tokens: options.max_tokens || 1000,
-        ...options,
-      });
+    const { cache, signal, ...requestOptions } = options;
 
-      return response.choices[0].message.content;
-    } catch (error) {
-      throw new Error(`ChatGPT API Error: ${error.message}`);
-    }
+    return this._createCompletion({
+      model: options.model || this.model,
+      messages: [
+        {
+          role: 'user',
+          content: message,
+        },
+      ],
+      temperature: options.temperature || 0.7,
+      max_tokens: options.max_tokens || 1000,
+      ...requestOptions,
+    }, { cache, signal });
   }
 
   /**
    * Have a conversation with context
    * @param {Array} messages - Array of message objects with role and content
-   * @param {Object} options - Additional options
+   * @param {Object} options - Additional options (cache: false skips the cache; signal cancels the request)
    * @returns {Promise<string>} - The response from ChatGPT
    */
   async conversation(messages, options = {}) {
+    const { cache, signal, ...requestOptions } = options;
+
+    return this._createCompletion({
+      model: options.model || this.model,
+      messages: messages,
+      temperature: options.temperature || 0.7,
+      max_tokens: options.max_tokens || 1000,
+      ...requestOptions,
+    }, { cache, signal });
+  }
+
+  /**
+   * Have a conversation in which the model may call registered tools
+   * @param {Array} messages - Array of message objects with role and content
+   * @param {ToolRegistry} tools - Tools the model may call
+   * @param {Object} options - Additional options (autoExecute, maxIterations, temperature, etc.; signal cancels the requests)
+   * @returns {Promise<Object>} - { content, toolCalls, messages }
+   */
+  async chatWithTools(messages, tools, options = {}) {
+    // Tool conversations are never cached, so the cache option is not forwarded
+    const { autoExecute, maxIterations, signal, ...requestOptions } = options;
+    delete requestOptions.cache;
+
+    return runWithTools(
+      async (history) => {
+        const response = await this._sendCompletion({
+          model: options.model || this.model,
+          messages: history,
+          temperature: options.temperature || 0.7,
+          max_tokens: options.max_tokens || 1000,
+          ...requestOptions,
+          tools: tools.definitions(),
+        }, signal);
+        return response.choices[0].message;
+      },
+      messages,
+      tools,
+      { autoExecute, maxIterations }
+    );
+  }
+
+  /**
+   * Ask about one or more images
+   * @param {string} message - The question or instruction
+   * @param {Array} images - Image URLs, data URLs, Buffers or { data, mimeType } objects
+   * @param {Object} options - Additional options (detail, model, temperature, max_tokens, etc.)
+   * @returns {Promise<string>} - The response from ChatGPT
+   */
+  async describeImages(message, images, options = {}) {
+    const { detail, ...requestOptions } = options;
+
+    return this.conversation([visionMessage(message, images, { detail })], {
+      model: 'gpt-4o',
+      ...requestOptions,
+    });
+  }
+
+  /**
+   * Generate images from a prompt
+   * @param {string} prompt - Description of the image
+   * @param {Object} options - Generation options (model, n, size, quality, style, response_format)
+   * @returns {Promise<Array<Object>>} - Image artifacts { url, b64, mimeType, revisedPrompt }
+   */
+  async generateImage(prompt, options = {}) {
+    const params = {
+      model: options.model || 'dall-e-3',
+      prompt,
+      n: options.n || 1,
+      size: options.size || '1024x1024',
+      ...options,
+    };
+
     try {
-      const response = await this.client.chat.completions.create({
-        model: options.model || this.model,
-        messages: messages,
-        temperature: options.temperature || 0.7,
-        max_tokens: options.max_tokens || 1000,
-        ...options,
+      const response = await this._schedule(() => observe(
+        this.observer,
+        { provider: this.provider, operation: 'images.generate', model: params.model },
+        () => (this.recorder
+          ? this.recorder.capture(this.provider, 'images.generate', params, () => this.client.images.generate(params))
+          : this.client.images.generate(params))
+      ));
+
+      return response.data.map((image) => ({
+        url: image.url || null,
+        b64: image.b64_json || null,
+        mimeType: 'image/png',
+        revisedPrompt: image.revised_prompt || null,
+      }));
+    } catch (error) {
+      throw Object.assign(new Error(`ChatGPT API Error: ${error.message}`), { statusCode: error.status });
+    }
+  }
+
+  /**
+   * Transcribe speech to text with Whisper
+   * @param {ReadStream|File} audio - Audio file stream (e.g. fs.createReadStream('call.mp3'))
+   * @param {Object} options - Transcription options (model, language, prompt, response_format, temperature)
+   * @returns {Promise<string>} - The transcript
+   */
+  async transcribe(audio, options = {}) {
+    const params = { model: 'whisper-1', ...options, file: audio };
+
+    try {
+      const response = await this._schedule(() => observe(
+        this.observer,
+        { provider: this.provider, operation: 'audio.transcriptions', model: params.model },
+        () => this.client.audio.transcriptions.create(params)
+      ));
+
+      return typeof response === 'string' ? response : response.text;
+    } catch (error) {
+      throw Object.assign(new Error(`ChatGPT API Error: ${error.message}`), { statusCode: error.status });
+    }
+  }
+
+  /**
+   * Turn text into speech
+   * @param {string} text - Text to speak
+   * @param {Object} options - Speech options (voice, model, response_format, speed; stream: true returns a stream)
+   * @returns {Promise<Buffer|ReadableStream>} - Audio bytes, or the response body stream when streaming
+   */
+  async synthesizeSpeech(text, options = {}) {
+    const { stream, ...speechOptions } = options;
+    const params = { model: 'tts-1', voice: 'alloy', ...speechOptions, input: text };
+
+    try {
+      const response = await this._schedule(() => observe(
+        this.observer,
+        { provider: this.provider, operation: 'audio.speech', model: params.model },
+        () => this.client.audio.speech.create(params)
+      ));
+
+      return stream ? response.body : Buffer.from(await response.arrayBuffer());
+    } catch (error) {
+      throw Object.assign(new Error(`ChatGPT API Error: ${error.message}`), { statusCode: error.status });
+    }
+  }
+
+  /**
+   * Check text against OpenAI's moderation endpoint
+   * @param {string} text - Text to check
+   * @param {Object} options - Moderation options (model)
+   * @returns {Promise<Object>} - Verdict { flagged, categories, scores }
+   */
+  async moderate(text, options = {}) {
+    const params = { ...options, input: text };
+
+    try {
+      const response = await this._schedule(() => observe(
+        this.observer,
+        { provider: this.provider, operation: 'moderations', model: params.model || 'default' },
+        () => this.client.moderations.create(params)
+      ));
+
+      const [result] = response.results;
+      return {
+        flagged: result.flagged,
+        categories: Object.keys(result.categories).filter((name) => result.categories[name]),
+        scores: result.category_scores,
+      };
+    } catch (error) {
+      throw Object.assign(new Error(`ChatGPT API Error: ${error.message}`), { statusCode: error.status });
+    }
+  }
+
+  /**
+   * Create embeddings
+   * @param {string|Array<string>} input - Text, or several texts
+   * @param {Object} options - Embedding options (model, dimensions; cache: false skips the cache)
+   * @returns {Promise<Array<number>|Array<Array<number>>>} - One vector, or one per text
+   */
+  async embed(input, options = {}) {
+    const { cache, ...embedOptions } = options;
+    const params = { model: 'text-embedding-3-small', ...embedOptions, input };
+    // Keyed apart from chat completions so the two can share one cache
+    const cacheKey = this.cache && cache !== false ? this.cache.keyFor(`${this.provider}:embeddings`, params) : null;
+    if (cacheKey) {
+      const cached = await this.cache.get(cacheKey);
+      if (cached !== undefined) {
+        return cached;
+      }
+    }
+
+    if (this.usageTracker) {
+      this.usageTracker.checkBudget();
+    }
+
+    let vectors;
+    try {
+      const response = await this._schedule(() => observe(
+        this.observer,
+        { provider: this.provider, operation: 'embeddings', model: params.model },
+        () => this.client.embeddings.create(params)
+      ));
+
+      if (this.usageTracker) {
+        this.usageTracker.record(params.model, response.usage);
+      }
+
+      vectors = response.data.map((item) => item.embedding);
+    } catch (error) {
+      throw Object.assign(new Error(`ChatGPT API Error: ${error.message}`), { statusCode: error.status });
+    }
+
+    const result = Array.isArray(input) ? vectors : vectors[0];
+    if (cacheKey) {
+      await this.cache.set(cacheKey, result);
+    }
+    return result;
+  }
+
+  /**
+   * Submit chat completions to the OpenAI Batch API (results within 24 hours, at a discount)
+   * @param {Array<Object>} requests - { id, messages, options } entries; ids must be unique
+   * @returns {Promise<Object>} - { id, status } of the created batch
+   */
+  async submitBatch(requests) {
+    const lines = requests.map(({ id, messages, options = {} }) => JSON.stringify({
+      custom_id: id,
+      method: 'POST',
+      url: '/v1/chat/completions',
+      body: { model: this.model, ...options, messages },
+    }));
+
+    try {
+      const file = await this.client.files.create({
+        file: await OpenAI.toFile(Buffer.from(`${lines.join('\n')}\n`), 'batch.jsonl'),
+        purpose: 'batch',
       });
+      const batch = await this.client.batches.create({
+        input_file_id: file.id,
+        endpoint: '/v1/chat/completions',
+        completion_window: '24h',
+      });
+      return { id: batch.id, status: batch.status };
+    } catch (error) {
+      throw Object.assign(new Error(`ChatGPT API Error: ${error.message}`), { statusCode: error.status });
+    }
+  }
+
+  /**
+   * Get the state of a submitted batch
+   * @param {string} batchId - Batch id returned by submitBatch
+   * @returns {Promise<Object>} - { id, status, done, requestCounts }
+   */
+  async getBatch(batchId) {
+    try {
+      const batch = await this.client.batches.retrieve(batchId);
+      return {
+        id: batch.id,
+        status: batch.status,
+        done: ['completed', 'failed', 'expired', 'cancelled'].includes(batch.status),
+        requestCounts: batch.request_counts || null,
+      };
+    } catch (error) {
+      throw Object.assign(new Error(`ChatGPT API Error: ${error.message}`), { statusCode: error.status });
+    }
+  }
 
-      return response.choices[0].message.content;
+  /**
+   * Download the results of a finished batch
+   * @param {string} batchId - Batch id returned by submitBatch
+   * @returns {Promise<Array<Object>>} - { id, content } for successes, { id, error } for failures
+   */
+  async getBatchResults(batchId) {
+    try {
+      const batch = await this.client.batches.retrieve(batchId);
+      const results = [];
+
+      for (const fileId of [batch.output_file_id, batch.error_file_id].filter(Boolean)) {
+        const text = await (await this.client.files.content(fileId)).text();
+        for (const line of text.split('\n').filter((row) => row.trim())) {
+          const { custom_id: id, response, error } = JSON.parse(line);
+          if (response && response.status_code === 200) {
+            if (this.usageTracker) {
+              this.usageTracker.record(response.body.model, response.body.usage);
+            }
+            results.push({ id, content: response.body.choices[0].message.content });
+          } else {
+            const message = error ? error.message : `${response.status_code} - ${JSON.stringify(response.body)}`;
+            results.push({ id, error: message });
+          }
+        }
+      }
+
+      return results;
     } catch (error) {
-      throw new Error(`ChatGPT API Error: ${error.message}`);
+      throw Object.assign(new Error(`ChatGPT API Error: ${error.message}`), { statusCode: error.status });
     }
   }
 
@@ -88,11 +392,130 @@ class ChatGPT {
   getAvailableModels() {
     return [
       'gpt-4',
+      'gpt-4o',
       'gpt-4-turbo-preview',
       'gpt-3.5-turbo',
       'gpt-3.5-turbo-16k',
     ];
   }
+
+  /**
+   * Create a chat completion and return the message content
+   * @param {Object} params - Chat completion request parameters
+   * @param {Object} callOptions - Per-call options (cache, signal)
+   * @returns {Promise<string>} - The response from ChatGPT
+   */
+  async _createCompletion(params, callOptions = {}) {
+    const cacheKey = this.cache && callOptions.cache !== false ? this.cache.keyFor(this.provider, params) : null;
+    if (cacheKey) {
+      const cached = await this.cache.get(cacheKey);
+      if (cached !== undefined) {
+        return cached;
+      }
+    }
+
+    const response = await this._sendCompletion(params, callOptions.signal);
+    const content = response.choices[0].message.content;
+    if (cacheKey) {
+      await this.cache.set(cacheKey, content);
+    }
+
+    return content;
+  }
+
+  /**
+   * Send a chat completion request and return the raw response
+   * @param {Object} params - Chat completion request parameters
+   * @param {AbortSignal} signal - Cancels the HTTP request
+   * @returns {Promise<Object>} - The API response
+   */
+  async _sendCompletion(params, signal = null) {
+    if (this.usageTracker) {
+      this.usageTracker.checkBudget();
+    }
+
+    try {
+      const response = await this._schedule(() => observe(
+        this.observer,
+        { provider: this.provider, operation: 'chat.completions', model: params.model },
+        () => this._request(params, signal)
+      ));
+
+      if (this.usageTracker) {
+        this.usageTracker.record(params.model, response.usage);
+      }
+
+      return response;
+    } catch (error) {
+      throw Object.assign(new Error(`ChatGPT API Error: ${error.message}`), { statusCode: error.status });
+    }
+  }
+
+  /**
+   * Call the completions endpoint, through the recorder when one is configured
+   * @param {Object} params - Chat completion request parameters
+   * @param {AbortSignal} signal - Cancels the HTTP request
+   * @returns {Promise<Object>} - The API response
+   */
+  _request(params, signal = null) {
+    const client = this._clientFor(params.model);
+    const create = () => (signal ? client.chat.completions.create(params, { signal }) : client.chat.completions.create(params));
+    if (this.recorder) {
+      return this.recorder.capture(this.provider, 'chat.completions', params, create, { signal });
+    }
+    return create();
+  }
+
+  /**
+   * Pick the client serving a model; on Azure each deployment has its own URL
+   * @param {string} model - Requested model
+   * @returns {OpenAI} - OpenAI SDK client
+   */
+  _clientFor(model) {
+    if (!this.azure || !this.azure.deployments[model]) {
+      return this.client;
+    }
+    return this._deploymentClient(this.azure.deployments[model]);
+  }
+
+  _deploymentClient(deployment) {
+    if (!this.deploymentClients.has(deployment)) {
+      const { endpoint, apiVersion } = this.azure;
+      this.deploymentClients.set(deployment, new OpenAI({
+        ...this.clientOptions,
+        baseURL: `${endpoint}/openai/deployments/${encodeURIComponent(deployment)}`,
+        defaultQuery: { 'api-version': apiVersion },
+        defaultHeaders: { 'api-key': this.clientOptions.apiKey },
+      }));
+    }
+    return this.deploymentClients.get(deployment);
+  }
+
+  static _azureConfig(azure) {
+    const settings = azure === true ? {} : azure;
+    const config = {
+      endpoint: (settings.endpoint || process.env.AZURE_OPENAI_ENDPOINT || '').replace(/\/+$/, ''),
+      deployment: settings.deployment || process.env.AZURE_OPENAI_DEPLOYMENT,
+      apiVersion: settings.apiVersion || process.env.AZURE_OPENAI_API_VERSION || '2024-02-01',
+      deployments: settings.deployments || {},
+    };
+
+    if (!config.endpoint || !config.deployment) {
+      throw new Error(
+        'Azure OpenAI requires an endpoint and a deployment. Set AZURE_OPENAI_ENDPOINT and AZURE_OPENAI_DEPLOYMENT or pass them in options.azure.'
+      );
+    }
+    return config;
+  }
+
+  /**
+   * Run a request through the rate limiter when one is configured
+   * @param {Function} task - Async function performing the request
+   * @returns {Promise<*>} - The task result
+   */
+  _schedule(task) {
+    return this.rateLimiter ? this.rateLimiter.schedule(task) : task();
+  }
 }
 
 module.exports = ChatGPT;
@@ -0,0 +1,239 @@
+const { isRetryableError } = require('./failoverProvider');
+
+/**
+ * CircuitBreaker Class
+ * Stops calling a dependency that keeps failing, so callers such as agent
+ * loops fail fast instead of waiting on timeouts:
+ * - closed: calls go through; outcomes are kept over a sliding window
+ * - open: once the failure rate over the window reaches the threshold,
+ *   calls are rejected immediately until the cooldown has passed
+ * - half-open: a limited number of trial calls go through; a success closes
+ *   the circuit, a failure opens it again, and an error that does not count
+ *   leaves it half-open
+ *
+ * Only failures that point at the dependency (server errors, rate limits,
+ * network errors and timeouts by default) count; a bad request does not.
+ * wrap() puts a breaker in front of every async method and async generator
+ * (such as Claude.stream()) of a client, and FailoverProvider reports the
+ * state of wrapped providers in getHealth().
+ */
+class CircuitBreaker {
+  /**
+   * @param {Object} options - Breaker options
+   * @param {string} options.name - Dependency name used in errors and health reports
+   * @param {number} options.failureThreshold - Failure rate (0-1) that opens the circuit (default 0.5)
+   * @param {number} options.minimumRequests - Calls in the window before the rate is judged (default 5)
+   * @param {number} options.windowSize - Most recent calls considered (default 20)
+   * @param {number} options.cooldown - Milliseconds the circuit stays open (default 30000)
+   * @param {number} options.halfOpenMax - Trial calls allowed at once while half-open (default 1)
+   * @param {Function} options.isFailure - (error) => boolean deciding whether an error counts
+   * @param {Function} options.now - Clock returning milliseconds (for tests)
+   */
+  constructor(options = {}) {
+    this.name = options.name || 'dependency';
+    this.failureThreshold = options.failureThreshold !== undefined ? options.failureThreshold : 0.5;
+    this.minimumRequests = options.minimumRequests || 5;
+    this.windowSize = options.windowSize || 20;
+    this.cooldown = options.cooldown !== undefined ? options.cooldown : 30000;
+    this.halfOpenMax = options.halfOpenMax || 1;
+    this.isFailure = options.isFailure || isRetryableError;
+    this.now = options.now || Date.now;
+
+    this.outcomes = [];
+    this.openedAt = null;
+    this.trials = 0;
+    this.rejected = 0;
+    this.listeners = [];
+    this._state = 'closed';
+  }
+
+  /**
+   * Current state, moving from open to half-open once the cooldown has passed
+   * @returns {string} - 'closed', 'open' or 'half-open'
+   */
+  get state() {
+    if (this._state === 'open' && this.now() - this.openedAt >= this.cooldown) {
+      this._transition('half-open');
+    }
+    return this._state;
+  }
+
+  /**
+   * Run a call through the breaker
+   * @param {Function} task - Async function calling the dependency
+   * @returns {Promise<*>} - The task result
+   */
+  async execute(task) {
+    const trial = this._admit();
+
+    try {
+      const result = await task();
+      this._record(true, trial);
+      return result;
+    } catch (error) {
+      this._record(this.isFailure(error) ? false : null, trial);
+      throw error;
+    } finally {
+      if (trial) {
+        this.trials--;
+      }
+    }
+  }
+
+  /**
+   * Run a streaming call through the breaker. The stream is one call, which
+   * succeeds once it has been read to the end; a consumer stopping early
+   * does not count either way.
+   * @param {Function} start - Function returning the dependency's async iterable
+   * @returns {AsyncGenerator} - The stream's items
+   */
+  async *executeStream(start) {
+    const trial = this._admit();
+    let outcome = null;
+
+    try {
+      yield* start();
+      outcome = true;
+    } catch (error) {
+      outcome = this.isFailure(error) ? false : null;
+      throw error;
+    } finally {
+      this._record(outcome, trial);
+      if (trial) {
+        this.trials--;
+      }
+    }
+  }
+
+  /**
+   * Wrap a client so every async method goes through the breaker
+   * @param {Object} client - Client (ChatGPT, Claude, GitHub, ...)
+   * @returns {Object} - Client with the same methods and a circuitBreaker property
+   */
+  wrap(client) {
+    return new Proxy(client, {
+      get: (target, property, receiver) => {
+        if (property === 'circuitBreaker') {
+          return this;
+        }
+        const value = Reflect.get(target, property, receiver);
+        // Only async methods and streams call out; setters and getters such as setModel stay synchronous
+        const kind = typeof value === 'function' && !String(property).startsWith('_') ? value.constructor.name : null;
+        if (kind === 'AsyncGeneratorFunction') {
+          return (...args) => this.executeStream(() => value.apply(target, args));
+        }
+        if (kind !== 'AsyncFunction') {
+          return value;
+        }
+        return (...args) => this.execute(() => value.apply(target, args));
+      },
+    });
+  }
+
+  /**
+   * Register a listener called on every state change
+   * @param {Function} listener - ({ name, from, to }) => void
+   * @returns {Function} - Call to remove the listener
+   */
+  onStateChange(listener) {
+    this.listeners.push(listener);
+    return () => {
+      const index = this.listeners.indexOf(listener);
+      if (index !== -1) {
+        this.listeners.splice(index, 1);
+      }
+    };
+  }
+
+  /**
+   * Get the breaker's health
+   * @returns {Object} - { name, state, failureRate, requests, rejected, retryAt }
+   */
+  getHealth() {
+    const state = this.state;
+    return {
+      name: this.name,
+      state,
+      failureRate: this._failureRate(),
+      requests: this.outcomes.length,
+      rejected: this.rejected,
+      retryAt: state === 'open' ? new Date(this.openedAt + this.cooldown).toISOString() : null,
+    };
+  }
+
+  /**
+   * Close the circuit and forget recorded outcomes
+   */
+  reset() {
+    this.outcomes = [];
+    this.openedAt = null;
+    this._transition('closed');
+  }
+
+  _admit() {
+    const state = this.state;
+    if (state === 'open' || (state === 'half-open' && this.trials >= this.halfOpenMax)) {
+      this.rejected++;
+      const retryIn = Math.max(0, Math.ceil((this.openedAt + this.cooldown - this.now()) / 1000));
+      throw Object.assign(new Error(`Circuit open for ${this.name}: retry in ${retryIn}s`), { circuitOpen: true });
+    }
+
+    const trial = state === 'half-open';
+    if (trial) {
+      this.trials++;
+    }
+    return trial;
+  }
+
+  // outcome is true for a success, false for a counted failure and null for an error that does not count
+  _record(outcome, trial) {
+    if (trial || this._state === 'half-open') {
+      if (outcome === true) {
+        this.reset();
+      } else if (outcome === false) {
+        this._open();
+      }
+      return;
+    }
+    if (outcome === null) {
+      return;
+    }
+
+    this.outcomes.push(outcome);
+    if (this.outcomes.length > this.windowSize) {
+      this.outcomes.shift();
+    }
+    if (this._state === 'closed' && this.outcomes.length >= this.minimumRequests && this._failureRate() >= this.failureThreshold) {
+      this._open();
+    }
+  }
+
+  _open() {
+    this.openedAt = this.now();
+    this._transition('open');
+  }
+
+  _failureRate() {
+    if (this.outcomes.length === 0) {
+      return 0;
+    }
+    return this.outcomes.filter((success) => !success).length / this.outcomes.length;
+  }
+
+  _transition(to) {
+    const from = this._state;
+    if (from === to) {
+      return;
+    }
+    this._state = to;
+    for (const listener of this.listeners) {
+      try {
+        listener({ name: this.name, from, to });
+      } catch (error) {
+        console.error(`Circuit breaker listener failed: ${error.message}`);
+      }
+    }
+  }
+}
+
+module.exports = CircuitBreaker;
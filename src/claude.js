@@ -0,0 +1,456 @@
+const https = require('https');
+const { StringDecoder } = require('string_decoder');
+const { observe } = require('./observer');
+const { runWithTools } = require('./tools');
+require('dotenv').config();
+
+/**
+ * Claude (Anthropic) Client Class
+ * Provides an interface to interact with Anthropic's Messages API.
+ * Accepts and returns OpenAI-style messages so it can be swapped in for
+ * ChatGPT or Grok: system messages become the `system` prompt, and tool
+ * calls and results are translated to Anthropic content blocks.
+ */
+class Claude {
+  /**
+   * @param {string} apiKey - Anthropic API key (falls back to ANTHROPIC_API_KEY)
+   * @param {Object} options - Client options
+   * @param {RateLimiter} options.rateLimiter - Optional limiter, may be shared with other clients
+   * @param {ConnectionPool} options.pool - Optional keep-alive connection pool
+   * @param {Object} options.observer - Optional hooks notified of every outbound call
+   * @param {UsageTracker} options.usageTracker - Optional token and cost tracker
+   * @param {ResponseCache} options.cache - Optional response cache
+   * @param {Recorder} options.recorder - Optional recorder for capturing or replaying calls
+   */
+  constructor(apiKey = null, options = {}) {
+    const key = apiKey || process.env.ANTHROPIC_API_KEY;
+
+    if (!key) {
+      throw new Error(
+        'Anthropic API key is required. Please set ANTHROPIC_API_KEY in your .env file or pass it to the constructor.'
+      );
+    }
+
+    this.apiKey = key;
+    this.baseUrl = 'https://api.anthropic.com/v1';
+    this.apiVersion = '2023-06-01';
+    this.model = 'claude-3-5-sonnet-20241022';
+    this.rateLimiter = options.rateLimiter || null;
+    this.pool = options.pool || null;
+    this.observer = options.observer || null;
+    this.usageTracker = options.usageTracker || null;
+    this.cache = options.cache || null;
+    this.recorder = options.recorder || null;
+  }
+
+  /**
+   * Send a message to Claude and get a response
+   * @param {string} message - The message to send
+   * @param {Object} options - Additional options (system, temperature, max_tokens, etc.; cache: false skips the cache; signal cancels the request)
+   * @returns {Promise<string>} - The response from Claude
+   */
+  async chat(message, options = {}) {
+    return this.conversation([{ role: 'user', content: message }], options);
+  }
+
+  /**
+   * Have a conversation with context
+   * @param {Array} messages - Array of message objects with role and content
+   * @param {Object} options - Additional options (system, temperature, max_tokens, etc.; cache: false skips the cache; signal cancels the request)
+   * @returns {Promise<string>} - The response from Claude
+   */
+  async conversation(messages, options = {}) {
+    const payload = this._buildPayload(messages, options);
+
+    const cacheKey = this.cache && options.cache !== false ? this.cache.keyFor('anthropic', payload) : null;
+    if (cacheKey) {
+      const cached = await this.cache.get(cacheKey);
+      if (cached !== undefined) {
+        return cached;
+      }
+    }
+
+    const response = await this._sendMessage(payload, options.signal);
+    const content = Claude.toOpenAIMessage(response).content;
+    if (cacheKey) {
+      await this.cache.set(cacheKey, content);
+    }
+
+    return content;
+  }
+
+  /**
+   * Have a conversation in which the model may call registered tools
+   * @param {Array} messages - Array of message objects with role and content
+   * @param {ToolRegistry} tools - Tools the model may call
+   * @param {Object} options - Additional options (autoExecute, maxIterations, temperature, etc.; signal cancels the requests)
+   * @returns {Promise<Object>} - { content, toolCalls, messages }
+   */
+  async chatWithTools(messages, tools, options = {}) {
+    const anthropicTools = tools.definitions().map(({ function: fn }) => ({
+      name: fn.name,
+      description: fn.description,
+      input_schema: fn.parameters,
+    }));
+
+    return runWithTools(
+      async (history) => {
+        const response = await this._sendMessage({
+          ...this._buildPayload(history, options),
+          tools: anthropicTools,
+        }, options.signal);
+        return Claude.toOpenAIMessage(response);
+      },
+      messages,
+      tools,
+      { autoExecute: options.autoExecute, maxIterations: options.maxIterations }
+    );
+  }
+
+  /**
+   * Stream a response as it is generated. The rate limiter slot and the
+   * observer's latency cover the whole stream, and usage is recorded even
+   * when the consumer stops early.
+   * @param {Array} messages - Array of message objects with role and content
+   * @param {Object} options - Additional options (system, temperature, max_tokens, etc.)
+   * @returns {AsyncGenerator<string>} - Text fragments in order
+   */
+  async *stream(messages, options = {}) {
+    const payload = { ...this._buildPayload(messages, options), stream: true };
+
+    if (this.usageTracker) {
+      this.usageTracker.checkBudget();
+    }
+
+    const usage = { input_tokens: 0, output_tokens: 0 };
+    // The limiter slot and the observed call last until the stream is consumed,
+    // not only until the response headers arrive
+    let finish;
+    const finished = new Promise((resolve, reject) => {
+      finish = { resolve, reject };
+    });
+    const response = await new Promise((resolve, reject) => {
+      this._schedule(() => observe(
+        this.observer,
+        { provider: 'anthropic', operation: 'messages.stream', model: payload.model },
+        async () => {
+          resolve(await this._openStream('/messages', payload));
+          await finished;
+          return { usage: Claude.toOpenAIUsage(usage) };
+        }
+      )).catch(reject);
+    });
+
+    const decoder = new StringDecoder('utf8');
+    let buffer = '';
+    let failure = null;
+
+    try {
+      for await (const chunk of response) {
+        // Multi-byte characters may be split across chunks
+        buffer += typeof chunk === 'string' ? chunk : decoder.write(chunk);
+        const events = buffer.split('\n\n');
+        buffer = events.pop();
+
+        for (const raw of events) {
+          const dataLine = raw.split('\n').find((line) => line.startsWith('data:'));
+          if (!dataLine) {
+            continue;
+          }
+
+          const event = JSON.parse(dataLine.slice(5).trim());
+          if (event.type === 'error') {
+            throw new Error(`Claude API Error: ${event.error?.message || 'stream error'}`);
+          }
+          if (event.type === 'message_start' && event.message.usage) {
+            usage.input_tokens = event.message.usage.input_tokens || 0;
+          }
+          if (event.type === 'message_delta' && event.usage) {
+            usage.output_tokens = event.usage.output_tokens || 0;
+          }
+          if (event.type === 'content_block_delta' && event.delta.type === 'text_delta') {
+            yield event.delta.text;
+          }
+        }
+      }
+    } catch (error) {
+      failure = error;
+      throw error;
+    } finally {
+      // Also runs when the consumer stops early, so partial usage is kept
+      if (this.usageTracker) {
+        this.usageTracker.record(payload.model, Claude.toOpenAIUsage(usage));
+      }
+      if (failure) {
+        finish.reject(failure);
+      } else {
+        finish.resolve();
+      }
+    }
+  }
+
+  /**
+   * Set the default model to use
+   * @param {string} model - The Claude model name
+   */
+  setModel(model) {
+    this.model = model;
+  }
+
+  /**
+   * Get available Claude models (for reference)
+   * @returns {Array<string>} - List of commonly available models
+   */
+  getAvailableModels() {
+    return [
+      'claude-3-5-sonnet-20241022',
+      'claude-3-5-haiku-20241022',
+      'claude-3-opus-20240229',
+    ];
+  }
+
+  /**
+   * Convert a Messages API response into an OpenAI-style assistant message
+   * @param {Object} response - Messages API response
+   * @returns {Object} - { role, content, tool_calls? }
+   */
+  static toOpenAIMessage(response) {
+    const blocks = response.content || [];
+    const text = blocks.filter((block) => block.type === 'text').map((block) => block.text).join('');
+    const toolCalls = blocks
+      .filter((block) => block.type === 'tool_use')
+      .map((block) => ({
+        id: block.id,
+        type: 'function',
+        function: { name: block.name, arguments: JSON.stringify(block.input || {}) },
+      }));
+
+    const message = { role: 'assistant', content: text || (toolCalls.length > 0 ? null : '') };
+    if (toolCalls.length > 0) {
+      message.tool_calls = toolCalls;
+    }
+    return message;
+  }
+
+  /**
+   * Convert Messages API usage into the prompt/completion shape used by UsageTracker
+   * @param {Object} usage - { input_tokens, output_tokens }
+   * @returns {Object|null} - { prompt_tokens, completion_tokens }
+   */
+  static toOpenAIUsage(usage) {
+    if (!usage) {
+      return null;
+    }
+    return { prompt_tokens: usage.input_tokens || 0, completion_tokens: usage.output_tokens || 0 };
+  }
+
+  /**
+   * Build a Messages API request body from OpenAI-style messages
+   * @param {Array} messages - Array of message objects with role and content
+   * @param {Object} options - Request options
+   * @returns {Object} - Request body
+   */
+  _buildPayload(messages, options = {}) {
+    const system = messages.filter((m) => m.role === 'system').map((m) => m.content);
+    if (options.system) {
+      system.unshift(options.system);
+    }
+
+    const payload = {
+      model: options.model || this.model,
+      messages: this._toAnthropicMessages(messages.filter((m) => m.role !== 'system')),
+      max_tokens: options.max_tokens || 1000,
+      temperature: options.temperature !== undefined ? options.temperature : 0.7,
+    };
+    if (system.length > 0) {
+      payload.system = system.join('\n\n');
+    }
+
+    return payload;
+  }
+
+  _toAnthropicMessages(messages) {
+    const result = [];
+
+    for (const message of messages) {
+      if (message.role === 'tool') {
+        const block = { type: 'tool_result', tool_use_id: message.tool_call_id, content: message.content };
+        const last = result[result.length - 1];
+        // Consecutive tool results travel together in one user turn
+        if (last && last.role === 'user' && Array.isArray(last.content) && last.content[0].type === 'tool_result') {
+          last.content.push(block);
+        } else {
+          result.push({ role: 'user', content: [block] });
+        }
+      } else if (message.role === 'assistant' && message.tool_calls) {
+        result.push({
+          role: 'assistant',
+          content: [
+            ...(message.content ? [{ type: 'text', text: message.content }] : []),
+            ...message.tool_calls.map((call) => ({
+              type: 'tool_use',
+              id: call.id,
+              name: call.function.name,
+              input: call.function.arguments ? JSON.parse(call.function.arguments) : {},
+            })),
+          ],
+        });
+      } else {
+        result.push({ role: message.role, content: message.content });
+      }
+    }
+
+    return result;
+  }
+
+  /**
+   * Send a Messages API request and return the raw response
+   * @param {Object} payload - Request body
+   * @param {AbortSignal} signal - Cancels the HTTP request
+   * @returns {Promise<Object>} - The API response
+   */
+  async _sendMessage(payload, signal = null) {
+    if (this.usageTracker) {
+      this.usageTracker.checkBudget();
+    }
+
+    const response = await this._schedule(() => observe(
+      this.observer,
+      { provider: 'anthropic', operation: 'messages', model: payload.model },
+      () => this._request('POST', '/messages', payload, signal)
+    ));
+
+    if (this.usageTracker) {
+      this.usageTracker.record(payload.model, Claude.toOpenAIUsage(response.usage));
+    }
+
+    return response;
+  }
+
+  /**
+   * Run a request through the rate limiter when one is configured
+   * @param {Function} task - Async function performing the request
+   * @returns {Promise<*>} - The task result
+   */
+  _schedule(task) {
+    return this.rateLimiter ? this.rateLimiter.schedule(task) : task();
+  }
+
+  /**
+   * Make a request to the Anthropic API, through the recorder when one is configured
+   * @param {string} method - HTTP method
+   * @param {string} path - API path
+   * @param {Object} body - Request body
+   * @param {AbortSignal} signal - Cancels the HTTP request
+   * @returns {Promise<Object>} - Parsed response
+   */
+  _request(method, path, body = null, signal = null) {
+    if (this.recorder) {
+      return this.recorder.capture('anthropic', `${method} ${path}`, body, () => this._httpRequest(method, path, body, signal), { signal });
+    }
+    return this._httpRequest(method, path, body, signal);
+  }
+
+  /**
+   * Make an HTTPS request to the Anthropic API
+   * @param {string} method - HTTP method
+   * @param {string} path - API path
+   * @param {Object} body - Request body
+   * @param {AbortSignal} signal - Cancels the HTTP request
+   * @returns {Promise<Object>} - Parsed response
+   */
+  _httpRequest(method, path, body = null, signal = null) {
+    return new Promise((resolve, reject) => {
+      const req = https.request({ ...this._requestOptions(method, path, body), signal: signal || undefined }, (res) => {
+        let data = '';
+
+        res.on('data', (chunk) => {
+          data += chunk;
+        });
+
+        res.on('end', () => {
+          try {
+            const parsed = JSON.parse(data);
+            if (res.statusCode >= 200 && res.statusCode < 300) {
+              resolve(parsed);
+            } else {
+              reject(Object.assign(new Error(`Claude API Error: ${res.statusCode} - ${parsed.error?.message || data}`), { statusCode: res.statusCode }));
+            }
+          } catch {
+            if (res.statusCode >= 200 && res.statusCode < 300) {
+              resolve({ raw: data });
+            } else {
+              reject(Object.assign(new Error(`Claude API Error: ${res.statusCode} - ${data}`), { statusCode: res.statusCode }));
+            }
+          }
+        });
+      });
+
+      req.on('error', (error) => {
+        reject(new Error(`Claude Request Error: ${error.message}`));
+      });
+
+      if (body) {
+        req.write(JSON.stringify(body));
+      }
+
+      req.end();
+    });
+  }
+
+  /**
+   * Open a streaming request and resolve with the response stream
+   * @param {string} path - API path
+   * @param {Object} body - Request body
+   * @returns {Promise<IncomingMessage>} - Server-sent event stream
+   */
+  _openStream(path, body) {
+    return new Promise((resolve, reject) => {
+      const req = https.request(this._requestOptions('POST', path, body), (res) => {
+        if (res.statusCode >= 200 && res.statusCode < 300) {
+          resolve(res);
+          return;
+        }
+
+        let data = '';
+        res.on('data', (chunk) => {
+          data += chunk;
+        });
+        res.on('end', () => {
+          reject(Object.assign(new Error(`Claude API Error: ${res.statusCode} - ${data}`), { statusCode: res.statusCode }));
+        });
+      });
+
+      req.on('error', (error) => {
+        reject(new Error(`Claude Request Error: ${error.message}`));
+      });
+
+      req.write(JSON.stringify(body));
+      req.end();
+    });
+  }
+
+  _requestOptions(method, path, body) {
+    const url = new URL(this.baseUrl + path);
+    const options = {
+      hostname: url.hostname,
+      port: url.port || 443,
+      path: url.pathname + url.search,
+      method,
+      agent: this.pool ? this.pool.agentFor(url.protocol) : undefined,
+      headers: {
+        'x-api-key': this.apiKey,
+        'anthropic-version': this.apiVersion,
+        'Content-Type': 'application/json',
+      },
+    };
+
+    if (body) {
+      options.headers['Content-Length'] = Buffer.byteLength(JSON.stringify(body));
+    }
+
+    return options;
+  }
+}
+
+module.exports = Claude;
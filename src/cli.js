@@ -0,0 +1,293 @@
+const path = require('path');
+const readline = require('readline');
+const Agent = require('./agent');
+const FailoverProvider = require('./failoverProvider');
+const McpServer = require('./mcpServer');
+const OpenAIProxy = require('./openaiProxy');
+const ReplSession = require('./replSession');
+const UsageTracker = require('./usageTracker');
+const { describeConfig } = require('./describe');
+const { diagnose, formatReport, hintFor } = require('./doctor');
+const EvalSuite = require('./evalSuite');
+
+const PROVIDERS = {
+  openai: { module: './chatgpt', env: 'OPENAI_API_KEY' },
+  xai: { module: './grok', env: 'GROK_API_KEY' },
+  anthropic: { module: './claude', env: 'ANTHROPIC_API_KEY' },
+  local: { module: './localProvider', env: 'LOCAL_MODEL_URL' },
+};
+
+const USAGE = `Usage: aitm <command> [options]
+
+Commands:
+  chat [--provider name] [--model name] <message>   Send one message and print the reply
+  repl [--provider name] [--model name] [--system text] [--tools module]
+                                                    Start an interactive agent session
+  config validate                                   Show which providers are configured
+  doctor [--timeout ms]                             Check that each configured provider works
+  eval <suite module> [--provider a,b] [--model name]
+                                                    Run an EvalSuite and fail when a case fails
+  mcp --tools module [--port n]                     Serve tools over MCP (stdio, or HTTP with --port and AITM_MCP_TOKEN)
+  proxy [--port n]                                  Serve an OpenAI-compatible API over all configured providers
+
+Providers: ${Object.keys(PROVIDERS).join(', ')} (default: the first one configured)
+`;
+
+const NO_PROVIDER = 'No provider is configured. Run `aitm config validate` for details.\n';
+
+/**
+ * Split command-line arguments into positionals and --name value options
+ * @param {Array<string>} args - Arguments after the command
+ * @returns {Object} - { positionals, options }
+ */
+function parseArgs(args) {
+  const positionals = [];
+  const options = {};
+
+  for (let i = 0; i < args.length; i++) {
+    const arg = args[i];
+    if (arg.startsWith('--')) {
+      const [name, inline] = arg.slice(2).split(/=(.*)/s);
+      if (inline !== undefined) {
+        options[name] = inline;
+      } else if (i + 1 < args.length && !args[i + 1].startsWith('--')) {
+        options[name] = args[++i];
+      } else {
+        options[name] = true;
+      }
+    } else {
+      positionals.push(arg);
+    }
+  }
+
+  return { positionals, options };
+}
+
+/**
+ * Create a client for a provider, loading its module on demand
+ * @param {string} provider - Provider name (openai, xai, anthropic, local)
+ * @param {Object} options - Client options (model, ...)
+ * @returns {Object} - AI client
+ */
+function createClient(provider, options = {}) {
+  const entry = PROVIDERS[provider];
+  if (!entry) {
+    throw new Error(`Unknown provider: ${provider}. Use ${Object.keys(PROVIDERS).join(', ')}.`);
+  }
+  const Client = require(entry.module);
+  return new Client(null, options);
+}
+
+/**
+ * Pick the first provider with credentials in the environment
+ * @returns {string|null} - Provider name
+ */
+function defaultProvider() {
+  return Object.keys(PROVIDERS).find((name) => process.env[PROVIDERS[name].env]) || null;
+}
+
+/**
+ * Run the CLI
+ * @param {Array<string>} argv - Arguments after the program name
+ * @param {Object} io - Streams and dependencies, replaceable for tests
+ * @param {Object} io.stdout - Writable for normal output
+ * @param {Object} io.stderr - Writable for errors
+ * @param {Function} io.createClient - (provider, options) => client
+ * @param {AsyncIterable<string>} io.input - Lines for the repl (default: the terminal)
+ * @returns {Promise<number>} - Exit code
+ */
+async function run(argv, io = {}) {
+  const stdout = io.stdout || process.stdout;
+  const stderr = io.stderr || process.stderr;
+  const makeClient = io.createClient || createClient;
+  const [command, ...rest] = argv;
+  const { positionals, options } = parseArgs(rest);
+
+  try {
+    switch (command) {
+      case 'chat': {
+        const message = positionals.join(' ');
+        if (!message) {
+          stderr.write('Usage: aitm chat [--provider name] [--model name] <message>\n');
+          return 2;
+        }
+        const provider = options.provider || defaultProvider();
+        if (!provider) {
+          stderr.write(NO_PROVIDER);
+          return 1;
+        }
+
+        const client = makeClient(provider, options.model ? { model: options.model } : {});
+        stdout.write(`${await client.chat(message)}\n`);
+        return 0;
+      }
+
+      case 'repl': {
+        const provider = options.provider || defaultProvider();
+        if (!provider) {
+          stderr.write(NO_PROVIDER);
+          return 1;
+        }
+
+        const usageTracker = new UsageTracker();
+        const client = makeClient(provider, { usageTracker, ...(options.model ? { model: options.model } : {}) });
+        const agent = new Agent({
+          client,
+          tools: options.tools ? require(path.resolve(options.tools)) : undefined,
+          systemPrompt: typeof options.system === 'string' ? options.system : undefined,
+          memory: true,
+        });
+        const session = new ReplSession({ agent, usageTracker });
+        const lines = io.input || readline.createInterface({ input: process.stdin, crlfDelay: Infinity });
+
+        stdout.write('aitm repl - type /help for commands, /exit to leave\n> ');
+        for await (const line of lines) {
+          try {
+            stdout.write(await session.handle(line));
+          } catch (error) {
+            stderr.write(`Error: ${error.message}\n`);
+          }
+          if (session.closed) {
+            break;
+          }
+          stdout.write('> ');
+        }
+        if (typeof lines.close === 'function') {
+          lines.close();
+        }
+        return 0;
+      }
+
+      case 'eval': {
+        if (!positionals[0]) {
+          stderr.write('Usage: aitm eval <suite module> [--provider a,b] [--model name]\n');
+          return 2;
+        }
+        const suite = require(path.resolve(positionals[0]));
+        if (!(suite instanceof EvalSuite)) {
+          stderr.write(`${positionals[0]} must export an EvalSuite\n`);
+          return 2;
+        }
+        const providers = typeof options.provider === 'string' ? options.provider.split(',') : [defaultProvider()].filter(Boolean);
+        if (providers.length === 0) {
+          stderr.write(NO_PROVIDER);
+          return 1;
+        }
+
+        const targets = {};
+        for (const provider of providers) {
+          targets[provider] = makeClient(provider, options.model ? { model: options.model } : {});
+        }
+        const report = await suite.run(targets);
+        stdout.write(EvalSuite.formatReport(report));
+        return report.passed ? 0 : 1;
+      }
+
+      case 'mcp': {
+        if (typeof options.tools !== 'string') {
+          stderr.write('Usage: aitm mcp --tools module [--port n]\n');
+          return 2;
+        }
+        if (options.port && !process.env.AITM_MCP_TOKEN) {
+          stderr.write('Set AITM_MCP_TOKEN before serving MCP over HTTP; clients send it as a bearer token.\n');
+          return 1;
+        }
+        const server = new McpServer({
+          tools: require(path.resolve(options.tools)),
+          token: process.env.AITM_MCP_TOKEN,
+          allowedOrigins: process.env.AITM_MCP_ALLOWED_ORIGINS ? process.env.AITM_MCP_ALLOWED_ORIGINS.split(',') : [],
+        });
+
+        if (options.port) {
+          await server.listen(Number(options.port));
+          stderr.write(`MCP server listening on http://127.0.0.1:${options.port}\n`);
+          // Keep serving until the process is stopped
+          await new Promise(() => {});
+        }
+
+        // stdout carries the protocol, so nothing else may be written to it
+        const lines = io.input || readline.createInterface({ input: process.stdin, crlfDelay: Infinity });
+        await server.serve(lines, stdout);
+        return 0;
+      }
+
+      case 'proxy': {
+        const configured = Object.keys(PROVIDERS).filter((name) => process.env[PROVIDERS[name].env]);
+        if (configured.length === 0) {
+          stderr.write(NO_PROVIDER);
+          return 1;
+        }
+
+        if (!process.env.AITM_PROXY_API_KEY) {
+          stderr.write('Set AITM_PROXY_API_KEY before starting the proxy; clients send it as their OpenAI API key.\n');
+          return 1;
+        }
+
+        const usageTracker = new UsageTracker();
+        const client = new FailoverProvider(configured.map((name) => ({ name, client: makeClient(name, { usageTracker }) })));
+        const proxy = new OpenAIProxy({ client, apiKeys: [process.env.AITM_PROXY_API_KEY] });
+        const port = Number(options.port) || 8080;
+        await proxy.listen(port);
+        stdout.write(`OpenAI-compatible proxy on http://127.0.0.1:${port}/v1 (providers: ${configured.join(', ')})\n`);
+        // Keep serving until the process is stopped
+        await new Promise(() => {});
+        return 0;
+      }
+
+      case 'config': {
+        if (positionals[0] !== 'validate') {
+          stderr.write('Usage: aitm config validate\n');
+          return 2;
+        }
+        const { providers } = describeConfig();
+        for (const [name, { configured, env: variables }] of Object.entries(providers)) {
+          const missing = Object.keys(variables).filter((variable) => !variables[variable]);
+          stdout.write(`${configured ? '✓' : '✗'} ${name}${missing.length > 0 && configured ? ` (unset: ${missing.join(', ')})` : ''}\n`);
+        }
+        const anyConfigured = Object.values(providers).some((provider) => provider.configured);
+        if (!anyConfigured) {
+          stderr.write('No provider is configured. Set an API key in your .env file.\n');
+        }
+        return anyConfigured ? 0 : 1;
+      }
+
+      case 'doctor': {
+        const configured = Object.keys(PROVIDERS).filter((name) => process.env[PROVIDERS[name].env]);
+        const clients = {};
+        const failures = [];
+        for (const name of configured) {
+          try {
+            clients[name] = makeClient(name);
+          } catch (error) {
+            failures.push({ name, ok: false, error: error.message, hint: hintFor(error.message) });
+          }
+        }
+
+        const results = await diagnose(clients, { timeout: Number(options.timeout) || undefined });
+        const report = [...failures, ...results];
+        stdout.write(formatReport(report));
+        return report.length > 0 && report.every((result) => result.ok) ? 0 : 1;
+      }
+
+      case undefined:
+      case 'help':
+      case '--help':
+        stdout.write(USAGE);
+        return 0;
+
+      default:
+        stderr.write(`Unknown command: ${command}\n\n${USAGE}`);
+        return 2;
+    }
+  } catch (error) {
+    stderr.write(`Error: ${error.message}\n`);
+    return 1;
+  }
+}
+
+module.exports = {
+  run,
+  parseArgs,
+  createClient,
+  defaultProvider,
+};
@@ -0,0 +1,50 @@
+const https = require('https');
+const http = require('http');
+
+/**
+ * ConnectionPool Class
+ * Keep-alive HTTP(S) agents that can be shared by every client of an
+ * application so sockets are reused instead of reopened per request.
+ */
+class ConnectionPool {
+  /**
+   * @param {Object} options - Pool options
+   * @param {number} options.maxSockets - Maximum open sockets per host
+   * @param {number} options.maxFreeSockets - Maximum idle sockets kept per host
+   * @param {number} options.idleTimeout - Milliseconds before an idle socket is closed
+   */
+  constructor(options = {}) {
+    const { maxSockets = 50, maxFreeSockets = 10, idleTimeout = 30000 } = options;
+
+    this.config = { maxSockets, maxFreeSockets, idleTimeout };
+
+    const agentOptions = {
+      keepAlive: true,
+      maxSockets,
+      maxFreeSockets,
+      timeout: idleTimeout,
+    };
+
+    this.httpsAgent = new https.Agent(agentOptions);
+    this.httpAgent = new http.Agent(agentOptions);
+  }
+
+  /**
+   * Get the agent matching a URL protocol
+   * @param {string} protocol - URL protocol ('https:' or 'http:')
+   * @returns {http.Agent} - The pooled agent
+   */
+  agentFor(protocol) {
+    return protocol === 'http:' ? this.httpAgent : this.httpsAgent;
+  }
+
+  /**
+   * Close all pooled sockets
+   */
+  destroy() {
+    this.httpsAgent.destroy();
+    this.httpAgent.destroy();
+  }
+}
+
+module.exports = ConnectionPool;
@@ -0,0 +1,258 @@
+const SUMMARY_PREFIX = 'Summary of the earlier conversation:';
+
+/**
+ * Text a message sends: its content (a string, text parts, or null for
+ * assistant tool calls) plus the names and arguments of its tool calls
+ * @param {Object} message - Chat message
+ * @returns {string} - Message text
+ */
+function messageText(message) {
+  let text = '';
+  if (typeof message.content === 'string') {
+    text = message.content;
+  } else if (Array.isArray(message.content)) {
+    text = message.content.map((part) => (typeof part === 'string' ? part : part.text || '')).join('');
+  }
+  for (const call of message.tool_calls || []) {
+    text += call.function ? `${call.function.name}${call.function.arguments || ''}` : '';
+  }
+  return text;
+}
+
+/**
+ * Conversation Class
+ * Keeps the message history of a multi-turn chat with any client exposing
+ * conversation(messages, options) (ChatGPT, Grok, ...), and keeps it inside
+ * a token budget by dropping or summarizing the oldest turns.
+ */
+class Conversation {
+  /**
+   * @param {Object} client - AI client with a conversation(messages, options) method
+   * @param {Object} options - Conversation options
+   * @param {string} options.systemPrompt - System message kept at the start of the history
+   * @param {number} options.maxTokens - Token budget for the history sent with each request
+   * @param {string} options.strategy - 'truncate' to drop old turns or 'summarize' to condense them
+   * @param {number} options.keepRecent - Number of recent messages never summarized
+   * @param {Function} options.summarizer - Optional async (messages) => summary text
+   */
+  constructor(client, options = {}) {
+    if (!client || typeof client.conversation !== 'function') {
+      throw new Error('Conversation requires a client with a conversation(messages, options) method');
+    }
+
+    const { strategy = 'truncate' } = options;
+    if (!['truncate', 'summarize'].includes(strategy)) {
+      throw new Error(`Unsupported conversation strategy: ${strategy}. Use 'truncate' or 'summarize'.`);
+    }
+
+    this.client = client;
+    this.maxTokens = options.maxTokens || 4000;
+    this.strategy = strategy;
+    this.keepRecent = options.keepRecent !== undefined ? options.keepRecent : 4;
+    this.summarizer = options.summarizer || null;
+    this.messages = [];
+
+    if (options.systemPrompt) {
+      this.messages.push({ role: 'system', content: options.systemPrompt });
+    }
+  }
+
+  /**
+   * Append a message to the history
+   * @param {string} role - 'system', 'user' or 'assistant'
+   * @param {string} content - Message text
+   */
+  addMessage(role, content) {
+    this.messages.push({ role, content });
+  }
+
+  /**
+   * Send a user message and record the assistant reply
+   * @param {string} content - User message
+   * @param {Object} options - Options passed through to the client
+   * @returns {Promise<string>} - The assistant reply; on failure the history is left as it was
+   */
+  async send(content, options = {}) {
+    const previous = this.messages.slice();
+    this.addMessage('user', content);
+
+    let reply;
+    try {
+      await this.fitBudget();
+      reply = await this.client.conversation(this.messages.map((m) => ({ ...m })), options);
+    } catch (error) {
+      // Otherwise a retry would send the user message twice in a row
+      this.messages = previous;
+      throw error;
+    }
+
+    this.addMessage('assistant', reply);
+    return reply;
+  }
+
+  /**
+   * Estimate the token count of a list of messages
+   * @param {Array} messages - Messages to measure (defaults to the history)
+   * @returns {number} - Approximate tokens (about four characters per token)
+   */
+  estimateTokens(messages = this.messages) {
+    return messages.reduce((total, message) => total + Math.ceil(messageText(message).length / 4) + 4, 0);
+  }
+
+  /**
+   * Shrink the history until it fits the token budget
+   */
+  async fitBudget() {
+    if (this.estimateTokens() <= this.maxTokens) {
+      return;
+    }
+
+    if (this.strategy === 'summarize') {
+      await this._summarizeOlderTurns();
+    }
+
+    // Drop the oldest non-system messages, always keeping the latest one. An
+    // assistant message with tool calls goes together with its tool replies,
+    // which the API rejects on their own
+    while (this.estimateTokens() > this.maxTokens) {
+      const index = this.messages.findIndex((m) => m.role !== 'system');
+      if (index === -1) {
+        break;
+      }
+      let end = index + 1;
+      while (end < this.messages.length && this.messages[end].role === 'tool') {
+        end++;
+      }
+      if (end >= this.messages.length) {
+        break;
+      }
+      this.messages.splice(index, end - index);
+    }
+  }
+
+  /**
+   * Remove all messages except the system prompt
+   */
+  clear() {
+    this.messages = this.messages.filter((m) => m.role === 'system' && !m.content.startsWith(SUMMARY_PREFIX));
+  }
+
+  /**
+   * Serialize the conversation
+   * @returns {Object} - Plain object with settings and messages
+   */
+  toJSON() {
+    return {
+      maxTokens: this.maxTokens,
+      strategy: this.strategy,
+      keepRecent: this.keepRecent,
+      messages: this.messages.map((m) => ({ ...m })),
+    };
+  }
+
+  /**
+   * Restore a conversation from toJSON() output
+   * @param {Object} data - Serialized conversation
+   * @param {Object} client - AI client to use
+   * @param {Object} options - Extra options (e.g. summarizer)
+   * @returns {Conversation} - Restored conversation
+   */
+  static fromJSON(data, client, options = {}) {
+    const conversation = new Conversation(client, {
+      maxTokens: data.maxTokens,
+      strategy: data.strategy,
+      keepRecent: data.keepRecent,
+      ...options,
+    });
+    conversation.messages = data.messages.map((m) => ({ ...m }));
+    return conversation;
+  }
+
+  /**
+   * Capture the conversation state for a Snapshot
+   * @returns {Object} - Serialized conversation
+   */
+  snapshot() {
+    return this.toJSON();
+  }
+
+  /**
+   * Replace the conversation state with a captured one
+   * @param {Object} state - Output of snapshot() or toJSON()
+   */
+  restore(state) {
+    this.maxTokens = state.maxTokens;
+    this.strategy = state.strategy;
+    this.keepRecent = state.keepRecent;
+    this.messages = state.messages.map((m) => ({ ...m }));
+  }
+
+  /**
+   * Persist the conversation to a store with async set(key, value)
+   * @param {Object} store - Store such as ResponseCache.MemoryStore or a database adapter
+   * @param {string} id - Conversation identifier
+   */
+  async save(store, id) {
+    await store.set(`conversation:${id}`, JSON.stringify(this.toJSON()));
+  }
+
+  /**
+   * Load a conversation from a store with async get(key)
+   * @param {Object} store - Store used with save()
+   * @param {string} id - Conversation identifier
+   * @param {Object} client - AI client to use
+   * @param {Object} options - Extra options (e.g. summarizer)
+   * @returns {Promise<Conversation|null>} - The conversation, or null if not found
+   */
+  static async load(store, id, client, options = {}) {
+    const stored = await store.get(`conversation:${id}`);
+    if (stored === undefined || stored === null) {
+      return null;
+    }
+    return Conversation.fromJSON(JSON.parse(stored), client, options);
+  }
+
+  async _summarizeOlderTurns() {
+    // Never summarize the latest message, it is the one being answered
+    let cutoff = Math.max(Math.min(this.messages.length - this.keepRecent, this.messages.length - 1), 0);
+    // Keep tool replies together with the assistant message that called them
+    while (cutoff > 0 && this.messages[cutoff].role === 'tool') {
+      cutoff--;
+    }
+    const older = this.messages
+      .slice(0, cutoff)
+      .filter((m) => m.role !== 'system' || messageText(m).startsWith(SUMMARY_PREFIX));
+
+    if (older.length === 0) {
+      return;
+    }
+
+    const summary = await this._summarize(older);
+    const systemMessages = this.messages
+      .slice(0, cutoff)
+      .filter((m) => m.role === 'system' && !messageText(m).startsWith(SUMMARY_PREFIX));
+
+    this.messages = [
+      ...systemMessages,
+      { role: 'system', content: `${SUMMARY_PREFIX} ${summary}` },
+      ...this.messages.slice(cutoff),
+    ];
+  }
+
+  _summarize(messages) {
+    if (this.summarizer) {
+      return this.summarizer(messages);
+    }
+
+    const transcript = messages.map((m) => `${m.role}: ${messageText(m)}`).join('\n');
+    return this.client.conversation([
+      {
+        role: 'system',
+        content: 'Summarize the conversation below in a few sentences, keeping facts, decisions and open questions.',
+      },
+      { role: 'user', content: transcript },
+    ]);
+  }
+}
+
+module.exports = Conversation;
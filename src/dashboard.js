@@ -0,0 +1,177 @@
+const http = require('http');
+const { MAX_LIMIT } = require('./pagination');
+
+const escapeHtml = (value) => String(value === null || value === undefined ? '' : value)
+  .replace(/&/g, '&amp;')
+  .replace(/</g, '&lt;')
+  .replace(/>/g, '&gt;')
+  .replace(/"/g, '&quot;');
+
+const startOfToday = () => {
+  const midnight = new Date();
+  midnight.setHours(0, 0, 0, 0);
+  return midnight;
+};
+
+/**
+ * Dashboard Class
+ * Minimal operator status page built from the components an application
+ * already has: live runs (ProgressHandle), rate limiter queue depth,
+ * scheduled jobs, recent errors (Metrics), spend today (UsageTracker) and
+ * provider health (FailoverProvider). Every component is optional.
+ *
+ * handler() serves GET / as HTML and GET /status.json as JSON, and can be
+ * mounted in an existing server (e.g. app.use('/dashboard', dashboard.handler()));
+ * listen() starts a standalone server.
+ */
+class Dashboard {
+  /**
+   * @param {Object} options - Dashboard sources
+   * @param {Metrics} options.metrics - Source of recent errors
+   * @param {UsageTracker} options.usageTracker - Source of spend
+   * @param {RateLimiter} options.rateLimiter - Source of queue depth
+   * @param {Scheduler} options.scheduler - Source of scheduled jobs
+   * @param {FailoverProvider} options.failover - Source of provider health
+   * @param {string} options.title - Page title (default 'AI-Time-Machines')
+   * @param {number} options.refreshSeconds - Page auto-refresh interval (default 5)
+   */
+  constructor(options = {}) {
+    this.metrics = options.metrics || null;
+    this.usageTracker = options.usageTracker || null;
+    this.rateLimiter = options.rateLimiter || null;
+    this.scheduler = options.scheduler || null;
+    this.failover = options.failover || null;
+    this.title = options.title || 'AI-Time-Machines';
+    this.refreshSeconds = options.refreshSeconds || 5;
+    this.runs = new Map();
+  }
+
+  /**
+   * Show a long-running operation on the dashboard
+   * @param {string} id - Run identifier
+   * @param {ProgressHandle} progress - Progress handle of the run
+   */
+  track(id, progress) {
+    this.runs.set(id, progress);
+  }
+
+  /**
+   * Stop showing a run
+   * @param {string} id - Run identifier
+   */
+  untrack(id) {
+    this.runs.delete(id);
+  }
+
+  /**
+   * Collect the current status from every configured source
+   * @returns {Object} - { generatedAt, runs, queue, jobs, errors, spendToday, providers }
+   */
+  status() {
+    return {
+      generatedAt: new Date().toISOString(),
+      runs: Array.from(this.runs.entries()).map(([id, progress]) => ({ id, ...progress.toJSON() })),
+      queue: this.rateLimiter ? this.rateLimiter.getStats() : null,
+      jobs: this.scheduler ? this.scheduler.list({ limit: MAX_LIMIT }).items : [],
+      errors: this.metrics ? [...this.metrics.recentErrors].reverse() : [],
+      spendToday: this.usageTracker ? this.usageTracker.getTotals(startOfToday()) : null,
+      providers: this.failover ? this.failover.getHealth() : [],
+    };
+  }
+
+  /**
+   * Render the status as an HTML page
+   * @returns {string} - HTML document
+   */
+  render() {
+    const status = this.status();
+    const table = (headings, rows) => {
+      if (rows.length === 0) {
+        return '<p class="empty">None</p>';
+      }
+      const head = headings.map((heading) => `<th>${escapeHtml(heading)}</th>`).join('');
+      const body = rows
+        .map((row) => `<tr>${row.map((cell) => `<td>${escapeHtml(cell)}</td>`).join('')}</tr>`)
+        .join('');
+      return `<table><tr>${head}</tr>${body}</table>`;
+    };
+    const percent = (value) => (value === null ? '' : `${value.toFixed(1)}%`);
+    const seconds = (ms) => (ms === null ? '' : `${Math.round(ms / 1000)}s`);
+
+    const sections = [
+      ['Runs', table(
+        ['Run', 'Status', 'Stage', 'Progress', 'ETA'],
+        status.runs.map((run) => [run.id, run.status, run.stage, percent(run.percent), seconds(run.etaMs)])
+      )],
+      ['Queue', status.queue
+        ? table(['In flight', 'Queued'], [[status.queue.inFlight, status.queue.queued]])
+        : '<p class="empty">No rate limiter</p>'],
+      ['Spend today', status.spendToday
+        ? table(['Requests', 'Tokens', 'Cost (USD)'], [[status.spendToday.requests, status.spendToday.totalTokens, status.spendToday.cost.toFixed(4)]])
+        : '<p class="empty">No usage tracker</p>'],
+      ['Providers', table(
+        ['Provider', 'Healthy', 'Circuit', 'Served', 'Failures', 'Last error'],
+        status.providers.map((p) => [p.name, p.healthy ? 'yes' : 'no', p.circuit, p.served, p.failures, p.lastError])
+      )],
+      ['Scheduled jobs', table(
+        ['Job', 'Last run', 'Next run', 'Last error'],
+        status.jobs.map((job) => [job.name, job.lastRun && new Date(job.lastRun).toISOString(), job.nextRun && new Date(job.nextRun).toISOString(), job.lastError])
+      )],
+      ['Recent errors', table(
+        ['Time', 'Provider', 'Model', 'Message'],
+        status.errors.map((error) => [error.timestamp, error.provider, error.model, error.message])
+      )],
+    ];
+
+    return [
+      '<!DOCTYPE html>',
+      '<html><head><meta charset="utf-8">',
+      `<meta http-equiv="refresh" content="${this.refreshSeconds}">`,
+      `<title>${escapeHtml(this.title)}</title>`,
+      '<style>body{font-family:sans-serif;margin:2em}table{border-collapse:collapse}th,td{border:1px solid #ccc;padding:4px 8px;text-align:left}.empty{color:#888}</style>',
+      '</head><body>',
+      `<h1>${escapeHtml(this.title)}</h1>`,
+      `<p>Updated ${escapeHtml(status.generatedAt)}</p>`,
+      ...sections.map(([heading, content]) => `<h2>${escapeHtml(heading)}</h2>${content}`),
+      '</body></html>',
+    ].join('\n');
+  }
+
+  /**
+   * Create a request handler serving the dashboard
+   * @returns {Function} - (req, res) handler for http.createServer or Express
+   */
+  handler() {
+    return (req, res) => {
+      const path = (req.url || '/').split('?')[0];
+
+      if (req.method === 'GET' && path === '/status.json') {
+        res.writeHead(200, { 'Content-Type': 'application/json' });
+        res.end(JSON.stringify(this.status()));
+      } else if (req.method === 'GET' && path === '/') {
+        res.writeHead(200, { 'Content-Type': 'text/html; charset=utf-8' });
+        res.end(this.render());
+      } else {
+        res.writeHead(404);
+        res.end();
+      }
+    };
+  }
+
+  /**
+   * Start a standalone dashboard server
+   * @param {number} port - Port to listen on
+   * @param {string} host - Interface to bind
+   * @returns {Promise<http.Server>} - The listening server
+   */
+  listen(port = 9465, host = '127.0.0.1') {
+    const server = http.createServer(this.handler());
+
+    return new Promise((resolve, reject) => {
+      server.once('error', reject);
+      server.listen(port, host, () => resolve(server));
+    });
+  }
+}
+
+module.exports = Dashboard;
@@ -0,0 +1,62 @@
+const { version } = require('../package.json');
+
+/**
+ * Environment variables read by each provider. Values are never reported,
+ * only whether they are set.
+ */
+const PROVIDER_ENV = {
+  openai: ['OPENAI_API_KEY'],
+  'azure-openai': ['AZURE_OPENAI_API_KEY', 'AZURE_OPENAI_ENDPOINT', 'AZURE_OPENAI_DEPLOYMENT', 'AZURE_OPENAI_API_VERSION'],
+  xai: ['GROK_API_KEY'],
+  anthropic: ['ANTHROPIC_API_KEY'],
+  local: ['LOCAL_MODEL_URL'],
+  openclaw: ['OPENCLAW_API_KEY'],
+  replit: ['REPLIT_API_TOKEN'],
+  github: ['GITHUB_TOKEN', 'GITHUB_APP_ID', 'GITHUB_APP_PRIVATE_KEY', 'GITHUB_APP_INSTALLATION_ID'],
+};
+
+const CLIENT_FEATURES = ['rateLimiter', 'pool', 'observer', 'usageTracker', 'cache', 'recorder'];
+
+/**
+ * Describe the runtime configuration for support and debugging.
+ * Secrets are never included: environment variables are reported as set or
+ * unset, and clients only report their model, endpoint and attached features.
+ * @param {Object} clients - Client instances keyed by a label (e.g. { chat: chatgpt })
+ * @returns {Object} - { version, runtime, providers, clients }
+ */
+function describeConfig(clients = {}) {
+  const providers = {};
+  for (const [provider, variables] of Object.entries(PROVIDER_ENV)) {
+    const env = Object.fromEntries(variables.map((name) => [name, Boolean(process.env[name])]));
+    providers[provider] = {
+      configured: Boolean(process.env[variables[0]]),
+      env,
+    };
+  }
+
+  const described = {};
+  for (const [label, client] of Object.entries(clients)) {
+    described[label] = {
+      type: client.constructor ? client.constructor.name : typeof client,
+      provider: client.provider || null,
+      model: client.model || null,
+      baseUrl: client.baseUrl || (client.azure ? client.azure.endpoint : null),
+      features: Object.fromEntries(CLIENT_FEATURES.map((feature) => [feature, Boolean(client[feature])])),
+    };
+  }
+
+  return {
+    version,
+    runtime: {
+      node: process.version,
+      platform: process.platform,
+      env: process.env.NODE_ENV || null,
+    },
+    providers,
+    clients: described,
+  };
+}
+
+module.exports = {
+  describeConfig,
+};
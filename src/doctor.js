@@ -0,0 +1,117 @@
+/**
+ * Environment diagnostics
+ *
+ * diagnose() probes each client with the cheapest call that proves it works:
+ * listing models where the client supports it (and checking the configured
+ * model is among them), otherwise a one-token chat. Failures are turned into
+ * hints about what to fix, and formatReport() renders the results for a
+ * terminal.
+ */
+
+const HINTS = [
+  { match: /\b(401|403)\b|unauthori[sz]ed|invalid.*key|api key is required/i, hint: 'Check the API key for this provider.' },
+  { match: /\b404\b|model.*(not found|does not exist)|no such model/i, hint: 'The model was not found. Pick one the account can use or pull it on the local server.' },
+  { match: /\b429\b|quota|rate limit/i, hint: 'Rate limited or out of quota. Check the plan and billing for this account.' },
+  { match: /ECONNREFUSED|ENOTFOUND|EAI_AGAIN|ECONNRESET|socket hang up/i, hint: 'The server could not be reached. Check the URL and that the service is running.' },
+  { match: /timed out|ETIMEDOUT/i, hint: 'The request timed out. Check network access to the provider.' },
+  { match: /\b5\d\d\b/, hint: 'The provider returned a server error. Try again later or check its status page.' },
+];
+
+/**
+ * Suggest a fix for a diagnostic error
+ * @param {string} message - Error message
+ * @returns {string} - Hint
+ */
+function hintFor(message) {
+  const found = HINTS.find(({ match }) => match.test(message));
+  return found ? found.hint : 'Unexpected error; run the failing call directly for details.';
+}
+
+const withTimeout = (promise, ms) => {
+  let timer;
+  const timeout = new Promise((_, reject) => {
+    timer = setTimeout(() => reject(new Error(`timed out after ${ms}ms`)), ms);
+  });
+  return Promise.race([promise, timeout]).finally(() => clearTimeout(timer));
+};
+
+/**
+ * Probe one client
+ * @param {string} name - Label of the client
+ * @param {Object} client - AI client
+ * @param {number} timeout - Milliseconds before the probe fails
+ * @returns {Promise<Object>} - { name, provider, model, ok, check, latencyMs, error, hint }
+ */
+async function probe(name, client, timeout) {
+  const result = {
+    name,
+    provider: client.provider || null,
+    model: client.model || null,
+    ok: false,
+    check: typeof client.listModels === 'function' ? 'list models' : 'chat',
+    latencyMs: null,
+    error: null,
+    hint: null,
+  };
+  const startedAt = Date.now();
+
+  try {
+    if (result.check === 'list models') {
+      const models = await withTimeout(client.listModels(), timeout);
+      const names = models.map((model) => (typeof model === 'string' ? model : model.name || model.id));
+      if (result.model && !names.some((model) => model === result.model || model.split(':')[0] === result.model)) {
+        throw new Error(`model ${result.model} not found (available: ${names.join(', ') || 'none'})`);
+      }
+    } else {
+      await withTimeout(client.chat('ping', { max_tokens: 1 }), timeout);
+    }
+    result.ok = true;
+  } catch (error) {
+    result.error = error.message;
+    result.hint = hintFor(error.message);
+  }
+
+  result.latencyMs = Date.now() - startedAt;
+  return result;
+}
+
+/**
+ * Probe a set of clients in parallel
+ * @param {Object} clients - Clients keyed by label (e.g. { openai: chatgpt })
+ * @param {Object} options - Diagnostic options
+ * @param {number} options.timeout - Milliseconds per probe (default 15000)
+ * @returns {Promise<Array<Object>>} - One result per client
+ */
+async function diagnose(clients, options = {}) {
+  const timeout = options.timeout || 15000;
+  return Promise.all(Object.entries(clients).map(([name, client]) => probe(name, client, timeout)));
+}
+
+/**
+ * Render diagnostic results as text
+ * @param {Array<Object>} results - Output of diagnose(), optionally with failures from client construction
+ * @returns {string} - Report
+ */
+function formatReport(results) {
+  if (results.length === 0) {
+    return 'No providers are configured. Set an API key in your .env file.\n';
+  }
+
+  const lines = results.map((result) => {
+    const target = [result.name, result.model].filter(Boolean).join(' / ');
+    if (result.ok) {
+      return `✓ ${target} (${result.check}, ${result.latencyMs}ms)`;
+    }
+    return `✗ ${target}\n    ${result.error}\n    → ${result.hint}`;
+  });
+
+  const failed = results.filter((result) => !result.ok).length;
+  lines.push('', failed === 0 ? 'All providers are working.' : `${failed} of ${results.length} providers need attention.`);
+  return `${lines.join('\n')}\n`;
+}
+
+module.exports = {
+  diagnose,
+  formatReport,
+  hintFor,
+};
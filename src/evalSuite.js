@@ -0,0 +1,235 @@
+const { completeJSON, parseJSONReply, validateSchema } = require('./structured');
+
+const CHECK_TYPES = ['contains', 'regex', 'schema', 'judge', 'custom'];
+
+const JUDGE_SCHEMA = {
+  type: 'object',
+  properties: {
+    score: { type: 'number', minimum: 0, maximum: 1 },
+    reason: { type: 'string' },
+  },
+  required: ['score', 'reason'],
+};
+
+/**
+ * EvalSuite Class
+ * Regression tests for prompts. Each case sends a prompt to every target
+ * (a client, optionally with request options such as a model) and checks the
+ * reply:
+ * - { type: 'contains', value, ignoreCase }     - reply includes the text
+ * - { type: 'regex', pattern }                 - reply matches the pattern
+ * - { type: 'schema', schema }                 - reply is JSON matching the schema
+ * - { type: 'judge', rubric, threshold }       - a judge model scores the reply 0-1
+ * - { type: 'custom', name, check }            - async (output) => boolean or { passed, score, reason }
+ *
+ * A case passes when all of its checks pass; its score is the mean of the
+ * check scores. run() returns a report that formatReport() renders for CI
+ * logs, and `aitm eval` exits non-zero when any case fails.
+ *
+ * Example:
+ *   const suite = new EvalSuite('support-bot', { judge: chatgpt })
+ *     .add({ name: 'refund policy', prompt: 'Can I get a refund?', checks: [
+ *       { type: 'contains', value: '30 days', ignoreCase: true },
+ *       { type: 'judge', rubric: 'Polite and does not promise anything outside the policy' },
+ *     ] });
+ *   const report = await suite.run({ 'gpt-4o': { client: chatgpt, options: { model: 'gpt-4o' } }, claude });
+ */
+class EvalSuite {
+  /**
+   * @param {string} name - Suite name
+   * @param {Object} options - Suite options
+   * @param {Object} options.judge - Client with conversation(messages, options), used by judge checks
+   * @param {Object} options.judgeOptions - Request options for the judge (model, etc.)
+   * @param {number} options.concurrency - Cases run at once per target (default 4)
+   */
+  constructor(name, options = {}) {
+    this.name = name;
+    this.judge = options.judge || null;
+    this.judgeOptions = options.judgeOptions || {};
+    this.concurrency = options.concurrency || 4;
+    this.cases = [];
+  }
+
+  /**
+   * Add a test case
+   * @param {Object} testCase - Case definition
+   * @param {string} testCase.name - Case name, unique within the suite
+   * @param {string} testCase.prompt - User prompt (or messages for a full conversation)
+   * @param {Array<Object>} testCase.messages - Messages sent instead of prompt
+   * @param {Object} testCase.options - Request options for this case (temperature, etc.)
+   * @param {Array<Object>} testCase.checks - Checks applied to the reply
+   * @returns {EvalSuite} - This suite, for chaining
+   */
+  add(testCase) {
+    if (!testCase.name || this.cases.some(({ name }) => name === testCase.name)) {
+      throw new Error(`Eval case names must be unique and non-empty: ${testCase.name}`);
+    }
+    if (!testCase.prompt && !testCase.messages) {
+      throw new Error(`Eval case ${testCase.name} needs a prompt or messages`);
+    }
+    for (const check of testCase.checks || []) {
+      if (!CHECK_TYPES.includes(check.type)) {
+        throw new Error(`Unsupported eval check: ${check.type}. Use ${CHECK_TYPES.join(', ')}.`);
+      }
+      if (check.type === 'custom' && typeof check.check !== 'function') {
+        throw new Error(`Eval case ${testCase.name} has a custom check without a check function`);
+      }
+      if (check.type === 'judge' && !this.judge) {
+        throw new Error(`Eval case ${testCase.name} uses a judge check but the suite has no judge client`);
+      }
+    }
+
+    this.cases.push({ ...testCase, checks: testCase.checks || [] });
+    return this;
+  }
+
+  /**
+   * Run every case against every target
+   * @param {Object} targets - Clients keyed by label, or { client, options } entries
+   * @returns {Promise<Object>} - { suite, passed, targets: [{ name, passed, failed, total, score, cases }] }
+   */
+  async run(targets) {
+    const results = [];
+
+    for (const [name, entry] of Object.entries(targets)) {
+      const { client, options = {} } = entry && entry.client ? entry : { client: entry };
+      const cases = await this._mapLimited(this.cases, (testCase) => this._runCase(testCase, client, options));
+      const passed = cases.filter((result) => result.passed).length;
+
+      results.push({
+        name,
+        passed,
+        failed: cases.length - passed,
+        total: cases.length,
+        score: cases.length > 0 ? cases.reduce((sum, result) => sum + result.score, 0) / cases.length : 1,
+        cases,
+      });
+    }
+
+    return { suite: this.name, passed: results.every((target) => target.failed === 0), targets: results };
+  }
+
+  /**
+   * Render a report as text
+   * @param {Object} report - Output of run()
+   * @returns {string} - Report
+   */
+  static formatReport(report) {
+    const lines = [`Suite: ${report.suite}`];
+
+    for (const target of report.targets) {
+      lines.push('', `${target.name}: ${target.passed}/${target.total} passed, score ${target.score.toFixed(2)}`);
+      for (const result of target.cases) {
+        lines.push(`  ${result.passed ? '✓' : '✗'} ${result.name} (${result.score.toFixed(2)})`);
+        if (result.error) {
+          lines.push(`      error: ${result.error}`);
+        }
+        for (const check of result.checks.filter((item) => !item.passed)) {
+          lines.push(`      ${check.type}: ${check.reason}`);
+        }
+      }
+    }
+
+    lines.push('', report.passed ? 'All cases passed.' : 'Some cases failed.');
+    return `${lines.join('\n')}\n`;
+  }
+
+  async _runCase(testCase, client, options) {
+    const messages = testCase.messages || [{ role: 'user', content: testCase.prompt }];
+    const startedAt = Date.now();
+    let output;
+
+    try {
+      output = await client.conversation(messages, { ...options, ...(testCase.options || {}) });
+    } catch (error) {
+      return { name: testCase.name, passed: false, score: 0, output: null, error: error.message, latencyMs: Date.now() - startedAt, checks: [] };
+    }
+
+    const latencyMs = Date.now() - startedAt;
+    const checks = [];
+    for (const check of testCase.checks) {
+      checks.push(await this._check(check, String(output), testCase));
+    }
+
+    return {
+      name: testCase.name,
+      passed: checks.every((check) => check.passed),
+      score: checks.length > 0 ? checks.reduce((sum, check) => sum + check.score, 0) / checks.length : 1,
+      output,
+      error: null,
+      latencyMs,
+      checks,
+    };
+  }
+
+  async _check(check, output, testCase) {
+    const result = (passed, reason, score = passed ? 1 : 0) => ({ type: check.type, passed, score, reason });
+
+    switch (check.type) {
+      case 'contains': {
+        const found = check.ignoreCase
+          ? output.toLowerCase().includes(String(check.value).toLowerCase())
+          : output.includes(check.value);
+        return result(found, found ? null : `reply does not contain "${check.value}"`);
+      }
+
+      case 'regex': {
+        const pattern = check.pattern instanceof RegExp ? check.pattern : new RegExp(check.pattern);
+        // search() ignores lastIndex, so global patterns behave the same for every target
+        const matched = output.search(pattern) !== -1;
+        return result(matched, matched ? null : `reply does not match ${pattern}`);
+      }
+
+      case 'schema': {
+        let errors;
+        try {
+          errors = validateSchema(parseJSONReply(output), check.schema);
+        } catch (error) {
+          errors = [`invalid JSON (${error.message})`];
+        }
+        return result(errors.length === 0, errors.length === 0 ? null : errors.join('; '));
+      }
+
+      case 'judge': {
+        const threshold = check.threshold !== undefined ? check.threshold : 0.7;
+        try {
+          const verdict = await completeJSON(this.judge, [{
+            role: 'user',
+            content: `Score the reply against the rubric from 0 (fails) to 1 (fully meets it).\n\nRubric: ${check.rubric}\n\nPrompt: ${testCase.prompt || JSON.stringify(testCase.messages)}\n\nReply: ${output}`,
+          }], JUDGE_SCHEMA, { requestOptions: { temperature: 0, ...this.judgeOptions } });
+          return result(verdict.score >= threshold, verdict.reason, verdict.score);
+        } catch (error) {
+          return result(false, `judge failed: ${error.message}`);
+        }
+      }
+
+      default: {
+        try {
+          const outcome = await check.check(output);
+          return typeof outcome === 'boolean'
+            ? result(outcome, outcome ? null : `${check.name || 'custom check'} failed`)
+            : result(Boolean(outcome.passed), outcome.reason || null, outcome.score !== undefined ? outcome.score : (outcome.passed ? 1 : 0));
+        } catch (error) {
+          return result(false, error.message);
+        }
+      }
+    }
+  }
+
+  async _mapLimited(items, task) {
+    const results = new Array(items.length);
+    let next = 0;
+
+    const worker = async () => {
+      while (next < items.length) {
+        const index = next++;
+        results[index] = await task(items[index]);
+      }
+    };
+
+    await Promise.all(Array.from({ length: Math.min(this.concurrency, items.length) }, worker));
+    return results;
+  }
+}
+
+module.exports = EvalSuite;
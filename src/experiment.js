@@ -0,0 +1,179 @@
+const crypto = require('crypto');
+
+/**
+ * Experiment Class
+ * A/B test of models and prompts. Each request is assigned to a variant
+ * (a client with its own request options and optional PromptTemplate),
+ * either at random by weight or, when a unit such as a user id is given,
+ * deterministically so the same unit always sees the same variant.
+ *
+ * Per variant the experiment tracks requests, errors and latency, plus any
+ * quality metrics reported with recordMetric() (ratings, eval scores,
+ * conversions). Token usage and cost are read from a UsageTracker when each
+ * variant's client records under the tag Experiment.tag(experiment, variant).
+ *
+ * Example:
+ *   const usage = new UsageTracker();
+ *   const experiment = new Experiment('summary', {
+ *     usageTracker: usage,
+ *     variants: [
+ *       { name: 'gpt-4o', client: new ChatGPT(null, { usageTracker: usage.withTag(Experiment.tag('summary', 'gpt-4o')) }), options: { model: 'gpt-4o' } },
+ *       { name: 'claude', client: new Claude(null, { usageTracker: usage.withTag(Experiment.tag('summary', 'claude')) }), weight: 2 },
+ *     ],
+ *   });
+ *   const { variant, output } = await experiment.run('Summarize: ...', { unit: userId });
+ *   experiment.recordMetric(variant, 'thumbs_up', 1);
+ */
+class Experiment {
+  /**
+   * @param {string} name - Experiment name
+   * @param {Object} options - Experiment options
+   * @param {Array<Object>} options.variants - { name, client, options, template, weight } entries
+   * @param {UsageTracker} options.usageTracker - Tracker the variants' clients record into, for usage per variant
+   * @param {Function} options.random - Random source for unassigned requests (for tests)
+   */
+  constructor(name, options = {}) {
+    const variants = options.variants || [];
+    if (variants.length < 2) {
+      throw new Error('Experiment requires at least two variants');
+    }
+
+    this.name = name;
+    this.variants = variants.map((variant) => {
+      if (!variant.name || !variant.client || typeof variant.client.conversation !== 'function') {
+        throw new Error(`Experiment variant ${variant.name} needs a name and a client with conversation(messages, options)`);
+      }
+      return {
+        name: variant.name,
+        client: variant.client,
+        options: variant.options || {},
+        template: variant.template || null,
+        weight: variant.weight !== undefined ? variant.weight : 1,
+      };
+    });
+    if (new Set(this.variants.map((variant) => variant.name)).size !== this.variants.length) {
+      throw new Error('Experiment variant names must be unique');
+    }
+
+    this.usageTracker = options.usageTracker || null;
+    this.random = options.random || Math.random;
+    this.stats = new Map(this.variants.map((variant) => [variant.name, {
+      requests: 0,
+      errors: 0,
+      totalLatencyMs: 0,
+      metrics: {},
+    }]));
+  }
+
+  /**
+   * UsageTracker tag under which a variant's usage is recorded
+   * @param {string} experiment - Experiment name
+   * @param {string} variant - Variant name
+   * @returns {string} - Tag
+   */
+  static tag(experiment, variant) {
+    return `${experiment}:${variant}`;
+  }
+
+  /**
+   * Pick a variant
+   * @param {string} unit - Stable id (user, session) for sticky assignment; random when omitted
+   * @returns {string} - Variant name
+   */
+  assign(unit = null) {
+    const point = unit === null || unit === undefined
+      ? this.random()
+      : crypto.createHash('sha256').update(`${this.name}:${unit}`).digest().readUInt32BE(0) / 0x100000000;
+
+    const total = this.variants.reduce((sum, variant) => sum + variant.weight, 0);
+    let threshold = point * total;
+    for (const variant of this.variants) {
+      threshold -= variant.weight;
+      if (threshold < 0) {
+        return variant.name;
+      }
+    }
+    return this.variants[this.variants.length - 1].name;
+  }
+
+  /**
+   * Send a request through an assigned variant
+   * @param {string|Array|Object} input - Prompt, messages, or template values for variants with a template
+   * @param {Object} options - Run options
+   * @param {string} options.unit - Stable id for sticky assignment
+   * @param {string} options.variant - Force a variant instead of assigning one
+   * @returns {Promise<Object>} - { variant, output, latencyMs }
+   */
+  async run(input, options = {}) {
+    const name = options.variant || this.assign(options.unit);
+    const variant = this.variants.find((candidate) => candidate.name === name);
+    if (!variant) {
+      throw new Error(`Unknown variant: ${name}`);
+    }
+
+    let messages;
+    if (variant.template) {
+      messages = [{ role: 'user', content: variant.template.render(input) }];
+    } else {
+      messages = typeof input === 'string' ? [{ role: 'user', content: input }] : input;
+    }
+
+    const stats = this.stats.get(name);
+    const startedAt = Date.now();
+    stats.requests++;
+    try {
+      const output = await variant.client.conversation(messages, variant.options);
+      const latencyMs = Date.now() - startedAt;
+      stats.totalLatencyMs += latencyMs;
+      return { variant: name, output, latencyMs };
+    } catch (error) {
+      stats.errors++;
+      stats.totalLatencyMs += Date.now() - startedAt;
+      error.variant = name;
+      throw error;
+    }
+  }
+
+  /**
+   * Report a quality measurement for a variant
+   * @param {string} variant - Variant name (as returned by run)
+   * @param {string} metric - Metric name, e.g. 'rating' or 'converted'
+   * @param {number} value - Measured value
+   */
+  recordMetric(variant, metric, value) {
+    const stats = this.stats.get(variant);
+    if (!stats) {
+      throw new Error(`Unknown variant: ${variant}`);
+    }
+    const totals = stats.metrics[metric] || (stats.metrics[metric] = { count: 0, sum: 0 });
+    totals.count++;
+    totals.sum += value;
+  }
+
+  /**
+   * Get aggregated results per variant
+   * @returns {Object} - { name, variants: { [variant]: { requests, errors, errorRate, meanLatencyMs, usage, metrics } } }
+   */
+  results() {
+    const usage = this.usageTracker ? this.usageTracker.getBreakdown('tag') : {};
+    const variants = {};
+
+    for (const [name, stats] of this.stats) {
+      variants[name] = {
+        requests: stats.requests,
+        errors: stats.errors,
+        errorRate: stats.requests > 0 ? stats.errors / stats.requests : 0,
+        meanLatencyMs: stats.requests > 0 ? stats.totalLatencyMs / stats.requests : null,
+        usage: usage[Experiment.tag(this.name, name)] || null,
+        metrics: Object.fromEntries(Object.entries(stats.metrics).map(([metric, { count, sum }]) => [
+          metric,
+          { count, mean: sum / count },
+        ])),
+      };
+    }
+
+    return { name: this.name, variants };
+  }
+}
+
+module.exports = Experiment;
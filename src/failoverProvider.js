@@ -0,0 +1,303 @@
+/**
+ * Errors worth trying another provider for: rate limits, server errors,
+ * network failures and timeouts. Client errors such as a bad request or an
+ * invalid key would fail the same way everywhere and are thrown at once.
+ * @param {Error} error - Error thrown by a provider
+ * @returns {boolean} - True when another provider should be tried
+ */
+function isRetryableError(error) {
+  const statusCode = error && error.statusCode;
+  if (typeof statusCode === 'number') {
+    return statusCode === 429 || statusCode >= 500;
+  }
+
+  const message = (error && error.message) || '';
+  // Without a status code, it may still lead the message, either bare or after 'API Error: '
+  return /(^|Error: )(429|5\d\d)\b/.test(message) ||
+    /Request Error|Connection error|Circuit open|timed? ?out|ETIMEDOUT|ECONNRESET|ECONNREFUSED|EAI_AGAIN|socket hang up/i.test(message);
+}
+
+/**
+ * FailoverProvider Class
+ * Wraps several AI clients (ChatGPT, Grok, Claude, LocalProvider, ...)
+ * behind the same chat, conversation and chatWithTools methods. A request
+ * goes to one provider and moves on to the next when it fails with a
+ * retryable error. Providers that fail are benched for a cooldown period.
+ *
+ * Modes:
+ * - 'failover': always start with the first healthy provider in order
+ * - 'round-robin': rotate the starting provider on every request
+ * - 'weighted': pick the starting provider at random by weight
+ * - 'race': send the request to the first raceWidth providers at once and
+ *   return the first response that passes the accept check. The other
+ *   requests are then cancelled through the AbortSignal passed to the
+ *   clients as options.signal, so they are not billed or recorded.
+ *
+ * A signal passed in the request options cancels every attempt. Providers
+ * with a timeout have their request cancelled once it runs longer, and the
+ * attempt fails with a retryable "timed out" error so the next provider is
+ * tried.
+ */
+class FailoverProvider {
+  /**
+   * @param {Array} providers - Clients, or { name, client, weight, timeout } entries (timeout in milliseconds)
+   * @param {Object} options - Failover options
+   * @param {string} options.mode - 'failover', 'round-robin', 'weighted' or 'race' (default 'failover')
+   * @param {number} options.raceWidth - Providers raced at once in race mode (default 2)
+   * @param {Function} options.accept - Async (result, providerName) => boolean guardrail applied in race mode
+   * @param {number} options.cooldown - Milliseconds a failed provider is skipped (default 30000)
+   * @param {number} options.timeout - Milliseconds before an attempt fails, for providers without their own timeout (default none)
+   * @param {Function} options.isRetryable - (error) => boolean deciding whether to try the next provider
+   * @param {Function} options.random - Random source for weighted mode (for tests)
+   */
+  constructor(providers, options = {}) {
+    if (!Array.isArray(providers) || providers.length === 0) {
+      throw new Error('FailoverProvider requires at least one provider');
+    }
+
+    const mode = options.mode || 'failover';
+    if (!['failover', 'round-robin', 'weighted', 'race'].includes(mode)) {
+      throw new Error(`Unsupported failover mode: ${mode}. Use 'failover', 'round-robin', 'weighted' or 'race'.`);
+    }
+
+    this.providers = providers.map((entry, index) => {
+      const client = entry && entry.client ? entry.client : entry;
+      return {
+        name: entry.name || (client.constructor && client.constructor.name) || `provider-${index}`,
+        client,
+        weight: entry.weight !== undefined ? entry.weight : 1,
+        timeout: entry.timeout !== undefined ? entry.timeout : options.timeout || null,
+        failures: 0,
+        served: 0,
+        lastError: null,
+        unhealthyUntil: 0,
+      };
+    });
+    this.mode = mode;
+    this.cooldown = options.cooldown !== undefined ? options.cooldown : 30000;
+    this.isRetryable = options.isRetryable || isRetryableError;
+    this.random = options.random || Math.random;
+    this.raceWidth = options.raceWidth || 2;
+    this.accept = options.accept || (() => true);
+    this.nextIndex = 0;
+    this.lastServedBy = null;
+  }
+
+  /**
+   * Send a message through the first available provider
+   * @param {string} message - The message to send
+   * @param {Object} options - Options passed to the provider
+   * @returns {Promise<string>} - The response
+   */
+  async chat(message, options = {}) {
+    return this._dispatch((client, signal) => client.chat(message, { ...options, signal }), options.signal);
+  }
+
+  /**
+   * Have a conversation through the first available provider
+   * @param {Array} messages - Array of message objects with role and content
+   * @param {Object} options - Options passed to the provider
+   * @returns {Promise<string>} - The response
+   */
+  async conversation(messages, options = {}) {
+    return this._dispatch((client, signal) => client.conversation(messages, { ...options, signal }), options.signal);
+  }
+
+  /**
+   * Have a tool-calling conversation through the first available provider
+   * @param {Array} messages - Array of message objects with role and content
+   * @param {ToolRegistry} tools - Tools the model may call
+   * @param {Object} options - Options passed to the provider
+   * @returns {Promise<Object>} - { content, toolCalls, messages }
+   */
+  async chatWithTools(messages, tools, options = {}) {
+    return this._dispatch((client, signal) => client.chatWithTools(messages, tools, { ...options, signal }), options.signal);
+  }
+
+  /**
+   * Get the health of every provider
+   * @returns {Array<Object>} - { name, healthy, failures, served, lastError, unhealthyUntil, circuit }
+   *   where circuit is the CircuitBreaker state of clients wrapped with one, otherwise null
+   */
+  getHealth() {
+    const now = Date.now();
+    return this.providers.map(({ name, client, failures, served, lastError, unhealthyUntil }) => {
+      const circuit = client.circuitBreaker ? client.circuitBreaker.state : null;
+      return {
+        name,
+        healthy: unhealthyUntil <= now && circuit !== 'open',
+        failures,
+        served,
+        lastError,
+        unhealthyUntil: unhealthyUntil > now ? unhealthyUntil : null,
+        circuit,
+      };
+    });
+  }
+
+  async _dispatch(call, signal) {
+    if (this.mode === 'race') {
+      return this._race(call, signal);
+    }
+
+    const errors = [];
+
+    for (const provider of this._order()) {
+      try {
+        const result = await this._attempt(provider, call, signal);
+        provider.failures = 0;
+        provider.unhealthyUntil = 0;
+        provider.served++;
+        this.lastServedBy = provider.name;
+        return result;
+      } catch (error) {
+        if (signal && signal.aborted) {
+          throw error;
+        }
+        provider.lastError = error.message;
+        if (!this.isRetryable(error)) {
+          throw error;
+        }
+
+        provider.failures++;
+        provider.unhealthyUntil = Date.now() + this.cooldown;
+        errors.push(`${provider.name}: ${error.message}`);
+      }
+    }
+
+    throw new Error(`All providers failed: ${errors.join('; ')}`);
+  }
+
+  _race(call, signal) {
+    const contenders = this._order().slice(0, this.raceWidth);
+    const controllers = new Map(contenders.map((provider) => [provider, new AbortController()]));
+    const errors = [];
+    let settled = false;
+    let remaining = contenders.length;
+
+    const abortAll = () => {
+      for (const controller of controllers.values()) {
+        controller.abort();
+      }
+    };
+    if (signal) {
+      signal.addEventListener('abort', abortAll, { once: true });
+    }
+
+    const race = new Promise((resolve, reject) => {
+      const lose = (provider, message) => {
+        errors.push(`${provider.name}: ${message}`);
+        remaining--;
+        if (remaining === 0 && !settled) {
+          reject(new Error(`All providers failed: ${errors.join('; ')}`));
+        }
+      };
+
+      for (const provider of contenders) {
+        Promise.resolve()
+          .then(() => this._attempt(provider, call, controllers.get(provider).signal))
+          .then(async (result) => {
+            if (settled) {
+              return;
+            }
+            if (!(await this.accept(result, provider.name))) {
+              lose(provider, 'response rejected');
+              return;
+            }
+            if (settled) {
+              return;
+            }
+
+            settled = true;
+            for (const [other, controller] of controllers) {
+              if (other !== provider) {
+                controller.abort();
+              }
+            }
+            provider.failures = 0;
+            provider.unhealthyUntil = 0;
+            provider.served++;
+            this.lastServedBy = provider.name;
+            resolve(result);
+          })
+          .catch((error) => {
+            if (controllers.get(provider).signal.aborted) {
+              // Cancelled because another provider won or the caller gave up; not a provider failure
+              lose(provider, error.message);
+              return;
+            }
+            provider.lastError = error.message;
+            if (this.isRetryable(error)) {
+              provider.failures++;
+              provider.unhealthyUntil = Date.now() + this.cooldown;
+            }
+            lose(provider, error.message);
+          });
+      }
+    });
+
+    return signal ? race.finally(() => signal.removeEventListener('abort', abortAll)) : race;
+  }
+
+  _attempt(provider, call, signal) {
+    if (!provider.timeout) {
+      return call(provider.client, signal);
+    }
+
+    const controller = new AbortController();
+    const forward = () => controller.abort();
+    if (signal) {
+      signal.addEventListener('abort', forward, { once: true });
+    }
+    let timer;
+    const timeout = new Promise((resolve, reject) => {
+      timer = setTimeout(() => {
+        controller.abort();
+        reject(new Error(`${provider.name} timed out after ${provider.timeout}ms`));
+      }, provider.timeout);
+    });
+
+    return Promise.race([call(provider.client, controller.signal), timeout]).finally(() => {
+      clearTimeout(timer);
+      if (signal) {
+        signal.removeEventListener('abort', forward);
+      }
+    });
+  }
+
+  _order() {
+    const count = this.providers.length;
+    let start = 0;
+
+    if (this.mode === 'round-robin') {
+      start = this.nextIndex;
+      this.nextIndex = (this.nextIndex + 1) % count;
+    } else if (this.mode === 'weighted') {
+      start = this._weightedIndex();
+    }
+
+    const rotated = this.providers.slice(start).concat(this.providers.slice(0, start));
+    const now = Date.now();
+    const healthy = rotated.filter((provider) => provider.unhealthyUntil <= now);
+    const benched = rotated.filter((provider) => provider.unhealthyUntil > now);
+
+    // Benched providers are a last resort rather than excluded outright
+    return [...healthy, ...benched];
+  }
+
+  _weightedIndex() {
+    const total = this.providers.reduce((sum, provider) => sum + provider.weight, 0);
+    let target = this.random() * total;
+
+    for (let i = 0; i < this.providers.length; i++) {
+      target -= this.providers[i].weight;
+      if (target < 0) {
+        return i;
+      }
+    }
+    return this.providers.length - 1;
+  }
+}
+
+module.exports = FailoverProvider;
+module.exports.isRetryableError = isRetryableError;
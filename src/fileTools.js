@@ -0,0 +1,137 @@
+const fs = require('fs');
+const path = require('path');
+
+/**
+ * Sandboxed filesystem tools for agents
+ *
+ * read_file, write_file and list_files work on paths relative to a root
+ * directory, like a chroot. Paths that resolve outside the root, including
+ * through symbolic links, are refused, as are files over maxBytes and, when
+ * extensions is set, files of any other type.
+ *
+ * Example:
+ *   const [readFile, writeFile, listFiles] = fileTools({ root: './workspace', extensions: ['.md', '.json'] });
+ */
+
+const DEFAULT_MAX_BYTES = 1024 * 1024;
+
+/**
+ * Build read_file, write_file and list_files tools
+ * @param {Object} options - Tool options
+ * @param {string} options.root - Directory the tools are confined to
+ * @param {number} options.maxBytes - Largest file read or written (default 1 MB)
+ * @param {Array<string>} options.extensions - Allowed file extensions, e.g. ['.md', '.json'] (default any)
+ * @param {boolean} options.readOnly - Leave out write_file (default false)
+ * @returns {Array<Object>} - Tool definitions { name, description, parameters, handler }
+ */
+function fileTools(options = {}) {
+  if (!options.root) {
+    throw new Error('fileTools requires a root directory');
+  }
+
+  const root = fs.realpathSync(options.root);
+  const maxBytes = options.maxBytes || DEFAULT_MAX_BYTES;
+  const extensions = options.extensions ? options.extensions.map((ext) => ext.toLowerCase()) : null;
+
+  const inside = (target) => target === root || target.startsWith(root + path.sep);
+
+  // Resolve a model-supplied path, following links in the part that exists
+  const resolve = async (relative = '.') => {
+    const target = path.resolve(root, String(relative));
+    if (!inside(target)) {
+      throw new Error(`Path ${relative} is outside the sandbox`);
+    }
+
+    let existing = target;
+    const rest = [];
+    for (;;) {
+      try {
+        existing = await fs.promises.realpath(existing);
+        break;
+      } catch (error) {
+        if (error.code !== 'ENOENT' || existing === root) {
+          throw error;
+        }
+        rest.unshift(path.basename(existing));
+        existing = path.dirname(existing);
+      }
+    }
+
+    const real = path.join(existing, ...rest);
+    if (!inside(real)) {
+      throw new Error(`Path ${relative} is outside the sandbox`);
+    }
+    return real;
+  };
+
+  const checkExtension = (file) => {
+    if (extensions && !extensions.includes(path.extname(file).toLowerCase())) {
+      throw new Error(`File type ${path.extname(file) || '(none)'} is not allowed`);
+    }
+  };
+
+  const tools = [
+    {
+      name: 'read_file',
+      description: 'Read a UTF-8 text file. Paths are relative to the workspace root.',
+      parameters: {
+        type: 'object',
+        properties: { path: { type: 'string' } },
+        required: ['path'],
+      },
+      handler: async ({ path: relative }) => {
+        const file = await resolve(relative);
+        checkExtension(file);
+        const stats = await fs.promises.stat(file);
+        if (!stats.isFile()) {
+          throw new Error(`${relative} is not a file`);
+        }
+        if (stats.size > maxBytes) {
+          throw new Error(`${relative} is ${stats.size} bytes, over the ${maxBytes} byte limit`);
+        }
+        return fs.promises.readFile(file, 'utf8');
+      },
+    },
+    {
+      name: 'write_file',
+      description: 'Create or replace a UTF-8 text file. Paths are relative to the workspace root.',
+      parameters: {
+        type: 'object',
+        properties: { path: { type: 'string' }, content: { type: 'string' } },
+        required: ['path', 'content'],
+      },
+      handler: async ({ path: relative, content }) => {
+        const file = await resolve(relative);
+        checkExtension(file);
+        const size = Buffer.byteLength(String(content));
+        if (size > maxBytes) {
+          throw new Error(`Content is ${size} bytes, over the ${maxBytes} byte limit`);
+        }
+        await fs.promises.mkdir(path.dirname(file), { recursive: true });
+        await fs.promises.writeFile(file, String(content));
+        return { path: path.relative(root, file), bytes: size };
+      },
+    },
+    {
+      name: 'list_files',
+      description: 'List the entries of a directory. Paths are relative to the workspace root.',
+      parameters: {
+        type: 'object',
+        properties: { path: { type: 'string', description: "Directory (default '.')" } },
+      },
+      handler: async ({ path: relative = '.' } = {}) => {
+        const dir = await resolve(relative);
+        const entries = await fs.promises.readdir(dir, { withFileTypes: true });
+        return entries
+          .map((entry) => ({ name: entry.name, type: entry.isDirectory() ? 'directory' : 'file' }))
+          .sort((a, b) => a.name.localeCompare(b.name));
+      },
+    },
+  ];
+
+  return options.readOnly ? tools.filter((tool) => tool.name !== 'write_file') : tools;
+}
+
+module.exports = {
+  fileTools,
+};
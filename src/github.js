@@ -0,0 +1,312 @@
+const crypto = require('crypto');
+const https = require('https');
+const { normalizeLimit, encodeCursor, decodeCursor } = require('./pagination');
+require('dotenv').config();
+
+/**
+ * GitHub Client Class
+ * Lets agents work with repositories: list and create issues, comment on
+ * issues and pull requests, and read repository files.
+ *
+ * Authenticates with a personal access token, or as a GitHub App
+ * installation (an installation token is requested and refreshed as needed).
+ */
+class GitHub {
+  /**
+   * @param {string} token - Access token (falls back to GITHUB_TOKEN)
+   * @param {Object} options - Client options
+   * @param {Object} options.app - GitHub App credentials { appId, privateKey, installationId } (falls back to GITHUB_APP_* env vars)
+   * @param {string} options.baseUrl - API base URL, for GitHub Enterprise (default 'https://api.github.com')
+   * @param {ConnectionPool} options.pool - Optional keep-alive connection pool
+   * @param {Recorder} options.recorder - Optional recorder for capturing or replaying calls
+   */
+  constructor(token = null, options = {}) {
+    this.token = token || process.env.GITHUB_TOKEN || null;
+    this.app = this.token ? null : GitHub._appConfig(options.app);
+
+    if (!this.token && !this.app) {
+      throw new Error(
+        'GitHub credentials are required. Please set GITHUB_TOKEN (or GITHUB_APP_ID, GITHUB_APP_PRIVATE_KEY and GITHUB_APP_INSTALLATION_ID) in your .env file or pass them to the constructor.'
+      );
+    }
+
+    this.baseUrl = options.baseUrl || 'https://api.github.com';
+    this.pool = options.pool || null;
+    this.recorder = options.recorder || null;
+    this.installationToken = null;
+  }
+
+  static _appConfig(app = {}) {
+    const config = {
+      appId: app.appId || process.env.GITHUB_APP_ID,
+      privateKey: app.privateKey || process.env.GITHUB_APP_PRIVATE_KEY,
+      installationId: app.installationId || process.env.GITHUB_APP_INSTALLATION_ID,
+    };
+    if (!config.appId || !config.privateKey || !config.installationId) {
+      return null;
+    }
+    // Keys stored in .env files usually have escaped newlines
+    config.privateKey = config.privateKey.replace(/\\n/g, '\n');
+    return config;
+  }
+
+  /**
+   * List issues in a repository
+   * @param {string} owner - Repository owner
+   * @param {string} repo - Repository name
+   * @param {Object} options - Filters (state, labels, assignee) plus cursor and limit
+   * @returns {Promise<Object>} - Page { items, nextCursor } of issues (the API also returns pull requests here; they have a pull_request field)
+   */
+  async listIssues(owner, repo, options = {}) {
+    return this._listPage(`${GitHub._repoPath(owner, repo)}/issues`, options);
+  }
+
+  /**
+   * Create an issue
+   * @param {string} owner - Repository owner
+   * @param {string} repo - Repository name
+   * @param {Object} issue - { title, body, labels, assignees }
+   * @returns {Promise<Object>} - Created issue
+   */
+  async createIssue(owner, repo, issue) {
+    if (!issue || !issue.title) {
+      throw new Error('Issue title is required');
+    }
+    return this._request('POST', `${GitHub._repoPath(owner, repo)}/issues`, issue);
+  }
+
+  /**
+   * Comment on an issue or pull request
+   * @param {string} owner - Repository owner
+   * @param {string} repo - Repository name
+   * @param {number} number - Issue or pull request number
+   * @param {string} body - Comment text (Markdown)
+   * @returns {Promise<Object>} - Created comment
+   */
+  async comment(owner, repo, number, body) {
+    return this._request('POST', `${GitHub._repoPath(owner, repo)}/issues/${encodeURIComponent(number)}/comments`, { body });
+  }
+
+  /**
+   * List pull requests in a repository
+   * @param {string} owner - Repository owner
+   * @param {string} repo - Repository name
+   * @param {Object} options - Filters (state, head, base) plus cursor and limit
+   * @returns {Promise<Object>} - Page { items, nextCursor } of pull requests
+   */
+  async listPullRequests(owner, repo, options = {}) {
+    return this._listPage(`${GitHub._repoPath(owner, repo)}/pulls`, options);
+  }
+
+  /**
+   * Read a file from a repository
+   * @param {string} owner - Repository owner
+   * @param {string} repo - Repository name
+   * @param {string} path - File path
+   * @param {Object} options - { ref } branch, tag or commit (default branch when omitted)
+   * @returns {Promise<Object>} - { path, sha, size, content } with content decoded as UTF-8
+   */
+  async getFile(owner, repo, path, options = {}) {
+    const encodedPath = path.split('/').map(encodeURIComponent).join('/');
+    const file = await this._request(
+      'GET',
+      `${GitHub._repoPath(owner, repo)}/contents/${encodedPath}${GitHub._query({ ref: options.ref })}`
+    );
+
+    if (Array.isArray(file) || file.type !== 'file') {
+      throw new Error(`GitHub path is not a file: ${path}`);
+    }
+
+    return {
+      path: file.path,
+      sha: file.sha,
+      size: file.size,
+      content: Buffer.from(file.content || '', file.encoding || 'base64').toString('utf8'),
+    };
+  }
+
+  /**
+   * List the files in a repository tree
+   * @param {string} owner - Repository owner
+   * @param {string} repo - Repository name
+   * @param {Object} options - { ref, prefix } ref defaults to 'HEAD'; prefix limits results to a directory
+   * @returns {Promise<Array>} - { path, sha, size } for each file; rejects when GitHub truncates the tree
+   */
+  async listFiles(owner, repo, options = {}) {
+    const ref = encodeURIComponent(options.ref || 'HEAD');
+    const tree = await this._request('GET', `${GitHub._repoPath(owner, repo)}/git/trees/${ref}?recursive=1`);
+
+    // GitHub cuts recursive trees off at 100,000 entries or 7 MB; a partial list would silently miss files
+    if (tree.truncated) {
+      throw new Error(`GitHub tree for ${owner}/${repo} at ${options.ref || 'HEAD'} is too large to list in one request`);
+    }
+
+    return (tree.tree || [])
+      .filter((entry) => entry.type === 'blob')
+      .filter((entry) => !options.prefix || entry.path.startsWith(options.prefix))
+      .map(({ path, sha, size }) => ({ path, sha, size }));
+  }
+
+  async _listPage(path, options) {
+    const { cursor, limit, ...filters } = options;
+    const perPage = normalizeLimit(limit);
+    const position = decodeCursor(cursor);
+    const page = position ? position.page : 1;
+
+    const { body, link } = await this._request(
+      'GET',
+      `${path}${GitHub._query({ ...filters, per_page: perPage, page })}`,
+      null,
+      { withLink: true }
+    );
+
+    // GitHub only sends a rel="next" link when another page exists
+    const nextPage = GitHub._nextPage(link);
+    return {
+      items: body,
+      nextCursor: nextPage ? encodeCursor({ page: nextPage }) : null,
+    };
+  }
+
+  static _repoPath(owner, repo) {
+    return `/repos/${encodeURIComponent(owner)}/${encodeURIComponent(repo)}`;
+  }
+
+  static _nextPage(link) {
+    const next = (link || '').split(',').find((part) => /rel="next"/.test(part));
+    const match = next && next.match(/<([^>]+)>/);
+    if (!match) {
+      return null;
+    }
+    const page = Number(new URL(match[1]).searchParams.get('page'));
+    return Number.isInteger(page) && page > 0 ? page : null;
+  }
+
+  static _query(params) {
+    const entries = Object.entries(params)
+      .filter(([, value]) => value !== undefined && value !== null)
+      .map(([key, value]) => [key, Array.isArray(value) ? value.join(',') : String(value)]);
+    return entries.length > 0 ? `?${new URLSearchParams(entries)}` : '';
+  }
+
+  /**
+   * Build the Authorization header, refreshing the App installation token when it is near expiry
+   * @returns {Promise<string>} - Header value
+   */
+  async _authorization() {
+    if (this.token) {
+      return `Bearer ${this.token}`;
+    }
+
+    if (!this.installationToken || this.installationToken.expiresAt - Date.now() < 60000) {
+      const result = await this._httpRequest(
+        'POST',
+        `/app/installations/${encodeURIComponent(this.app.installationId)}/access_tokens`,
+        null,
+        `Bearer ${this._appJWT()}`
+      );
+      this.installationToken = { token: result.token, expiresAt: Date.parse(result.expires_at) };
+    }
+
+    return `Bearer ${this.installationToken.token}`;
+  }
+
+  /**
+   * Sign a short-lived JWT identifying the GitHub App
+   * @returns {string} - RS256 JWT
+   */
+  _appJWT() {
+    const now = Math.floor(Date.now() / 1000);
+    const encode = (value) => Buffer.from(JSON.stringify(value)).toString('base64url');
+    // Backdate iat to allow for clock drift, as GitHub recommends
+    const unsigned = `${encode({ alg: 'RS256', typ: 'JWT' })}.${encode({ iat: now - 60, exp: now + 540, iss: String(this.app.appId) })}`;
+    const signature = crypto.createSign('RSA-SHA256').update(unsigned).sign(this.app.privateKey, 'base64url');
+    return `${unsigned}.${signature}`;
+  }
+
+  /**
+   * Make a request to the GitHub API, through the recorder when one is configured
+   * @param {string} method - HTTP method
+   * @param {string} path - API path
+   * @param {Object} body - Request body
+   * @param {Object} options - { withLink } resolves { body, link } with the Link header instead of the body alone
+   * @returns {Promise<Object>} - Parsed response
+   */
+  async _request(method, path, body = null, options = {}) {
+    const send = async () => this._httpRequest(method, path, body, await this._authorization(), options);
+    if (this.recorder) {
+      return this.recorder.capture('github', `${method} ${path}`, body, send);
+    }
+    return send();
+  }
+
+  /**
+   * Make an HTTPS request to the GitHub API
+   * @param {string} method - HTTP method
+   * @param {string} path - API path
+   * @param {Object} body - Request body
+   * @param {string} authorization - Authorization header value
+   * @param {Object} options - { withLink } resolves { body, link } with the Link header instead of the body alone
+   * @returns {Promise<Object>} - Parsed response
+   */
+  _httpRequest(method, path, body, authorization, options = {}) {
+    return new Promise((resolve, reject) => {
+      const url = new URL(this.baseUrl + path);
+      const bodyStr = body ? JSON.stringify(body) : null;
+
+      const requestOptions = {
+        hostname: url.hostname,
+        port: url.port || 443,
+        path: url.pathname + url.search,
+        method,
+        agent: this.pool ? this.pool.agentFor(url.protocol) : undefined,
+        headers: {
+          Authorization: authorization,
+          Accept: 'application/vnd.github+json',
+          'X-GitHub-Api-Version': '2022-11-28',
+          'User-Agent': 'ai-time-machines',
+        },
+      };
+
+      if (bodyStr) {
+        requestOptions.headers['Content-Type'] = 'application/json';
+        requestOptions.headers['Content-Length'] = Buffer.byteLength(bodyStr);
+      }
+
+      const req = https.request(requestOptions, (res) => {
+        let data = '';
+
+        res.on('data', (chunk) => {
+          data += chunk;
+        });
+
+        res.on('end', () => {
+          let parsed;
+          try {
+            parsed = data ? JSON.parse(data) : {};
+          } catch {
+            parsed = { raw: data };
+          }
+
+          if (res.statusCode >= 200 && res.statusCode < 300) {
+            resolve(options.withLink ? { body: parsed, link: res.headers.link || null } : parsed);
+          } else {
+            reject(Object.assign(new Error(`GitHub API Error: ${res.statusCode} - ${parsed.message || data}`), { statusCode: res.statusCode }));
+          }
+        });
+      });
+
+      req.on('error', (error) => {
+        reject(new Error(`GitHub Request Error: ${error.message}`));
+      });
+
+      if (bodyStr) {
+        req.write(bodyStr);
+      }
+
+      req.end();
+    });
+  }
+}
+
+module.exports = GitHub;
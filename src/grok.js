@@ -1,4 +1,6 @@
 const https = require('https');
+const { observe } = require('./observer');
+const { runWithTools } = require('./tools');
 require('dotenv').config();
 
 /**
@@ -6,7 +8,17 @@ require('dotenv').config();
  * Provides an interface to interact with xAI's Grok API
  */
 class Grok {
-  constructor(apiKey = null) {
+  /**
+   * @param {string} apiKey - xAI API key (falls back to GROK_API_KEY)
+   * @param {Object} options - Client options
+   * @param {RateLimiter} options.rateLimiter - Optional limiter, may be shared with other clients
+   * @param {ConnectionPool} options.pool - Optional keep-alive connection pool
+   * @param {Object} options.observer - Optional hooks notified of every outbound call
+   * @param {UsageTracker} options.usageTracker - Optional token and cost tracker
+   * @param {ResponseCache} options.cache - Optional response cache
+   * @param {Recorder} options.recorder - Optional recorder for capturing or replaying calls
+   */
+  constructor(apiKey = null, options = {}) {
     const key = apiKey || process.env.GROK_API_KEY;
 
     if (!key) {
@@ -18,12 +30,18 @@ class Grok {
     this.apiKey = key;
     this.baseUrl = 'https://api.x.ai/v1';
     this.model = 'grok-beta';
+    this.rateLimiter = options.rateLimiter || null;
+    this.pool = options.pool || null;
+    this.observer = options.observer || null;
+    this.usageTracker = options.usageTracker || null;
+    this.cache = options.cache || null;
+    this.recorder = options.recorder || null;
   }
 
   /**
    * Send a message to Grok and get a response
    * @param {string} message - The message to send
-   * @param {Object} options - Additional options (temperature, max_tokens, stream, etc.)
+   * @param {Object} options - Additional options (temperature, max_tokens, etc.; cache: false skips the cache; signal cancels the request)
    * @returns {Promise<string>} - The response from Grok
    */
   async chat(message, options = {}) {
@@ -40,14 +58,13 @@ class Grok {
       stream: false,
     };
 
-    const response = await this._request('POST', '/chat/completions', payload);
-    return response.choices[0].message.content;
+    return this._createCompletion(payload, { cache: options.cache, signal: options.signal });
   }
 
   /**
    * Have a conversation with context
    * @param {Array} messages - Array of message objects with role and content
-   * @param {Object} options - Additional options
+   * @param {Object} options - Additional options (cache: false skips the cache; signal cancels the request)
    * @returns {Promise<string>} - The response from Grok
    */
   async conversation(messages, options = {}) {
@@ -59,8 +76,33 @@ class Grok {
       stream: false,
     };
 
-    const response = await this._request('POST', '/chat/completions', payload);
-    return response.choices[0].message.content;
+    return this._createCompletion(payload, { cache: options.cache, signal: options.signal });
+  }
+
+  /**
+   * Have a conversation in which the model may call registered tools
+   * @param {Array} messages - Array of message objects with role and content
+   * @param {ToolRegistry} tools - Tools the model may call
+   * @param {Object} options - Additional options (autoExecute, maxIterations, temperature, etc.; signal cancels the requests)
+   * @returns {Promise<Object>} - { content, toolCalls, messages }
+   */
+  async chatWithTools(messages, tools, options = {}) {
+    return runWithTools(
+      async (history) => {
+        const response = await this._sendCompletion({
+          model: options.model || this.model,
+          messages: history,
+          temperature: options.temperature !== undefined ? options.temperature : 0.7,
+          max_tokens: options.max_tokens || 1000,
+          stream: false,
+          tools: tools.definitions(),
+        }, options.signal);
+        return response.choices[0].message;
+      },
+      messages,
+      tools,
+      { autoExecute: options.autoExecute, maxIterations: options.maxIterations }
+    );
   }
 
   /**
@@ -102,14 +144,87 @@ class Grok {
     return ['grok-beta', 'grok-vision-beta'];
   }
 
+  /**
+   * Create a chat completion and return the message content
+   * @param {Object} payload - Chat completion request body
+   * @param {Object} callOptions - Per-call options (cache, signal)
+   * @returns {Promise<string>} - The response from Grok
+   */
+  async _createCompletion(payload, callOptions = {}) {
+    const cacheKey = this.cache && callOptions.cache !== false ? this.cache.keyFor('xai', payload) : null;
+    if (cacheKey) {
+      const cached = await this.cache.get(cacheKey);
+      if (cached !== undefined) {
+        return cached;
+      }
+    }
+
+    const response = await this._sendCompletion(payload, callOptions.signal);
+    const content = response.choices[0].message.content;
+    if (cacheKey) {
+      await this.cache.set(cacheKey, content);
+    }
+
+    return content;
+  }
+
+  /**
+   * Send a chat completion request and return the raw response
+   * @param {Object} payload - Chat completion request body
+   * @param {AbortSignal} signal - Cancels the HTTP request
+   * @returns {Promise<Object>} - The API response
+   */
+  async _sendCompletion(payload, signal = null) {
+    if (this.usageTracker) {
+      this.usageTracker.checkBudget();
+    }
+
+    const response = await this._schedule(() => observe(
+      this.observer,
+      { provider: 'xai', operation: 'chat.completions', model: payload.model },
+      () => this._request('POST', '/chat/completions', payload, signal)
+    ));
+
+    if (this.usageTracker) {
+      this.usageTracker.record(payload.model, response.usage);
+    }
+
+    return response;
+  }
+
+  /**
+   * Run a request through the rate limiter when one is configured
+   * @param {Function} task - Async function performing the request
+   * @returns {Promise<*>} - The task result
+   */
+  _schedule(task) {
+    return this.rateLimiter ? this.rateLimiter.schedule(task) : task();
+  }
+
+  /**
+   * Make a request to the xAI API, through the recorder when one is configured
+   * @param {string} method - HTTP method
+   * @param {string} path - API path
+   * @param {Object} body - Request body
+   * @param {AbortSignal} signal - Cancels the HTTP request
+   * @returns {Promise<Object>} - Parsed response
+   */
+  _request(method, path, body = null, signal = null) {
+    if (this.recorder) {
+      return this.recorder.capture('xai', `${method} ${path}`, body, () => this._httpRequest(method, path, body, signal), { signal });
+    }
+    return this._httpRequest(method, path, body, signal);
+  }
+
   /**
    * Make an HTTPS request to the xAI API
    * @param {string} method - HTTP method
    * @param {string} path - API path
    * @param {Object} body - Request body
+   * @param {AbortSignal} signal - Cancels the HTTP request
    * @returns {Promise<Object>} - Parsed response
    */
-  _request(method, path, body = null) {
+  _httpRequest(method, path, body = null, signal = null) {
     return new Promise((resolve, reject) => {
       const url = new URL(this.baseUrl + path);
       const bodyStr = body ? JSON.stringify(body) : null;
@@ -119,6 +234,8 @@ class Grok {
         port: url.port || 443,
         path: url.pathname + url.search,
         method,
+        agent: this.pool ? this.pool.agentFor(url.protocol) : undefined,
+        signal: signal || undefined,
         headers: {
           Authorization: `Bearer ${this.apiKey}`,
           'Content-Type': 'application/json',
@@ -142,13 +259,13 @@ class Grok {
             if (res.statusCode >= 200 && res.statusCode < 300) {
               resolve(parsed);
             } else {
-              reject(new Error(`Grok API Error: ${res.statusCode} - ${parsed.error?.message || data}`));
+              reject(Object.assign(new Error(`Grok API Error: ${res.statusCode} - ${parsed.error?.message || data}`), { statusCode: res.statusCode }));
             }
           } catch {
             if (res.statusCode >= 200 && res.statusCode < 300) {
               resolve({ raw: data });
             } else {
-              reject(new Error(`Grok API Error: ${res.statusCode} - ${data}`));
+              reject(Object.assign(new Error(`Grok API Error: ${res.statusCode} - ${data}`), { statusCode: res.statusCode }));
             }
           }
         });
@@ -0,0 +1,193 @@
+const https = require('https');
+const http = require('http');
+
+/**
+ * HTTP request tool for agents
+ *
+ * Lets a model call internal APIs with GET or POST, but only on hosts in the
+ * allow-list. Credentials never pass through the model: auth headers are
+ * configured per host as templates such as 'Bearer {{INTERNAL_API_TOKEN}}'
+ * and filled in from the secrets (process.env by default) when the request
+ * is made. Responses are cut off at maxResponseBytes and redirects are not
+ * followed, so the allow-list cannot be sidestepped.
+ *
+ * Example:
+ *   const tool = httpTool({
+ *     allowedHosts: ['api.internal.example', '*.status.example'],
+ *     auth: { 'api.internal.example': { Authorization: 'Bearer {{INTERNAL_API_TOKEN}}' } },
+ *   });
+ */
+
+const DEFAULT_MAX_RESPONSE_BYTES = 100 * 1024;
+
+/**
+ * Check a host against allow-list patterns ('example.com' or '*.example.com')
+ * @param {string} host - Hostname to check
+ * @param {Array<string>} patterns - Allowed hosts
+ * @returns {string|null} - The matching pattern, or null when the host is not allowed
+ */
+function matchHost(host, patterns) {
+  const name = host.toLowerCase();
+  return patterns.find((pattern) => {
+    const allowed = pattern.toLowerCase();
+    return allowed.startsWith('*.') ? name.endsWith(allowed.slice(1)) : name === allowed;
+  }) || null;
+}
+
+/**
+ * Replace {{NAME}} placeholders with secrets
+ * @param {string} template - Header template
+ * @param {Object} secrets - Secret values by name
+ * @returns {string} - Filled-in header value
+ */
+function fillTemplate(template, secrets) {
+  return template.replace(/\{\{\s*([A-Za-z0-9_]+)\s*\}\}/g, (match, name) => {
+    if (secrets[name] === undefined || secrets[name] === '') {
+      throw new Error(`Missing secret ${name} for HTTP tool auth`);
+    }
+    return secrets[name];
+  });
+}
+
+/**
+ * Build an HTTP request tool
+ * @param {Object} options - Tool options
+ * @param {Array<string>} options.allowedHosts - Hosts the tool may call; '*.example.com' allows subdomains
+ * @param {Object} options.auth - Header templates keyed by allowed host pattern
+ * @param {Object} options.secrets - Values for {{NAME}} placeholders (default process.env)
+ * @param {boolean} options.allowHttp - Also allow plain http:// URLs (default false)
+ * @param {number} options.maxResponseBytes - Largest response body returned (default 100 KB)
+ * @param {number} options.timeout - Milliseconds before a request is abandoned (default 10000)
+ * @param {string} options.name - Tool name (default 'http_request')
+ * @returns {Object} - Tool definition { name, description, parameters, handler }
+ */
+function httpTool(options = {}) {
+  const allowedHosts = options.allowedHosts || [];
+  if (allowedHosts.length === 0) {
+    throw new Error('httpTool requires at least one allowed host');
+  }
+
+  const auth = options.auth || {};
+  const secrets = options.secrets || process.env;
+  const protocols = options.allowHttp ? ['https:', 'http:'] : ['https:'];
+  const maxResponseBytes = options.maxResponseBytes || DEFAULT_MAX_RESPONSE_BYTES;
+  const timeout = options.timeout || 10000;
+
+  return {
+    name: options.name || 'http_request',
+    description: `Make an HTTP GET or POST request. Only these hosts are reachable: ${allowedHosts.join(', ')}`,
+    parameters: {
+      type: 'object',
+      properties: {
+        method: { type: 'string', enum: ['GET', 'POST'] },
+        url: { type: 'string', description: 'Absolute URL' },
+        body: { description: 'JSON body for POST requests' },
+      },
+      required: ['method', 'url'],
+    },
+    handler: async ({ method = 'GET', url, body }) => {
+      if (!['GET', 'POST'].includes(method)) {
+        throw new Error(`Unsupported method: ${method}`);
+      }
+
+      let target;
+      try {
+        target = new URL(url);
+      } catch {
+        throw new Error(`Invalid URL: ${url}`);
+      }
+      if (!protocols.includes(target.protocol)) {
+        throw new Error(`Protocol ${target.protocol} is not allowed`);
+      }
+      const pattern = matchHost(target.hostname, allowedHosts);
+      if (!pattern) {
+        throw new Error(`Host ${target.hostname} is not allowed`);
+      }
+
+      const headers = { Accept: 'application/json, text/plain;q=0.9, */*;q=0.5' };
+      for (const [header, template] of Object.entries(auth[pattern] || {})) {
+        headers[header] = fillTemplate(template, secrets);
+      }
+
+      const bodyStr = method === 'POST' && body !== undefined
+        ? (typeof body === 'string' ? body : JSON.stringify(body))
+        : null;
+      if (bodyStr) {
+        headers['Content-Type'] = typeof body === 'string' ? 'text/plain' : 'application/json';
+        headers['Content-Length'] = Buffer.byteLength(bodyStr);
+      }
+
+      return send(target, method, headers, bodyStr, { maxResponseBytes, timeout });
+    },
+  };
+}
+
+/**
+ * Send the request and collect at most maxResponseBytes of the response
+ * @param {URL} target - Request URL
+ * @param {string} method - HTTP method
+ * @param {Object} headers - Request headers
+ * @param {string|null} bodyStr - Request body
+ * @param {Object} limits - { maxResponseBytes, timeout }
+ * @returns {Promise<Object>} - { status, contentType, location, body, truncated }
+ */
+function send(target, method, headers, bodyStr, { maxResponseBytes, timeout }) {
+  const transport = target.protocol === 'https:' ? https : http;
+
+  return new Promise((resolve, reject) => {
+    const req = transport.request({
+      hostname: target.hostname,
+      port: target.port || (target.protocol === 'https:' ? 443 : 80),
+      path: target.pathname + target.search,
+      method,
+      headers,
+      timeout,
+    }, (res) => {
+      const chunks = [];
+      let received = 0;
+      let truncated = false;
+
+      const finish = () => resolve({
+        status: res.statusCode,
+        contentType: res.headers['content-type'] || null,
+        location: res.headers.location || null,
+        body: Buffer.concat(chunks).toString('utf8'),
+        truncated,
+      });
+
+      res.on('data', (chunk) => {
+        if (truncated) {
+          return;
+        }
+        const room = maxResponseBytes - received;
+        if (chunk.length > room) {
+          chunks.push(chunk.subarray(0, room));
+          truncated = true;
+          res.destroy();
+          finish();
+          return;
+        }
+        chunks.push(chunk);
+        received += chunk.length;
+      });
+      res.on('end', finish);
+    });
+
+    req.on('timeout', () => {
+      req.destroy(new Error(`timed out after ${timeout}ms`));
+    });
+    req.on('error', (error) => {
+      reject(new Error(`HTTP tool request failed: ${error.message}`));
+    });
+
+    if (bodyStr) {
+      req.write(bodyStr);
+    }
+    req.end();
+  });
+}
+
+module.exports = {
+  httpTool,
+  matchHost,
+};
@@ -0,0 +1,93 @@
+/**
+ * Helpers for multimodal chat messages
+ *
+ * Images may be given as http(s) URLs, data URLs, Buffers or
+ * { data, mimeType } objects. Raw bytes are base64-encoded into data URLs
+ * after checking the size and format limits of the vision APIs.
+ */
+
+const MAX_IMAGE_BYTES = 20 * 1024 * 1024;
+
+const SUPPORTED_MIME_TYPES = ['image/png', 'image/jpeg', 'image/gif', 'image/webp'];
+
+/**
+ * Guess an image type from its leading bytes
+ * @param {Buffer} data - Image bytes
+ * @returns {string|null} - MIME type, or null when unrecognized
+ */
+function detectMimeType(data) {
+  if (data.length >= 8 && data.subarray(0, 8).equals(Buffer.from([0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a]))) {
+    return 'image/png';
+  }
+  if (data.length >= 3 && data[0] === 0xff && data[1] === 0xd8 && data[2] === 0xff) {
+    return 'image/jpeg';
+  }
+  if (data.length >= 6 && ['GIF87a', 'GIF89a'].includes(data.subarray(0, 6).toString('ascii'))) {
+    return 'image/gif';
+  }
+  if (data.length >= 12 && data.subarray(0, 4).toString('ascii') === 'RIFF' && data.subarray(8, 12).toString('ascii') === 'WEBP') {
+    return 'image/webp';
+  }
+  return null;
+}
+
+/**
+ * Build an image_url content part for a chat message
+ * @param {string|Buffer|Object} image - URL, data URL, Buffer or { data, mimeType }
+ * @param {Object} options - Part options
+ * @param {string} options.detail - 'low', 'high' or 'auto'
+ * @returns {Object} - { type: 'image_url', image_url: { url, detail? } }
+ */
+function imagePart(image, options = {}) {
+  let url;
+
+  if (typeof image === 'string') {
+    if (!/^(https?:|data:image\/)/.test(image)) {
+      throw new Error('Image strings must be http(s) URLs or image data URLs');
+    }
+    url = image;
+  } else {
+    const data = Buffer.isBuffer(image) ? image : image && image.data;
+    if (!Buffer.isBuffer(data)) {
+      throw new Error('Images must be a URL, a Buffer or { data: Buffer, mimeType }');
+    }
+    if (data.length > MAX_IMAGE_BYTES) {
+      throw new Error(`Image is ${data.length} bytes; the limit is ${MAX_IMAGE_BYTES} bytes`);
+    }
+
+    const mimeType = image.mimeType || detectMimeType(data);
+    if (!SUPPORTED_MIME_TYPES.includes(mimeType)) {
+      throw new Error(`Unsupported image type: ${mimeType || 'unknown'}. Use PNG, JPEG, GIF or WebP.`);
+    }
+    url = `data:${mimeType};base64,${data.toString('base64')}`;
+  }
+
+  return {
+    type: 'image_url',
+    image_url: options.detail ? { url, detail: options.detail } : { url },
+  };
+}
+
+/**
+ * Build a user message combining text and images
+ * @param {string} text - Message text
+ * @param {Array} images - Images accepted by imagePart()
+ * @param {Object} options - Options passed to imagePart()
+ * @returns {Object} - { role: 'user', content: [...] }
+ */
+function visionMessage(text, images = [], options = {}) {
+  return {
+    role: 'user',
+    content: [
+      ...(text ? [{ type: 'text', text }] : []),
+      ...images.map((image) => imagePart(image, options)),
+    ],
+  };
+}
+
+module.exports = {
+  MAX_IMAGE_BYTES,
+  detectMimeType,
+  imagePart,
+  visionMessage,
+};
@@ -1,7 +1,52 @@
 const ChatGPT = require('./chatgpt');
 const OpenClaw = require('./openclaw');
 const Grok = require('./grok');
+const Claude = require('./claude');
+const LocalProvider = require('./localProvider');
+const FailoverProvider = require('./failoverProvider');
+const Moderator = require('./moderator');
+const PolicyEngine = require('./policyEngine');
+const ApprovalQueue = require('./approvalQueue');
+const AuditLog = require('./auditLog');
 const Replit = require('./replit');
+const GitHub = require('./github');
+const RateLimiter = require('./rateLimiter');
+const ConnectionPool = require('./connectionPool');
+const { calendarTools } = require('./calendarTools');
+const { httpTool } = require('./httpTool');
+const { fileTools } = require('./fileTools');
+const { shellTool } = require('./shellTool');
+const Metrics = require('./metrics');
+const Dashboard = require('./dashboard');
+const McpServer = require('./mcpServer');
+const OpenAIProxy = require('./openaiProxy');
+const BatchJob = require('./batchJob');
+const EvalSuite = require('./evalSuite');
+const Experiment = require('./experiment');
+const SemanticCache = require('./semanticCache');
+const CircuitBreaker = require('./circuitBreaker');
+const WebhookServer = require('./webhookServer');
+const UsageTracker = require('./usageTracker');
+const BillingMeter = require('./billingMeter');
+const ResponseCache = require('./responseCache');
+const { PromptTemplate, PromptRegistry } = require('./prompts');
+const Conversation = require('./conversation');
+const { ToolRegistry } = require('./tools');
+const { imagePart, visionMessage } = require('./images');
+const { completeJSON, validateSchema } = require('./structured');
+const { describeConfig } = require('./describe');
+const { paginate, iterateAll } = require('./pagination');
+const { diagnose, formatReport } = require('./doctor');
+const Agent = require('./agent');
+const Orchestrator = require('./orchestrator');
+const Scheduler = require('./scheduler');
+const Pipeline = require('./pipeline');
+const Saga = require('./saga');
+const ProgressHandle = require('./progressHandle');
+const Recorder = require('./recorder');
+const Snapshot = require('./snapshot');
+const StreamTee = require('./streamTee');
+const Anonymizer = require('./anonymizer');
 
 /**
  * Main entry point for AI-Time-Machines integrations
@@ -12,7 +57,57 @@ module.exports = {
   ChatGPT,
   OpenClaw,
   Grok,
+  Claude,
+  LocalProvider,
+  FailoverProvider,
+  Moderator,
+  PolicyEngine,
+  ApprovalQueue,
+  AuditLog,
   Replit,
+  GitHub,
+  RateLimiter,
+  ConnectionPool,
+  calendarTools,
+  httpTool,
+  fileTools,
+  shellTool,
+  Metrics,
+  Dashboard,
+  McpServer,
+  OpenAIProxy,
+  BatchJob,
+  EvalSuite,
+  Experiment,
+  SemanticCache,
+  CircuitBreaker,
+  WebhookServer,
+  UsageTracker,
+  BillingMeter,
+  ResponseCache,
+  PromptTemplate,
+  PromptRegistry,
+  Conversation,
+  ToolRegistry,
+  imagePart,
+  visionMessage,
+  completeJSON,
+  validateSchema,
+  describeConfig,
+  paginate,
+  iterateAll,
+  diagnose,
+  formatReport,
+  Agent,
+  Orchestrator,
+  Scheduler,
+  Pipeline,
+  Saga,
+  ProgressHandle,
+  Recorder,
+  Snapshot,
+  StreamTee,
+  Anonymizer,
 };
 
 /**
@@ -0,0 +1,269 @@
+const https = require('https');
+const http = require('http');
+const { observe } = require('./observer');
+const { runWithTools } = require('./tools');
+require('dotenv').config();
+
+/**
+ * LocalProvider Class
+ * Talks to a self-hosted model server through its OpenAI-compatible chat
+ * completions endpoint, so the conversation, tool and agent helpers work
+ * without cloud API keys. Works with Ollama (the default) and with any other
+ * OpenAI-compatible server such as llama.cpp, vLLM or LM Studio.
+ */
+class LocalProvider {
+  /**
+   * @param {string} baseUrl - Server URL (falls back to LOCAL_MODEL_URL, then http://localhost:11434)
+   * @param {Object} options - Client options
+   * @param {string} options.flavor - 'ollama' or 'openai'; selects the model list and pull endpoints (default 'ollama')
+   * @param {string} options.apiKey - Bearer token for servers that require one
+   * @param {string} options.model - Default model (default 'llama3')
+   * @param {RateLimiter} options.rateLimiter - Optional limiter, may be shared with other clients
+   * @param {ConnectionPool} options.pool - Optional keep-alive connection pool
+   * @param {Object} options.observer - Optional hooks notified of every outbound call
+   * @param {UsageTracker} options.usageTracker - Optional token and cost tracker
+   * @param {ResponseCache} options.cache - Optional response cache
+   * @param {Recorder} options.recorder - Optional recorder for capturing or replaying calls
+   */
+  constructor(baseUrl = null, options = {}) {
+    const flavor = options.flavor || 'ollama';
+    if (!['ollama', 'openai'].includes(flavor)) {
+      throw new Error(`Unsupported local server flavor: ${flavor}. Use 'ollama' or 'openai'.`);
+    }
+
+    this.baseUrl = (baseUrl || process.env.LOCAL_MODEL_URL || 'http://localhost:11434').replace(/\/+$/, '');
+    this.flavor = flavor;
+    this.apiKey = options.apiKey || null;
+    this.model = options.model || 'llama3';
+    this.rateLimiter = options.rateLimiter || null;
+    this.pool = options.pool || null;
+    this.observer = options.observer || null;
+    this.usageTracker = options.usageTracker || null;
+    this.cache = options.cache || null;
+    this.recorder = options.recorder || null;
+  }
+
+  /**
+   * Send a message to the local model and get a response
+   * @param {string} message - The message to send
+   * @param {Object} options - Additional options (temperature, max_tokens, etc.; cache: false skips the cache; signal cancels the request)
+   * @returns {Promise<string>} - The response from the model
+   */
+  async chat(message, options = {}) {
+    return this.conversation([{ role: 'user', content: message }], options);
+  }
+
+  /**
+   * Have a conversation with context
+   * @param {Array} messages - Array of message objects with role and content
+   * @param {Object} options - Additional options (cache: false skips the cache; signal cancels the request)
+   * @returns {Promise<string>} - The response from the model
+   */
+  async conversation(messages, options = {}) {
+    const payload = {
+      model: options.model || this.model,
+      messages,
+      temperature: options.temperature !== undefined ? options.temperature : 0.7,
+      max_tokens: options.max_tokens || 1000,
+      stream: false,
+    };
+
+    const cacheKey = this.cache && options.cache !== false ? this.cache.keyFor('local', payload) : null;
+    if (cacheKey) {
+      const cached = await this.cache.get(cacheKey);
+      if (cached !== undefined) {
+        return cached;
+      }
+    }
+
+    const response = await this._sendCompletion(payload, options.signal);
+    const content = response.choices[0].message.content;
+    if (cacheKey) {
+      await this.cache.set(cacheKey, content);
+    }
+
+    return content;
+  }
+
+  /**
+   * Have a conversation in which the model may call registered tools.
+   * Requires a model and server with tool calling support.
+   * @param {Array} messages - Array of message objects with role and content
+   * @param {ToolRegistry} tools - Tools the model may call
+   * @param {Object} options - Additional options (autoExecute, maxIterations, temperature, etc.; signal cancels the requests)
+   * @returns {Promise<Object>} - { content, toolCalls, messages }
+   */
+  async chatWithTools(messages, tools, options = {}) {
+    return runWithTools(
+      async (history) => {
+        const response = await this._sendCompletion({
+          model: options.model || this.model,
+          messages: history,
+          temperature: options.temperature !== undefined ? options.temperature : 0.7,
+          max_tokens: options.max_tokens || 1000,
+          stream: false,
+          tools: tools.definitions(),
+        }, options.signal);
+        return response.choices[0].message;
+      },
+      messages,
+      tools,
+      { autoExecute: options.autoExecute, maxIterations: options.maxIterations }
+    );
+  }
+
+  /**
+   * List the models installed on the server
+   * @returns {Promise<Array<string>>} - Model names
+   */
+  async listModels() {
+    if (this.flavor === 'ollama') {
+      const response = await this._request('GET', '/api/tags');
+      return (response.models || []).map((model) => model.name);
+    }
+
+    const response = await this._request('GET', '/v1/models');
+    return (response.data || []).map((model) => model.id);
+  }
+
+  /**
+   * Download a model onto an Ollama server
+   * @param {string} model - Model name (e.g. 'llama3', 'mistral:7b')
+   * @returns {Promise<Object>} - Final pull status
+   */
+  async pullModel(model) {
+    if (this.flavor !== 'ollama') {
+      throw new Error('Pulling models is only supported by Ollama servers');
+    }
+
+    return this._request('POST', '/api/pull', { name: model, stream: false });
+  }
+
+  /**
+   * Set the default model to use
+   * @param {string} model - The model name
+   */
+  setModel(model) {
+    this.model = model;
+  }
+
+  /**
+   * Send a chat completion request and return the raw response
+   * @param {Object} payload - Chat completion request body
+   * @param {AbortSignal} signal - Cancels the HTTP request
+   * @returns {Promise<Object>} - The API response
+   */
+  async _sendCompletion(payload, signal = null) {
+    if (this.usageTracker) {
+      this.usageTracker.checkBudget();
+    }
+
+    const response = await this._schedule(() => observe(
+      this.observer,
+      { provider: 'local', operation: 'chat.completions', model: payload.model },
+      () => this._request('POST', '/v1/chat/completions', payload, signal)
+    ));
+
+    if (this.usageTracker) {
+      this.usageTracker.record(payload.model, response.usage);
+    }
+
+    return response;
+  }
+
+  /**
+   * Run a request through the rate limiter when one is configured
+   * @param {Function} task - Async function performing the request
+   * @returns {Promise<*>} - The task result
+   */
+  _schedule(task) {
+    return this.rateLimiter ? this.rateLimiter.schedule(task) : task();
+  }
+
+  /**
+   * Make a request to the local server, through the recorder when one is configured
+   * @param {string} method - HTTP method
+   * @param {string} path - API path
+   * @param {Object} body - Request body
+   * @returns {Promise<Object>} - Parsed response
+   */
+  _request(method, path, body = null, signal = null) {
+    if (this.recorder) {
+      return this.recorder.capture('local', `${method} ${path}`, body, () => this._httpRequest(method, path, body, signal), { signal });
+    }
+    return this._httpRequest(method, path, body, signal);
+  }
+
+  /**
+   * Make an HTTP or HTTPS request to the local server
+   * @param {string} method - HTTP method
+   * @param {string} path - API path
+   * @param {Object} body - Request body
+   * @param {AbortSignal} signal - Cancels the HTTP request
+   * @returns {Promise<Object>} - Parsed response
+   */
+  _httpRequest(method, path, body = null, signal = null) {
+    return new Promise((resolve, reject) => {
+      const url = new URL(this.baseUrl + path);
+      const isHttps = url.protocol === 'https:';
+      const transport = isHttps ? https : http;
+      const bodyStr = body ? JSON.stringify(body) : null;
+
+      const options = {
+        hostname: url.hostname,
+        port: url.port || (isHttps ? 443 : 80),
+        path: url.pathname + url.search,
+        method,
+        agent: this.pool ? this.pool.agentFor(url.protocol) : undefined,
+        signal: signal || undefined,
+        headers: {
+          'Content-Type': 'application/json',
+        },
+      };
+
+      if (this.apiKey) {
+        options.headers.Authorization = `Bearer ${this.apiKey}`;
+      }
+      if (bodyStr) {
+        options.headers['Content-Length'] = Buffer.byteLength(bodyStr);
+      }
+
+      const req = transport.request(options, (res) => {
+        let data = '';
+
+        res.on('data', (chunk) => {
+          data += chunk;
+        });
+
+        res.on('end', () => {
+          try {
+            const parsed = JSON.parse(data);
+            if (res.statusCode >= 200 && res.statusCode < 300) {
+              resolve(parsed);
+            } else {
+              reject(Object.assign(new Error(`Local Model Error: ${res.statusCode} - ${parsed.error?.message || parsed.error || data}`), { statusCode: res.statusCode }));
+            }
+          } catch {
+            if (res.statusCode >= 200 && res.statusCode < 300) {
+              resolve({ raw: data });
+            } else {
+              reject(Object.assign(new Error(`Local Model Error: ${res.statusCode} - ${data}`), { statusCode: res.statusCode }));
+            }
+          }
+        });
+      });
+
+      req.on('error', (error) => {
+        reject(new Error(`Local Model Request Error: ${error.message}`));
+      });
+
+      if (bodyStr) {
+        req.write(bodyStr);
+      }
+
+      req.end();
+    });
+  }
+}
+
+module.exports = LocalProvider;
@@ -0,0 +1,267 @@
+const http = require('http');
+const { safeEqual, isJSONRequest, readBody, sendJSON, sendJSONAndClose } = require('./utils');
+const { version } = require('../package.json');
+
+const PROTOCOL_VERSION = '2024-11-05';
+
+// JSON-RPC 2.0 error codes
+const PARSE_ERROR = -32700;
+const INVALID_REQUEST = -32600;
+const METHOD_NOT_FOUND = -32601;
+const INVALID_PARAMS = -32602;
+const INTERNAL_ERROR = -32603;
+
+const rpcError = (id, code, message) => ({ jsonrpc: '2.0', id, error: { code, message } });
+
+/**
+ * McpServer Class
+ * Exposes a ToolRegistry over the Model Context Protocol, so external
+ * assistants and IDE agents can list and call the same tools an Agent uses.
+ * Supports the stdio transport (one JSON-RPC message per line) through
+ * serve(), and plain HTTP POST of JSON-RPC messages through handler() and
+ * listen().
+ *
+ * With a PolicyEngine, each call is checked like an Agent tool call; calls
+ * the policy denies or holds for approval are returned as tool errors. With
+ * an AuditLog, every call is recorded under the 'mcp' component.
+ *
+ * Over HTTP, requests must be application/json, carry the bearer token when
+ * one is configured, and come from an allow-listed Origin when they carry
+ * one. Binding to localhost alone does not stop a web page the user visits
+ * from posting to the server, so the Origin check is always applied.
+ *
+ * Example:
+ *   const server = new McpServer({ tools, policy });
+ *   await server.serve(readline.createInterface({ input: process.stdin }), process.stdout);
+ */
+class McpServer {
+  /**
+   * @param {Object} options - Server options
+   * @param {ToolRegistry} options.tools - Tools to expose
+   * @param {string} options.name - Server name reported to clients (default 'ai-time-machines')
+   * @param {PolicyEngine} options.policy - Rules checked before each tool call
+   * @param {AuditLog} options.auditLog - Log that records each tool call
+   * @param {string} options.token - Bearer token required on HTTP requests
+   * @param {Array<string>} options.allowedOrigins - Browser origins allowed over HTTP (default none)
+   * @param {number} options.maxBodyBytes - Largest accepted HTTP body (default 1 MB)
+   */
+  constructor(options = {}) {
+    if (!options.tools) {
+      throw new Error('McpServer requires a tools registry');
+    }
+
+    this.tools = options.tools;
+    this.name = options.name || 'ai-time-machines';
+    this.policy = options.policy || null;
+    this.auditLog = options.auditLog || null;
+    this.token = options.token || null;
+    this.allowedOrigins = options.allowedOrigins || [];
+    this.maxBodyBytes = options.maxBodyBytes || 1024 * 1024;
+  }
+
+  /**
+   * Handle one JSON-RPC message
+   * @param {Object} message - Request or notification
+   * @returns {Promise<Object|null>} - Response, or null for notifications
+   */
+  async handle(message) {
+    if (!message || message.jsonrpc !== '2.0' || typeof message.method !== 'string') {
+      return rpcError(message && message.id !== undefined ? message.id : null, INVALID_REQUEST, 'Invalid request');
+    }
+
+    const { id, method, params = {} } = message;
+    const isNotification = id === undefined;
+    let result;
+
+    try {
+      switch (method) {
+        case 'initialize':
+          result = {
+            protocolVersion: PROTOCOL_VERSION,
+            capabilities: { tools: {} },
+            serverInfo: { name: this.name, version },
+          };
+          break;
+
+        case 'ping':
+          result = {};
+          break;
+
+        case 'tools/list':
+          result = {
+            tools: this.tools.definitions().map(({ function: tool }) => ({
+              name: tool.name,
+              description: tool.description,
+              inputSchema: tool.parameters,
+            })),
+          };
+          break;
+
+        case 'tools/call':
+          if (typeof params.name !== 'string') {
+            return rpcError(id, INVALID_PARAMS, 'tools/call requires a tool name');
+          }
+          if (!this.tools.has(params.name)) {
+            return rpcError(id, INVALID_PARAMS, `Unknown tool: ${params.name}`);
+          }
+          result = await this._callTool(params.name, params.arguments || {});
+          break;
+
+        default:
+          if (isNotification) {
+            // Notifications such as notifications/initialized need no reply
+            return null;
+          }
+          return rpcError(id, METHOD_NOT_FOUND, `Method not found: ${method}`);
+      }
+    } catch (error) {
+      return isNotification ? null : rpcError(id, INTERNAL_ERROR, error.message);
+    }
+
+    return isNotification ? null : { jsonrpc: '2.0', id, result };
+  }
+
+  /**
+   * Serve the stdio transport
+   * @param {AsyncIterable<string>} lines - Incoming messages, one per line (e.g. a readline interface)
+   * @param {Object} output - Writable for responses (e.g. process.stdout)
+   * @returns {Promise<void>} - Resolves when the input ends and all calls have been answered
+   */
+  async serve(lines, output) {
+    const inFlight = new Set();
+
+    for await (const line of lines) {
+      if (!line.trim()) {
+        continue;
+      }
+      // Calls run concurrently so a slow tool does not hold up pings or listings
+      const reply = this._handleText(line).then((response) => {
+        if (response) {
+          output.write(`${JSON.stringify(response)}\n`);
+        }
+      });
+      inFlight.add(reply);
+      reply.finally(() => inFlight.delete(reply));
+    }
+
+    await Promise.all(inFlight);
+  }
+
+  /**
+   * Create a request handler for the HTTP transport
+   * @returns {Function} - (req, res) handler for http.createServer
+   */
+  handler() {
+    return (req, res) => {
+      this._handleHttp(req, res).catch(() => {
+        if (!res.headersSent) {
+          sendJSON(res, 500, rpcError(null, INTERNAL_ERROR, 'Internal error'));
+        }
+      });
+    };
+  }
+
+  /**
+   * Start a standalone HTTP server
+   * @param {number} port - Port to listen on
+   * @param {string} host - Interface to bind
+   * @returns {Promise<http.Server>} - The listening server
+   */
+  listen(port = 3002, host = '127.0.0.1') {
+    const server = http.createServer(this.handler());
+
+    return new Promise((resolve, reject) => {
+      server.once('error', reject);
+      server.listen(port, host, () => resolve(server));
+    });
+  }
+
+  async _callTool(name, args) {
+    let text;
+    let isError = false;
+
+    const decision = this.policy ? await this.policy.checkToolCall(name, args) : { allowed: true };
+    if (!decision.allowed) {
+      text = `Error: denied by policy: ${decision.reason}`;
+      isError = true;
+    } else {
+      const { content } = await this.tools.execute({
+        id: name,
+        function: { name, arguments: JSON.stringify(args) },
+      });
+      text = content;
+      // ToolRegistry reports handler failures as "Error: ..." content
+      isError = content.startsWith('Error: ');
+    }
+
+    if (this.auditLog) {
+      await this.auditLog.record({
+        component: 'mcp',
+        action: 'tools/call',
+        tags: [name],
+        data: { tool: name, args, isError },
+      });
+    }
+
+    return { content: [{ type: 'text', text }], isError };
+  }
+
+  async _handleText(text) {
+    let message;
+    try {
+      message = JSON.parse(text);
+    } catch {
+      return rpcError(null, PARSE_ERROR, 'Parse error');
+    }
+    return this.handle(message);
+  }
+
+  async _handleHttp(req, res) {
+    if (req.method !== 'POST') {
+      res.writeHead(405, { Allow: 'POST' });
+      res.end();
+      return;
+    }
+    // Requests without an Origin come from non-browser clients
+    const { origin } = req.headers;
+    if (origin !== undefined && !this.allowedOrigins.includes(origin)) {
+      sendJSON(res, 403, rpcError(null, INVALID_REQUEST, `Origin not allowed: ${origin}`));
+      return;
+    }
+    if (!this._authorized(req)) {
+      res.setHeader('WWW-Authenticate', 'Bearer');
+      sendJSON(res, 401, rpcError(null, INVALID_REQUEST, 'Invalid or missing bearer token'));
+      return;
+    }
+    if (!isJSONRequest(req)) {
+      sendJSON(res, 415, rpcError(null, INVALID_REQUEST, 'Content-Type must be application/json'));
+      return;
+    }
+
+    let body;
+    try {
+      body = await readBody(req, this.maxBodyBytes);
+    } catch (error) {
+      sendJSONAndClose(req, res, 413, rpcError(null, INVALID_REQUEST, error.message));
+      return;
+    }
+
+    const response = await this._handleText(body);
+    if (response) {
+      sendJSON(res, 200, response);
+    } else {
+      res.writeHead(202);
+      res.end();
+    }
+  }
+
+  _authorized(req) {
+    if (!this.token) {
+      return true;
+    }
+    const match = (req.headers.authorization || '').match(/^Bearer (.+)$/);
+    return Boolean(match) && safeEqual(match[1], this.token);
+  }
+}
+
+module.exports = McpServer;
@@ -0,0 +1,186 @@
+const http = require('http');
+
+const DEFAULT_BUCKETS = [0.1, 0.25, 0.5, 1, 2.5, 5, 10, 30];
+
+/**
+ * Metrics Class
+ * Observer that aggregates client calls into Prometheus-style counters and
+ * histograms. Pass an instance as options.observer to any provider client.
+ */
+class Metrics {
+  /**
+   * @param {Object} options - Metrics options
+   * @param {string} options.prefix - Metric name prefix
+   * @param {Array<number>} options.buckets - Latency histogram buckets in seconds
+   * @param {number} options.maxRecentErrors - Failed calls kept in recentErrors (default 20)
+   */
+  constructor(options = {}) {
+    this.prefix = options.prefix || 'ai_time_machines';
+    this.buckets = options.buckets || DEFAULT_BUCKETS;
+    this.maxRecentErrors = options.maxRecentErrors !== undefined ? options.maxRecentErrors : 20;
+    this.reset();
+  }
+
+  /**
+   * Clear all collected values
+   */
+  reset() {
+    this.requests = new Map();
+    this.errors = new Map();
+    this.tokens = new Map();
+    this.latency = new Map();
+    this.recentErrors = [];
+  }
+
+  /**
+   * Observer hook: count a request
+   * @param {Object} event - Request event
+   */
+  onRequest(event) {
+    this._increment(this.requests, this._labels(event), 1);
+  }
+
+  /**
+   * Observer hook: record latency and token usage
+   * @param {Object} event - Response event
+   */
+  onResponse(event) {
+    const labels = this._labels(event);
+    this._recordLatency(labels, event.latencyMs);
+
+    if (event.usage) {
+      this._increment(this.tokens, { ...labels, type: 'prompt' }, event.usage.prompt_tokens || 0);
+      this._increment(this.tokens, { ...labels, type: 'completion' }, event.usage.completion_tokens || 0);
+    }
+  }
+
+  /**
+   * Observer hook: count an error and record latency
+   * @param {Object} event - Error event
+   */
+  onError(event) {
+    const labels = this._labels(event);
+    this._increment(this.errors, labels, 1);
+    this._recordLatency(labels, event.latencyMs);
+
+    this.recentErrors.push({
+      timestamp: new Date().toISOString(),
+      ...labels,
+      operation: event.operation || null,
+      message: event.error ? event.error.message : null,
+    });
+    if (this.recentErrors.length > this.maxRecentErrors) {
+      this.recentErrors.splice(0, this.recentErrors.length - this.maxRecentErrors);
+    }
+  }
+
+  /**
+   * Render all metrics in the Prometheus text exposition format
+   * @returns {string} - Metrics text
+   */
+  toPrometheus() {
+    const lines = [];
+    const name = (suffix) => `${this.prefix}_${suffix}`;
+
+    const counter = (metric, help, values) => {
+      lines.push(`# HELP ${metric} ${help}`);
+      lines.push(`# TYPE ${metric} counter`);
+      for (const { labels, value } of values.values()) {
+        lines.push(`${metric}${formatLabels(labels)} ${value}`);
+      }
+    };
+
+    counter(name('requests_total'), 'Outbound provider requests.', this.requests);
+    counter(name('request_errors_total'), 'Outbound provider requests that failed.', this.errors);
+    counter(name('tokens_total'), 'Tokens consumed by provider requests.', this.tokens);
+
+    const histogram = name('request_duration_seconds');
+    lines.push(`# HELP ${histogram} Outbound provider request latency.`);
+    lines.push(`# TYPE ${histogram} histogram`);
+    for (const { labels, counts, sum, count } of this.latency.values()) {
+      this.buckets.forEach((bucket, i) => {
+        lines.push(`${histogram}_bucket${formatLabels({ ...labels, le: String(bucket) })} ${counts[i]}`);
+      });
+      lines.push(`${histogram}_bucket${formatLabels({ ...labels, le: '+Inf' })} ${count}`);
+      lines.push(`${histogram}_sum${formatLabels(labels)} ${sum}`);
+      lines.push(`${histogram}_count${formatLabels(labels)} ${count}`);
+    }
+
+    return lines.join('\n') + '\n';
+  }
+
+  /**
+   * Start an embedded HTTP exporter serving GET /metrics
+   * @param {number} port - Port to listen on
+   * @param {string} host - Interface to bind
+   * @returns {Promise<http.Server>} - The listening server
+   */
+  listen(port = 9464, host = '127.0.0.1') {
+    const server = http.createServer((req, res) => {
+      if (req.method === 'GET' && req.url === '/metrics') {
+        res.writeHead(200, { 'Content-Type': 'text/plain; version=0.0.4' });
+        res.end(this.toPrometheus());
+      } else {
+        res.writeHead(404);
+        res.end();
+      }
+    });
+
+    return new Promise((resolve, reject) => {
+      server.once('error', reject);
+      server.listen(port, host, () => resolve(server));
+    });
+  }
+
+  _labels(event) {
+    return {
+      provider: event.provider || 'unknown',
+      model: event.model || 'unknown',
+    };
+  }
+
+  _increment(map, labels, amount) {
+    const key = JSON.stringify(labels);
+    const entry = map.get(key) || { labels, value: 0 };
+    entry.value += amount;
+    map.set(key, entry);
+  }
+
+  _recordLatency(labels, latencyMs) {
+    if (typeof latencyMs !== 'number') {
+      return;
+    }
+
+    const key = JSON.stringify(labels);
+    const entry = this.latency.get(key) || {
+      labels,
+      counts: this.buckets.map(() => 0),
+      sum: 0,
+      count: 0,
+    };
+    const seconds = latencyMs / 1000;
+
+    this.buckets.forEach((bucket, i) => {
+      if (seconds <= bucket) {
+        entry.counts[i]++;
+      }
+    });
+    entry.sum += seconds;
+    entry.count++;
+    this.latency.set(key, entry);
+  }
+}
+
+/**
+ * Format a label set as {key="value",...}
+ * @param {Object} labels - Label map
+ * @returns {string} - Prometheus label string
+ */
+function formatLabels(labels) {
+  const pairs = Object.entries(labels).map(
+    ([key, value]) => `${key}="${String(value).replace(/\\/g, '\\\\').replace(/"/g, '\\"').replace(/\n/g, '\\n')}"`
+  );
+  return pairs.length ? `{${pairs.join(',')}}` : '';
+}
+
+module.exports = Metrics;
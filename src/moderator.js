@@ -0,0 +1,146 @@
+/**
+ * Moderator Class
+ * Checks prompts and responses for unsafe content, either with a client
+ * exposing moderate(text) (ChatGPT) or with any async classifier returning
+ * { flagged, categories }.
+ *
+ * Unsafe content is either blocked (an error is thrown) or flagged (the
+ * onFlag callback is told and the call goes ahead).
+ */
+class Moderator {
+  /**
+   * @param {Object} options - Moderator options
+   * @param {Object} options.client - Client with moderate(text), such as ChatGPT
+   * @param {Function} options.classifier - Async (text) => { flagged, categories }; used instead of a client
+   * @param {string} options.action - 'block' or 'flag' (default 'block')
+   * @param {Function} options.onFlag - Called with { stage, text, verdict } for unsafe content
+   */
+  constructor(options = {}) {
+    const { client, classifier, action = 'block' } = options;
+
+    if (typeof classifier === 'function') {
+      this.classifier = classifier;
+    } else if (client && typeof client.moderate === 'function') {
+      this.classifier = (text) => client.moderate(text);
+    } else {
+      throw new Error('Moderator requires a client with moderate(text) or a classifier function');
+    }
+
+    if (!['block', 'flag'].includes(action)) {
+      throw new Error(`Unsupported moderation action: ${action}. Use 'block' or 'flag'.`);
+    }
+
+    this.action = action;
+    this.onFlag = options.onFlag || null;
+  }
+
+  /**
+   * Classify text
+   * @param {string} text - Text to check
+   * @returns {Promise<Object>} - Verdict { flagged, categories, scores }
+   */
+  async moderate(text) {
+    const verdict = await this.classifier(text);
+    return {
+      flagged: Boolean(verdict && verdict.flagged),
+      categories: (verdict && verdict.categories) || [],
+      scores: (verdict && verdict.scores) || {},
+    };
+  }
+
+  /**
+   * Check text and apply the configured action
+   * @param {string} text - Text to check
+   * @param {string} stage - 'input' or 'output'
+   * @returns {Promise<Object>} - Verdict
+   */
+  async check(text, stage) {
+    if (!text) {
+      return { flagged: false, categories: [], scores: {} };
+    }
+
+    const verdict = await this.moderate(text);
+    if (!verdict.flagged) {
+      return verdict;
+    }
+
+    if (this.onFlag) {
+      await this.onFlag({ stage, text, verdict });
+    }
+    if (this.action === 'block') {
+      const error = new Error(`Moderation blocked ${stage}: ${verdict.categories.join(', ') || 'flagged'}`);
+      error.verdict = verdict;
+      error.statusCode = 400;
+      throw error;
+    }
+    return verdict;
+  }
+
+  /**
+   * Wrap an AI client so prompts and responses are moderated. The wrapper is
+   * a Proxy, so everything else on the client (model, setModel, ...) stays
+   * reachable. Streams have their input checked before the first request and
+   * their full output checked once the stream ends; text already yielded
+   * cannot be withheld, so a blocked output surfaces as an error at the end.
+   * @param {Object} client - Client with chat, conversation, chatWithTools and/or stream
+   * @param {Object} options - Wrap options
+   * @param {boolean} options.input - Check the latest user message (default true)
+   * @param {boolean} options.output - Check the response (default true)
+   * @returns {Object} - Client with the same methods
+   */
+  wrap(client, options = {}) {
+    const checkInput = options.input !== undefined ? options.input : true;
+    const checkOutput = options.output !== undefined ? options.output : true;
+    const moderator = this;
+
+    const lastUserText = (messages) => {
+      const message = [...messages].reverse().find((m) => m.role === 'user');
+      if (!message) {
+        return '';
+      }
+      return typeof message.content === 'string'
+        ? message.content
+        : (message.content || []).filter((part) => part.type === 'text').map((part) => part.text).join('\n');
+    };
+
+    const methods = {
+      chat: async (message, requestOptions) => {
+        if (checkInput) await this.check(message, 'input');
+        const response = await client.chat(message, requestOptions);
+        if (checkOutput) await this.check(response, 'output');
+        return response;
+      },
+      conversation: async (messages, requestOptions) => {
+        if (checkInput) await this.check(lastUserText(messages), 'input');
+        const response = await client.conversation(messages, requestOptions);
+        if (checkOutput) await this.check(response, 'output');
+        return response;
+      },
+      chatWithTools: async (messages, tools, requestOptions) => {
+        if (checkInput) await this.check(lastUserText(messages), 'input');
+        const result = await client.chatWithTools(messages, tools, requestOptions);
+        if (checkOutput) await this.check(result.content, 'output');
+        return result;
+      },
+      stream: async function* stream(messages, requestOptions) {
+        if (checkInput) await moderator.check(lastUserText(messages), 'input');
+        let text = '';
+        for await (const chunk of client.stream(messages, requestOptions)) {
+          text += chunk;
+          yield chunk;
+        }
+        if (checkOutput) await moderator.check(text, 'output');
+      },
+    };
+
+    return new Proxy(client, {
+      get: (target, property, receiver) => (
+        Object.prototype.hasOwnProperty.call(methods, property) && typeof target[property] === 'function'
+          ? methods[property]
+          : Reflect.get(target, property, receiver)
+      ),
+    });
+  }
+}
+
+module.exports = Moderator;
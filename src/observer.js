@@ -0,0 +1,66 @@
+/**
+ * Observer hooks for outbound client calls
+ *
+ * An observer is a plain object with any of these optional methods:
+ * - onRequest(event)  - called before the request is sent
+ * - onResponse(event) - called with latencyMs and token usage on success
+ * - onError(event)    - called with latencyMs and the error on failure
+ *
+ * Every event carries the provider, operation and model of the call, so
+ * applications can forward them to their own logging or telemetry.
+ */
+
+/**
+ * Invoke an observer hook, ignoring failures so telemetry never breaks a request
+ * @param {Object} observer - Observer object
+ * @param {string} hook - Hook name
+ * @param {Object} event - Event payload
+ */
+function notify(observer, hook, event) {
+  if (typeof observer[hook] !== 'function') {
+    return;
+  }
+
+  try {
+    observer[hook](event);
+  } catch (error) {
+    console.error(`Observer ${hook} failed: ${error.message}`);
+  }
+}
+
+/**
+ * Run a request and report it to an observer
+ * @param {Object|null} observer - Observer object, or null to skip reporting
+ * @param {Object} context - Call details (provider, operation, model)
+ * @param {Function} task - Async function performing the request
+ * @returns {Promise<*>} - The task result
+ */
+async function observe(observer, context, task) {
+  if (!observer) {
+    return task();
+  }
+
+  const startedAt = Date.now();
+  notify(observer, 'onRequest', { ...context, timestamp: new Date(startedAt).toISOString() });
+
+  try {
+    const response = await task();
+    notify(observer, 'onResponse', {
+      ...context,
+      latencyMs: Date.now() - startedAt,
+      usage: (response && response.usage) || null,
+    });
+    return response;
+  } catch (error) {
+    notify(observer, 'onError', {
+      ...context,
+      latencyMs: Date.now() - startedAt,
+      error,
+    });
+    throw error;
+  }
+}
+
+module.exports = {
+  observe,
+};
@@ -0,0 +1,203 @@
+const crypto = require('crypto');
+const http = require('http');
+const { safeEqual, isJSONRequest, readBody, sendJSON, sendJSONAndClose } = require('./utils');
+
+// Request fields forwarded to the client; everything else is ignored
+const FORWARDED_OPTIONS = ['temperature', 'max_tokens', 'top_p', 'stop', 'presence_penalty', 'frequency_penalty'];
+
+/**
+ * OpenAIProxy Class
+ * Serves an OpenAI-compatible POST /v1/chat/completions endpoint backed by
+ * any client with conversation(messages, options). Pointing an existing
+ * OpenAI SDK app at the proxy's base URL gives it whatever the client is
+ * built from: a FailoverProvider for provider failover, a Moderator-wrapped
+ * client for moderation, and clients created with a ResponseCache and a
+ * UsageTracker for caching and usage accounting.
+ *
+ * Every request must carry one of the configured API keys as a bearer token,
+ * and request bodies must be application/json, so a web page cannot spend
+ * the operator's provider credits with a simple cross-origin POST. Client
+ * errors keep the status code the provider returned (error.statusCode);
+ * anything else is reported as 502.
+ *
+ * Requests with stream: true are answered as server-sent events carrying the
+ * whole reply in one chunk. Tool calling is not supported.
+ *
+ * Example:
+ *   const proxy = new OpenAIProxy({
+ *     client: moderator.wrap(new FailoverProvider([chatgpt, claude])),
+ *     apiKeys: [process.env.PROXY_API_KEY],
+ *   });
+ *   await proxy.listen(8080); // OPENAI_BASE_URL=http://127.0.0.1:8080/v1
+ */
+class OpenAIProxy {
+  /**
+   * @param {Object} options - Proxy options
+   * @param {Object} options.client - Client with conversation(messages, options)
+   * @param {Array<string>} options.apiKeys - Bearer tokens accepted by the proxy (at least one is required)
+   * @param {boolean} options.forwardModel - Pass the requested model to the client (default false, so each provider uses its own model)
+   * @param {string} options.model - Model reported when the request names none (default 'aitm')
+   * @param {number} options.maxBodyBytes - Largest accepted body (default 1 MB)
+   */
+  constructor(options = {}) {
+    if (!options.client || typeof options.client.conversation !== 'function') {
+      throw new Error('OpenAIProxy requires a client with a conversation(messages, options) method');
+    }
+
+    this.apiKeys = options.apiKeys ? options.apiKeys.filter(Boolean) : [];
+    if (this.apiKeys.length === 0) {
+      throw new Error('OpenAIProxy requires at least one API key');
+    }
+
+    this.client = options.client;
+    this.forwardModel = options.forwardModel || false;
+    this.model = options.model || 'aitm';
+    this.maxBodyBytes = options.maxBodyBytes || 1024 * 1024;
+  }
+
+  /**
+   * Answer a chat completions request body
+   * @param {Object} body - Request body in the OpenAI format
+   * @returns {Promise<Object>} - chat.completion object
+   */
+  async complete(body) {
+    if (!Array.isArray(body.messages) || body.messages.length === 0) {
+      throw Object.assign(new Error('messages must be a non-empty array'), { statusCode: 400 });
+    }
+    if (body.tools || body.functions) {
+      throw Object.assign(new Error('Tool calling is not supported by this proxy'), { statusCode: 400 });
+    }
+
+    const options = {};
+    for (const name of FORWARDED_OPTIONS) {
+      if (body[name] !== undefined) {
+        options[name] = body[name];
+      }
+    }
+    if (this.forwardModel && body.model) {
+      options.model = body.model;
+    }
+
+    const content = await this.client.conversation(
+      body.messages.map(({ role, content: text }) => ({ role, content: text })),
+      options
+    );
+
+    return {
+      id: `chatcmpl-${crypto.randomUUID()}`,
+      object: 'chat.completion',
+      created: Math.floor(Date.now() / 1000),
+      model: body.model || this.model,
+      choices: [{ index: 0, message: { role: 'assistant', content }, finish_reason: 'stop' }],
+    };
+  }
+
+  /**
+   * Create a request handler for the proxy routes
+   * @returns {Function} - (req, res) handler for http.createServer
+   */
+  handler() {
+    return (req, res) => {
+      this._handle(req, res).catch(() => {
+        if (!res.headersSent) {
+          this._sendError(res, 500, 'Internal error', 'server_error');
+        }
+      });
+    };
+  }
+
+  /**
+   * Start a standalone proxy server
+   * @param {number} port - Port to listen on
+   * @param {string} host - Interface to bind
+   * @returns {Promise<http.Server>} - The listening server
+   */
+  listen(port = 8080, host = '127.0.0.1') {
+    const server = http.createServer(this.handler());
+
+    return new Promise((resolve, reject) => {
+      server.once('error', reject);
+      server.listen(port, host, () => resolve(server));
+    });
+  }
+
+  async _handle(req, res) {
+    const path = (req.url || '/').split('?')[0];
+
+    if (!this._authorized(req)) {
+      this._sendError(res, 401, 'Invalid API key', 'invalid_request_error');
+      return;
+    }
+    if (req.method === 'GET' && path === '/v1/models') {
+      sendJSON(res, 200, { object: 'list', data: [{ id: this.model, object: 'model', owned_by: 'aitm' }] });
+      return;
+    }
+    if (req.method !== 'POST' || path !== '/v1/chat/completions') {
+      this._sendError(res, 404, `Unknown route: ${req.method} ${path}`, 'invalid_request_error');
+      return;
+    }
+
+    if (!isJSONRequest(req)) {
+      this._sendError(res, 415, 'Content-Type must be application/json', 'invalid_request_error');
+      return;
+    }
+
+    let text;
+    try {
+      text = await readBody(req, this.maxBodyBytes);
+    } catch (error) {
+      sendJSONAndClose(req, res, 413, { error: { message: error.message, type: 'invalid_request_error', code: null } });
+      return;
+    }
+
+    let body;
+    try {
+      body = JSON.parse(text);
+    } catch (error) {
+      this._sendError(res, 400, 'Body must be JSON', 'invalid_request_error');
+      return;
+    }
+
+    let completion;
+    try {
+      completion = await this.complete(body);
+    } catch (error) {
+      const statusCode = error.statusCode || 502;
+      this._sendError(res, statusCode, error.message, statusCode < 500 ? 'invalid_request_error' : 'api_error');
+      return;
+    }
+
+    if (body.stream) {
+      this._sendStream(res, completion);
+    } else {
+      sendJSON(res, 200, completion);
+    }
+  }
+
+  _authorized(req) {
+    const match = (req.headers.authorization || '').match(/^Bearer (.+)$/);
+    return Boolean(match) && this.apiKeys.some((key) => safeEqual(match[1], key));
+  }
+
+  _sendStream(res, completion) {
+    const { id, created, model, choices: [{ message }] } = completion;
+    const chunk = (delta, finishReason) => ({
+      id,
+      object: 'chat.completion.chunk',
+      created,
+      model,
+      choices: [{ index: 0, delta, finish_reason: finishReason }],
+    });
+
+    res.writeHead(200, { 'Content-Type': 'text/event-stream', 'Cache-Control': 'no-cache' });
+    res.write(`data: ${JSON.stringify(chunk({ role: 'assistant', content: message.content }, null))}\n\n`);
+    res.write(`data: ${JSON.stringify(chunk({}, 'stop'))}\n\n`);
+    res.end('data: [DONE]\n\n');
+  }
+
+  _sendError(res, statusCode, message, type) {
+    sendJSON(res, statusCode, { error: { message, type, code: null } });
+  }
+}
+
+module.exports = OpenAIProxy;
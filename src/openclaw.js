@@ -7,7 +7,13 @@ require('dotenv').config();
  * Provides an interface to interact with the OpenClaw Analytics & Automation Platform
  */
 class OpenClaw {
-  constructor(apiKey = null) {
+  /**
+   * @param {string} apiKey - OpenClaw API key (falls back to OPENCLAW_API_KEY)
+   * @param {Object} options - Client options
+   * @param {ConnectionPool} options.pool - Optional keep-alive connection pool
+   * @param {Recorder} options.recorder - Optional recorder for capturing or replaying calls
+   */
+  constructor(apiKey = null, options = {}) {
     // Use provided API key or fall back to environment variable
     const key = apiKey || process.env.OPENCLAW_API_KEY;
 
@@ -21,6 +27,8 @@ class OpenClaw {
     this.baseUrl = 'https://openclaw.io/api';
     this.version = '1.0';
     this.source = 'ai-time-machines';
+    this.pool = options.pool || null;
+    this.recorder = options.recorder || null;
   }
 
   /**
@@ -97,6 +105,20 @@ class OpenClaw {
     return this._request('GET', path);
   }
 
+  /**
+   * Make a request to the OpenClaw API, through the recorder when one is configured
+   * @param {string} method - HTTP method
+   * @param {string} path - API path
+   * @param {Object} body - Request body
+   * @returns {Promise<Object>} - Parsed response
+   */
+  _request(method, path, body = null) {
+    if (this.recorder) {
+      return this.recorder.capture('openclaw', `${method} ${path}`, body, () => this._httpRequest(method, path, body));
+    }
+    return this._httpRequest(method, path, body);
+  }
+
   /**
    * Make an HTTP request to the OpenClaw API
    * @param {string} method - HTTP method (GET, POST, etc.)
@@ -104,7 +126,7 @@ class OpenClaw {
    * @param {Object} body - Request body (for POST requests)
    * @returns {Promise<Object>} - Parsed response
    */
-  _request(method, path, body = null) {
+  _httpRequest(method, path, body = null) {
     return new Promise((resolve, reject) => {
       const url = new URL(this.baseUrl + path);
       const isHttps = url.protocol === 'https:';
@@ -115,6 +137,7 @@ class OpenClaw {
         port: url.port || (isHttps ? 443 : 80),
         path: url.pathname + url.search,
         method,
+        agent: this.pool ? this.pool.agentFor(url.protocol) : undefined,
         headers: {
           'Authorization': `Bearer ${this.apiKey}`,
           'Content-Type': 'application/json',
@@ -141,13 +164,13 @@ class OpenClaw {
             if (res.statusCode >= 200 && res.statusCode < 300) {
               resolve(parsed);
             } else {
-              reject(new Error(`OpenClaw API Error: ${res.statusCode} - ${parsed.message || data}`));
+              reject(Object.assign(new Error(`OpenClaw API Error: ${res.statusCode} - ${parsed.message || data}`), { statusCode: res.statusCode }));
             }
           } catch {
             if (res.statusCode >= 200 && res.statusCode < 300) {
               resolve({ raw: data });
             } else {
-              reject(new Error(`OpenClaw API Error: ${res.statusCode} - ${data}`));
+              reject(Object.assign(new Error(`OpenClaw API Error: ${res.statusCode} - ${data}`), { statusCode: res.statusCode }));
             }
           }
         });
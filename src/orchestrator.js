@@ -0,0 +1,191 @@
+/**
+ * Orchestrator Class
+ * Runs several named agents side by side. Each agent works through its own
+ * inbox one task at a time; different agents run concurrently. Inboxes are
+ * bounded, so send() waits when an agent is backed up. A task that times out
+ * is rejected at once, but the agent only takes its next task after the
+ * aborted run has settled.
+ */
+class Orchestrator {
+  /**
+   * @param {Object} options - Orchestrator options
+   * @param {number} options.maxQueue - Maximum queued tasks per agent (default 100)
+   * @param {number} options.timeout - Default per-task timeout in milliseconds (default 60000)
+   */
+  constructor(options = {}) {
+    this.maxQueue = options.maxQueue || 100;
+    this.timeout = options.timeout || 60000;
+    this.agents = new Map();
+  }
+
+  /**
+   * Add a named agent
+   * @param {string} name - Agent name
+   * @param {Agent} agent - Agent instance (anything with run(task, { signal }))
+   * @param {Object} options - Agent options
+   * @param {number} options.timeout - Per-task timeout overriding the default
+   * @returns {Orchestrator} - This orchestrator
+   */
+  addAgent(name, agent, options = {}) {
+    if (this.agents.has(name)) {
+      throw new Error(`Agent already registered: ${name}`);
+    }
+    if (!agent || typeof agent.run !== 'function') {
+      throw new Error(`Agent ${name} must have a run(task, options) method`);
+    }
+
+    this.agents.set(name, {
+      agent,
+      timeout: options.timeout || this.timeout,
+      inbox: [],
+      spaceWaiters: [],
+      busy: false,
+      completed: 0,
+      failed: 0,
+    });
+    return this;
+  }
+
+  /**
+   * Send a task to an agent and wait for its result
+   * @param {string} to - Agent name
+   * @param {string} task - Task for the agent
+   * @param {Object} options - Message options
+   * @param {string} options.from - Name of the sender, passed to the agent as context
+   * @returns {Promise<Object>} - The agent's run result
+   */
+  async send(to, task, options = {}) {
+    const entry = this._entry(to);
+
+    while (entry.inbox.length >= this.maxQueue) {
+      await new Promise((resolve) => entry.spaceWaiters.push(resolve));
+    }
+
+    const content = options.from ? `[from ${options.from}] ${task}` : task;
+    const result = new Promise((resolve, reject) => {
+      entry.inbox.push({ task: content, resolve, reject });
+    });
+
+    this._drain(to);
+    return result;
+  }
+
+  /**
+   * Send tasks to several agents concurrently and collect every outcome
+   * @param {Object} tasks - Map of agent name to task
+   * @returns {Promise<Object>} - Map of agent name to { status, output } or { status, error }
+   */
+  async runAll(tasks) {
+    const names = Object.keys(tasks);
+    const settled = await Promise.allSettled(names.map((name) => this.send(name, tasks[name])));
+
+    return Object.fromEntries(settled.map((outcome, i) => [
+      names[i],
+      outcome.status === 'fulfilled'
+        ? { status: 'fulfilled', output: outcome.value.output }
+        : { status: 'rejected', error: outcome.reason.message },
+    ]));
+  }
+
+  /**
+   * Let a supervisor agent delegate work to worker agents. Registers a
+   * `delegate` tool on the supervisor that sends a task to a worker and
+   * returns the worker's answer.
+   * @param {string} supervisor - Supervisor agent name
+   * @param {Array<string>} workers - Worker agent names
+   * @returns {Orchestrator} - This orchestrator
+   */
+  supervise(supervisor, workers) {
+    const { agent } = this._entry(supervisor);
+    workers.forEach((name) => this._entry(name));
+
+    if (!agent.tools || typeof agent.tools.register !== 'function') {
+      throw new Error(`Supervisor ${supervisor} has no tool registry`);
+    }
+
+    agent.tools.register({
+      name: 'delegate',
+      description: 'Send a task to a worker agent and get its answer',
+      parameters: {
+        type: 'object',
+        properties: {
+          worker: { type: 'string', enum: workers },
+          task: { type: 'string' },
+        },
+        required: ['worker', 'task'],
+      },
+      handler: async ({ worker, task }) => {
+        if (!workers.includes(worker)) {
+          throw new Error(`Unknown worker: ${worker}`);
+        }
+        const result = await this.send(worker, task, { from: supervisor });
+        return result.output;
+      },
+    });
+
+    return this;
+  }
+
+  /**
+   * Get per-agent queue and outcome counts
+   * @returns {Object} - Stats keyed by agent name
+   */
+  getStats() {
+    return Object.fromEntries(Array.from(this.agents.entries()).map(([name, entry]) => [
+      name,
+      { queued: entry.inbox.length, busy: entry.busy, completed: entry.completed, failed: entry.failed },
+    ]));
+  }
+
+  async _drain(name) {
+    const entry = this.agents.get(name);
+    if (entry.busy) {
+      return;
+    }
+
+    entry.busy = true;
+    while (entry.inbox.length > 0) {
+      const { task, resolve, reject } = entry.inbox.shift();
+      const waiter = entry.spaceWaiters.shift();
+      if (waiter) {
+        waiter();
+      }
+
+      const controller = new AbortController();
+      const run = Promise.resolve().then(() => entry.agent.run(task, { signal: controller.signal }));
+      try {
+        resolve(await this._withTimeout(name, entry, run, controller));
+        entry.completed++;
+      } catch (error) {
+        reject(error);
+        entry.failed++;
+      }
+      // A timed-out run may still be finishing; the next task must not share the agent with it
+      await run.catch(() => {});
+    }
+    entry.busy = false;
+  }
+
+  _withTimeout(name, entry, run, controller) {
+    let timer;
+
+    const timeout = new Promise((_resolve, reject) => {
+      timer = setTimeout(() => {
+        controller.abort();
+        reject(new Error(`Agent ${name} timed out after ${entry.timeout}ms`));
+      }, entry.timeout);
+    });
+
+    return Promise.race([run, timeout]).finally(() => clearTimeout(timer));
+  }
+
+  _entry(name) {
+    const entry = this.agents.get(name);
+    if (!entry) {
+      throw new Error(`Unknown agent: ${name}`);
+    }
+    return entry;
+  }
+}
+
+module.exports = Orchestrator;
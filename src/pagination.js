@@ -0,0 +1,97 @@
+/**
+ * Shared pagination helpers
+ *
+ * Every list API returns a page of the form { items, nextCursor }, where
+ * nextCursor is an opaque string to pass back as options.cursor, or null on
+ * the last page. Limits default to DEFAULT_LIMIT and are capped at
+ * MAX_LIMIT so all list APIs behave the same way.
+ */
+
+const DEFAULT_LIMIT = 50;
+const MAX_LIMIT = 100;
+
+/**
+ * Clamp a requested page size to the supported range
+ * @param {number} limit - Requested page size
+ * @returns {number} - Page size between 1 and MAX_LIMIT
+ */
+function normalizeLimit(limit) {
+  if (limit === undefined || limit === null) {
+    return DEFAULT_LIMIT;
+  }
+  if (!Number.isInteger(limit) || limit < 1) {
+    throw new Error(`Page limit must be a positive integer, got ${limit}`);
+  }
+  return Math.min(limit, MAX_LIMIT);
+}
+
+/**
+ * Encode a position as an opaque cursor
+ * @param {Object} position - JSON-serializable position (e.g. { offset: 50 })
+ * @returns {string} - Cursor
+ */
+function encodeCursor(position) {
+  return Buffer.from(JSON.stringify(position)).toString('base64url');
+}
+
+/**
+ * Decode a cursor produced by encodeCursor()
+ * @param {string} cursor - Cursor
+ * @returns {Object|null} - Position, or null when no cursor was given
+ */
+function decodeCursor(cursor) {
+  if (!cursor) {
+    return null;
+  }
+  try {
+    return JSON.parse(Buffer.from(cursor, 'base64url').toString('utf8'));
+  } catch {
+    throw new Error('Invalid page cursor');
+  }
+}
+
+/**
+ * Page through an in-memory list
+ * @param {Array} items - Full list
+ * @param {Object} options - { cursor, limit }
+ * @returns {Object} - { items, nextCursor }
+ */
+function paginate(items, options = {}) {
+  const limit = normalizeLimit(options.limit);
+  const position = decodeCursor(options.cursor);
+  const offset = position ? position.offset : 0;
+
+  if (!Number.isInteger(offset) || offset < 0) {
+    throw new Error('Invalid page cursor');
+  }
+
+  const end = offset + limit;
+  return {
+    items: items.slice(offset, end),
+    nextCursor: end < items.length ? encodeCursor({ offset: end }) : null,
+  };
+}
+
+/**
+ * Iterate over every item of a paginated list API
+ * @param {Function} fetchPage - Async (cursor) => { items, nextCursor }
+ * @returns {AsyncGenerator} - Items across all pages
+ */
+async function* iterateAll(fetchPage) {
+  let cursor = null;
+  do {
+    const page = await fetchPage(cursor);
+    yield* page.items;
+    cursor = page.nextCursor;
+  } while (cursor);
+}
+
+module.exports = {
+  DEFAULT_LIMIT,
+  MAX_LIMIT,
+  normalizeLimit,
+  encodeCursor,
+  decodeCursor,
+  paginate,
+  iterateAll,
+};
@@ -0,0 +1,232 @@
+const sleep = (ms) => new Promise((resolve) => setTimeout(resolve, ms));
+
+/**
+ * Pipeline Class
+ * A DAG of steps built from named actions. Steps whose dependencies have
+ * finished run in parallel; each step may retry, and may be conditional on
+ * another step's output. Definitions are plain JSON so pipelines can be
+ * stored and run again later with the same action registry.
+ *
+ * Definition format:
+ * {
+ *   "name": "ingest",
+ *   "steps": [
+ *     { "id": "fetch", "action": "fetch", "params": { "url": "..." } },
+ *     { "id": "classify", "action": "classify", "dependsOn": ["fetch"], "retries": 2, "retryDelay": 500 },
+ *     { "id": "alert", "action": "notify", "dependsOn": ["classify"],
+ *       "when": { "step": "classify", "path": "priority", "equals": "high" } }
+ *   ]
+ * }
+ *
+ * Actions are async (context) => output where context is
+ * { input, params, results } and results maps dependency ids to outputs.
+ * Steps whose condition is false are skipped, and so are steps that depend
+ * on a skipped step.
+ */
+class Pipeline {
+  /**
+   * @param {Object} definition - Pipeline definition
+   * @param {Object} actions - Map of action name to async function
+   */
+  constructor(definition, actions = {}) {
+    this.name = definition.name || 'pipeline';
+    this.steps = (definition.steps || []).map((step) => ({
+      dependsOn: [],
+      params: {},
+      retries: 0,
+      retryDelay: 0,
+      ...step,
+    }));
+    this.actions = actions;
+    this._validate();
+  }
+
+  /**
+   * Build a pipeline from a JSON string or object
+   * @param {string|Object} json - Serialized definition
+   * @param {Object} actions - Map of action name to async function
+   * @returns {Pipeline} - The pipeline
+   */
+  static fromJSON(json, actions) {
+    return new Pipeline(typeof json === 'string' ? JSON.parse(json) : json, actions);
+  }
+
+  /**
+   * Serialize the pipeline definition
+   * @returns {Object} - Plain definition object
+   */
+  toJSON() {
+    return {
+      name: this.name,
+      steps: this.steps.map((step) => ({ ...step })),
+    };
+  }
+
+  /**
+   * Run the pipeline
+   * @param {*} input - Input passed to every step
+   * @param {Object} options - Run options
+   * @param {Function} options.onStep - Called with { id, status, output, error, attempts } as steps finish
+   * @param {ProgressHandle} options.progress - Advanced as steps finish; cancelling it stops new steps from starting
+   * @returns {Promise<Object>} - { outputs, status } keyed by step id
+   */
+  async run(input, options = {}) {
+    const outputs = {};
+    const status = {};
+    const pending = new Map(this.steps.map((step) => [step.id, step]));
+    const running = new Map();
+    const progress = options.progress || null;
+
+    const report = (event) => {
+      if (progress) {
+        progress.advance(1, event.id);
+      }
+      if (typeof options.onStep === 'function') {
+        options.onStep(event);
+      }
+    };
+
+    if (progress) {
+      progress.update({ total: this.steps.length });
+    }
+
+    while (pending.size > 0 || running.size > 0) {
+      if (progress && progress.cancelled) {
+        await Promise.allSettled(running.values());
+        progress.throwIfCancelled();
+      }
+
+      for (const step of Array.from(pending.values())) {
+        const deps = step.dependsOn.map((id) => status[id]);
+        if (deps.some((s) => s === undefined)) {
+          continue;
+        }
+
+        pending.delete(step.id);
+
+        if (deps.includes('skipped') || !this._conditionMet(step, outputs)) {
+          status[step.id] = 'skipped';
+          report({ id: step.id, status: 'skipped' });
+          continue;
+        }
+
+        const results = Object.fromEntries(step.dependsOn.map((id) => [id, outputs[id]]));
+        running.set(step.id, this._runStep(step, { input, params: step.params, results }));
+      }
+
+      if (running.size === 0) {
+        // Newly skipped steps may have unblocked others; loop again
+        continue;
+      }
+
+      const { id, output, error, attempts } = await Promise.race(running.values());
+      running.delete(id);
+
+      if (error) {
+        status[id] = 'failed';
+        report({ id, status: 'failed', error, attempts });
+        await Promise.allSettled(running.values());
+        const failure = new Error(`Pipeline ${this.name} failed at step ${id}: ${error.message}`);
+        failure.step = id;
+        failure.outputs = outputs;
+        if (progress) {
+          progress.fail(failure);
+        }
+        throw failure;
+      }
+
+      outputs[id] = output;
+      status[id] = 'completed';
+      report({ id, status: 'completed', output, attempts });
+    }
+
+    if (progress) {
+      progress.complete();
+    }
+    return { outputs, status };
+  }
+
+  async _runStep(step, context) {
+    const action = this.actions[step.action];
+    let attempts = 0;
+
+    for (;;) {
+      attempts++;
+      try {
+        return { id: step.id, output: await action(context), attempts };
+      } catch (error) {
+        if (attempts > step.retries) {
+          return { id: step.id, error, attempts };
+        }
+        await sleep(step.retryDelay * attempts);
+      }
+    }
+  }
+
+  _conditionMet(step, outputs) {
+    if (!step.when) {
+      return true;
+    }
+
+    const { step: source, path, equals, notEquals } = step.when;
+    const value = (path ? path.split('.') : []).reduce(
+      (current, key) => (current == null ? undefined : current[key]),
+      outputs[source]
+    );
+
+    if (equals !== undefined) {
+      return value === equals;
+    }
+    if (notEquals !== undefined) {
+      return value !== notEquals;
+    }
+    return Boolean(value);
+  }
+
+  _validate() {
+    const ids = new Set();
+
+    for (const step of this.steps) {
+      if (!step.id) {
+        throw new Error(`Pipeline ${this.name} has a step without an id`);
+      }
+      if (ids.has(step.id)) {
+        throw new Error(`Pipeline ${this.name} has duplicate step id: ${step.id}`);
+      }
+      if (typeof this.actions[step.action] !== 'function') {
+        throw new Error(`Pipeline ${this.name} step ${step.id} uses unknown action: ${step.action}`);
+      }
+      ids.add(step.id);
+    }
+
+    for (const step of this.steps) {
+      const references = [...step.dependsOn, ...(step.when ? [step.when.step] : [])];
+      for (const id of references) {
+        if (!ids.has(id)) {
+          throw new Error(`Pipeline ${this.name} step ${step.id} references unknown step: ${id}`);
+        }
+      }
+      if (step.when && !step.dependsOn.includes(step.when.step)) {
+        throw new Error(`Pipeline ${this.name} step ${step.id} must depend on its condition step ${step.when.step}`);
+      }
+    }
+
+    // Depth-first search for cycles
+    const visiting = new Set();
+    const visited = new Set();
+    const byId = new Map(this.steps.map((step) => [step.id, step]));
+    const visit = (id) => {
+      if (visited.has(id)) return;
+      if (visiting.has(id)) {
+        throw new Error(`Pipeline ${this.name} has a dependency cycle at step ${id}`);
+      }
+      visiting.add(id);
+      byId.get(id).dependsOn.forEach(visit);
+      visiting.delete(id);
+      visited.add(id);
+    };
+    this.steps.forEach((step) => visit(step.id));
+  }
+}
+
+module.exports = Pipeline;
@@ -0,0 +1,187 @@
+const { MemoryJournal } = require('./recorder');
+
+const ALLOWED = { allowed: true, rule: null, reason: null };
+
+function deny(rule, reason) {
+  return { allowed: false, rule, reason };
+}
+
+function policyError(decision) {
+  const error = new Error(`Denied by policy: ${decision.reason}`);
+  error.rule = decision.rule;
+  error.statusCode = 403;
+  return error;
+}
+
+/**
+ * PolicyEngine Class
+ * Operator-declared rules checked before an agent acts. Denials are
+ * appended to an audit journal so they can be reviewed later.
+ *
+ * Rules (all optional):
+ * - allowedTools / deniedTools: tool names the agent may or may not call
+ * - bannedPatterns: strings or regexes rejected in prompts and tool arguments
+ * - maxDailySpend: USD per day, measured with the usageTracker
+ * - tools: per-tool argument limits, e.g.
+ *   { send_payment: { max: { amount: 100 }, allow: { to: ['treasury'] }, approveAbove: { amount: 20 } } }
+ *   where max caps numeric arguments, allow lists the accepted values and
+ *   approveAbove marks larger values as needing human approval (the decision
+ *   then has approvalRequired set; see ApprovalQueue). Approval is only
+ *   offered once every other rule, including the spend limit, has passed.
+ *
+ * Example:
+ *   const policy = new PolicyEngine({ rules: { deniedTools: ['delete_repo'], maxDailySpend: 5 }, usageTracker });
+ *   const agent = new Agent({ client, tools, policy });
+ */
+class PolicyEngine {
+  /**
+   * @param {Object} options - Policy options
+   * @param {Object} options.rules - Rules described above
+   * @param {UsageTracker} options.usageTracker - Required for maxDailySpend
+   * @param {Object} options.auditLog - Journal with async append(entry) for denials (default in-memory)
+   */
+  constructor(options = {}) {
+    const rules = options.rules || {};
+
+    if (rules.maxDailySpend !== undefined && !options.usageTracker) {
+      throw new Error('PolicyEngine needs a usageTracker to enforce maxDailySpend');
+    }
+
+    this.allowedTools = rules.allowedTools ? new Set(rules.allowedTools) : null;
+    this.deniedTools = new Set(rules.deniedTools || []);
+    this.bannedPatterns = (rules.bannedPatterns || []).map((pattern) => (
+      pattern instanceof RegExp ? pattern : new RegExp(pattern.replace(/[.*+?^${}()|[\]\\]/g, '\\$&'), 'i')
+    ));
+    this.maxDailySpend = rules.maxDailySpend !== undefined ? rules.maxDailySpend : null;
+    this.toolRules = rules.tools || {};
+    this.usageTracker = options.usageTracker || null;
+    this.auditLog = options.auditLog || new MemoryJournal();
+  }
+
+  /**
+   * Check a prompt before it is sent to a model
+   * @param {string} text - Prompt text
+   * @returns {Promise<Object>} - Decision { allowed, rule, reason }
+   */
+  async checkPrompt(text) {
+    const decision = this._checkPatterns(text) || this._checkSpend() || ALLOWED;
+    return this._audit(decision, { type: 'prompt' });
+  }
+
+  /**
+   * Check a tool call before it is executed
+   * @param {string} name - Tool name
+   * @param {Object} args - Parsed tool arguments
+   * @returns {Promise<Object>} - Decision { allowed, rule, reason }
+   */
+  async checkToolCall(name, args = {}) {
+    const decision = this._checkTool(name)
+      || this._checkPatterns(JSON.stringify(args))
+      || this._checkArguments(name, args)
+      || this._checkSpend()
+      || this._checkApproval(name, args)
+      || ALLOWED;
+    return this._audit(decision, { type: 'tool', tool: name });
+  }
+
+  /**
+   * Check a prompt and throw when it is denied
+   * @param {string} text - Prompt text
+   */
+  async enforcePrompt(text) {
+    const decision = await this.checkPrompt(text);
+    if (!decision.allowed) {
+      throw policyError(decision);
+    }
+  }
+
+  /**
+   * Check a tool call and throw when it is denied
+   * @param {string} name - Tool name
+   * @param {Object} args - Parsed tool arguments
+   */
+  async enforceToolCall(name, args = {}) {
+    const decision = await this.checkToolCall(name, args);
+    if (!decision.allowed) {
+      throw policyError(decision);
+    }
+  }
+
+  _checkTool(name) {
+    if (this.deniedTools.has(name) || (this.allowedTools && !this.allowedTools.has(name))) {
+      return deny('tool', `tool ${name} is not allowed`);
+    }
+    return null;
+  }
+
+  _checkPatterns(text) {
+    const banned = this.bannedPatterns.find((pattern) => {
+      pattern.lastIndex = 0;
+      return pattern.test(String(text || ''));
+    });
+    return banned ? deny('pattern', `matches banned pattern ${banned}`) : null;
+  }
+
+  _checkArguments(name, args) {
+    const rule = this.toolRules[name];
+    if (!rule) {
+      return null;
+    }
+
+    for (const [field, limit] of Object.entries(rule.max || {})) {
+      if (args[field] !== undefined && !(Number(args[field]) <= limit)) {
+        return deny('max', `${name}.${field} ${args[field]} exceeds ${limit}`);
+      }
+    }
+    for (const [field, values] of Object.entries(rule.allow || {})) {
+      if (args[field] !== undefined && !values.includes(args[field])) {
+        return deny('allow', `${name}.${field} ${args[field]} is not allow-listed`);
+      }
+    }
+    return null;
+  }
+
+  _checkApproval(name, args) {
+    const rule = this.toolRules[name];
+    if (!rule) {
+      return null;
+    }
+
+    for (const [field, threshold] of Object.entries(rule.approveAbove || {})) {
+      // Written as "not at or below" so values that are not numbers need approval too
+      if (args[field] !== undefined && !(Number(args[field]) <= threshold)) {
+        const reason = Number.isNaN(Number(args[field])) ? 'is not a number' : `is above ${threshold}`;
+        return { ...deny('approval', `${name}.${field} ${args[field]} ${reason} and needs approval`), approvalRequired: true };
+      }
+    }
+    return null;
+  }
+
+  _checkSpend() {
+    if (this.maxDailySpend === null) {
+      return null;
+    }
+
+    const midnight = new Date();
+    midnight.setHours(0, 0, 0, 0);
+    const spent = this.usageTracker.getTotals(midnight).cost;
+    if (spent >= this.maxDailySpend) {
+      return deny('spend', `daily spend $${spent.toFixed(4)} reached the $${this.maxDailySpend.toFixed(2)} limit`);
+    }
+    return null;
+  }
+
+  async _audit(decision, context) {
+    if (!decision.allowed) {
+      await this.auditLog.append({
+        timestamp: new Date().toISOString(),
+        ...context,
+        rule: decision.rule,
+        reason: decision.reason,
+      });
+    }
+    return decision;
+  }
+}
+
+module.exports = PolicyEngine;
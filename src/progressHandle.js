@@ -0,0 +1,179 @@
+/**
+ * ProgressHandle Class
+ * Tracks a long-running operation (ingest, migration, backfill, pipeline
+ * run) so callers can show percent complete, ETA and the current stage, and
+ * can ask the operation to stop. The operation calls advance() as it works
+ * and checks cancelled (or calls throwIfCancelled()) between units of work.
+ *
+ * toJSON() returns a plain status object suitable for progress bars and
+ * status endpoints.
+ */
+class ProgressHandle {
+  /**
+   * @param {Object} options - Progress options
+   * @param {number} options.total - Units of work expected, if known
+   * @param {string} options.stage - Initial stage name
+   * @param {Function} options.now - Clock returning milliseconds (for tests)
+   */
+  constructor(options = {}) {
+    this.now = options.now || Date.now;
+    this.total = options.total !== undefined ? options.total : null;
+    this.completed = 0;
+    this.stage = options.stage || null;
+    this.status = 'running';
+    this.error = null;
+    this.cancelReason = null;
+    this.startedAt = this.now();
+    this.finishedAt = null;
+    this.listeners = [];
+  }
+
+  /**
+   * Percent complete, or null when the total is unknown
+   * @returns {number|null}
+   */
+  get percent() {
+    if (!this.total) {
+      return this.status === 'completed' ? 100 : null;
+    }
+    return Math.min(100, (this.completed / this.total) * 100);
+  }
+
+  /**
+   * Estimated milliseconds remaining from the average rate so far, or null when unknown
+   * @returns {number|null}
+   */
+  get eta() {
+    if (this.status !== 'running') {
+      return 0;
+    }
+    if (!this.total || this.completed === 0) {
+      return null;
+    }
+    const elapsed = this.now() - this.startedAt;
+    return Math.max(0, Math.round((elapsed / this.completed) * (this.total - this.completed)));
+  }
+
+  /**
+   * Whether cancellation has been requested
+   * @returns {boolean}
+   */
+  get cancelled() {
+    return this.cancelReason !== null;
+  }
+
+  /**
+   * Record completed work
+   * @param {number} units - Units just completed (default 1)
+   * @param {string} stage - New stage name, if it changed
+   */
+  advance(units = 1, stage = undefined) {
+    this.completed += units;
+    if (stage !== undefined) {
+      this.stage = stage;
+    }
+    this._emit();
+  }
+
+  /**
+   * Update the total or stage without completing work
+   * @param {Object} update - { total, stage }
+   */
+  update(update = {}) {
+    if (update.total !== undefined) {
+      this.total = update.total;
+    }
+    if (update.stage !== undefined) {
+      this.stage = update.stage;
+    }
+    this._emit();
+  }
+
+  /**
+   * Ask the operation to stop; it stops at its next cancellation check
+   * @param {string} reason - Why the operation was cancelled
+   */
+  cancel(reason = 'cancelled') {
+    if (this.status === 'running' && !this.cancelled) {
+      this.cancelReason = reason;
+      this._emit();
+    }
+  }
+
+  /**
+   * Throw if cancellation has been requested, marking the operation cancelled
+   */
+  throwIfCancelled() {
+    if (this.cancelled) {
+      this._finish('cancelled');
+      throw new Error(`Operation cancelled: ${this.cancelReason}`);
+    }
+  }
+
+  /**
+   * Mark the operation as completed
+   */
+  complete() {
+    this._finish('completed');
+  }
+
+  /**
+   * Mark the operation as failed
+   * @param {Error} error - The failure
+   */
+  fail(error) {
+    this.error = error.message;
+    this._finish('failed');
+  }
+
+  /**
+   * Register a listener called with toJSON() whenever progress changes
+   * @param {Function} listener - Listener function
+   * @returns {Function} - Call to unsubscribe
+   */
+  onProgress(listener) {
+    this.listeners.push(listener);
+    return () => {
+      const index = this.listeners.indexOf(listener);
+      if (index !== -1) {
+        this.listeners.splice(index, 1);
+      }
+    };
+  }
+
+  /**
+   * Current status as a plain object
+   * @returns {Object} - { status, stage, completed, total, percent, etaMs, elapsedMs, cancelReason, error }
+   */
+  toJSON() {
+    return {
+      status: this.status,
+      stage: this.stage,
+      completed: this.completed,
+      total: this.total,
+      percent: this.percent,
+      etaMs: this.eta,
+      elapsedMs: (this.finishedAt || this.now()) - this.startedAt,
+      cancelReason: this.cancelReason,
+      error: this.error,
+    };
+  }
+
+  _finish(status) {
+    if (this.status !== 'running') {
+      return;
+    }
+    this.status = status;
+    this.finishedAt = this.now();
+    this._emit();
+  }
+
+  _emit() {
+    const state = this.toJSON();
+    for (const listener of this.listeners) {
+      listener(state);
+    }
+  }
+}
+
+module.exports = ProgressHandle;
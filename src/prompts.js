@@ -0,0 +1,274 @@
+const fs = require('fs');
+const path = require('path');
+const { paginate } = require('./pagination');
+
+const VARIABLE_PATTERN = /\{\{\s*([A-Za-z_][\w.]*)\s*\}\}/g;
+const PARTIAL_PATTERN = /\{\{>\s*([\w.-]+)\s*\}\}/g;
+const EXAMPLES_VARIABLE = 'examples';
+const MAX_PARTIAL_DEPTH = 10;
+
+/**
+ * PromptTemplate Class
+ * A prompt with {{variable}} placeholders, {{> partial}} includes and
+ * optional few-shot examples.
+ *
+ * Examples are rendered with exampleTemplate and inserted at {{examples}};
+ * when the template has no such placeholder they are placed before it.
+ */
+class PromptTemplate {
+  /**
+   * @param {string} template - Template text, e.g. 'Summarize {{doc}} for {{audience}}'
+   * @param {Object} options - Template options
+   * @param {Object} options.partials - Partial templates keyed by name
+   * @param {Array<Object>} options.examples - Few-shot examples (objects of example variables)
+   * @param {string} options.exampleTemplate - Template used to render each example
+   * @param {string} options.exampleSeparator - Text placed between rendered examples
+   */
+  constructor(template, options = {}) {
+    if (typeof template !== 'string') {
+      throw new TypeError('Prompt template must be a string');
+    }
+
+    this.template = template;
+    this.partials = options.partials || {};
+    this.examples = options.examples || [];
+    this.exampleTemplate = options.exampleTemplate || 'Input: {{input}}\nOutput: {{output}}';
+    this.exampleSeparator = options.exampleSeparator !== undefined ? options.exampleSeparator : '\n\n';
+  }
+
+  /**
+   * Get the variables the template requires, after expanding partials
+   * @returns {Array<string>} - Variable names in order of first use
+   */
+  get variables() {
+    const names = [];
+    for (const match of this._expand().matchAll(VARIABLE_PATTERN)) {
+      if (match[1] !== EXAMPLES_VARIABLE && !names.includes(match[1])) {
+        names.push(match[1]);
+      }
+    }
+    return names;
+  }
+
+  /**
+   * Return a copy of this template with different few-shot examples
+   * @param {Array<Object>} examples - Few-shot examples
+   * @returns {PromptTemplate} - New template
+   */
+  withExamples(examples) {
+    return new PromptTemplate(this.template, {
+      partials: this.partials,
+      examples,
+      exampleTemplate: this.exampleTemplate,
+      exampleSeparator: this.exampleSeparator,
+    });
+  }
+
+  /**
+   * Render the template
+   * @param {Object} values - Variable values; dotted names read nested properties
+   * @returns {string} - The rendered prompt
+   */
+  render(values = {}) {
+    const body = this._expand();
+    const examples = this._renderExamples();
+    const rendered = substitute(body, (name) => (name === EXAMPLES_VARIABLE ? examples : lookup(values, name)));
+
+    if (examples && !hasVariable(body, EXAMPLES_VARIABLE)) {
+      return `${examples}${this.exampleSeparator}${rendered}`;
+    }
+
+    return rendered;
+  }
+
+  _renderExamples() {
+    const example = new PromptTemplate(this.exampleTemplate, { partials: this.partials });
+    return this.examples.map((values) => example.render(values)).join(this.exampleSeparator);
+  }
+
+  _expand(text = this.template, depth = 0) {
+    if (depth > MAX_PARTIAL_DEPTH) {
+      throw new Error('Prompt partials are nested too deeply (possible cycle)');
+    }
+
+    return text.replace(PARTIAL_PATTERN, (_match, name) => {
+      if (!Object.prototype.hasOwnProperty.call(this.partials, name)) {
+        throw new Error(`Unknown prompt partial: ${name}`);
+      }
+      return this._expand(this.partials[name], depth + 1);
+    });
+  }
+}
+
+/**
+ * PromptRegistry Class
+ * Named collection of prompt templates and shared partials
+ */
+class PromptRegistry {
+  constructor() {
+    this.templates = new Map();
+    this.partials = {};
+  }
+
+  /**
+   * Register a template
+   * @param {string} name - Template name
+   * @param {string} template - Template text
+   * @param {Object} options - PromptTemplate options (partials are shared by the registry)
+   * @returns {PromptTemplate} - The registered template
+   */
+  register(name, template, options = {}) {
+    const prompt = new PromptTemplate(template, { ...options, partials: this.partials });
+    this.templates.set(name, prompt);
+    return prompt;
+  }
+
+  /**
+   * Register a partial available to every template in the registry
+   * @param {string} name - Partial name
+   * @param {string} text - Partial text
+   */
+  registerPartial(name, text) {
+    this.partials[name] = text;
+  }
+
+  /**
+   * Get a registered template
+   * @param {string} name - Template name
+   * @returns {PromptTemplate} - The template
+   */
+  get(name) {
+    const prompt = this.templates.get(name);
+    if (!prompt) {
+      throw new Error(`Unknown prompt template: ${name}`);
+    }
+    return prompt;
+  }
+
+  /**
+   * Check whether a template is registered
+   * @param {string} name - Template name
+   * @returns {boolean} - True when registered
+   */
+  has(name) {
+    return this.templates.has(name);
+  }
+
+  /**
+   * Render a registered template
+   * @param {string} name - Template name
+   * @param {Object} values - Variable values
+   * @returns {string} - The rendered prompt
+   */
+  render(name, values = {}) {
+    return this.get(name).render(values);
+  }
+
+  /**
+   * Get registered template names
+   * @param {Object} options - { cursor, limit }
+   * @returns {Object} - Page { items, nextCursor } of template names
+   */
+  list(options = {}) {
+    return paginate(Array.from(this.templates.keys()), options);
+  }
+
+  /**
+   * Capture templates and partials for a Snapshot
+   * @returns {Object} - { templates, partials }
+   */
+  snapshot() {
+    const templates = {};
+    for (const [name, prompt] of this.templates) {
+      templates[name] = {
+        template: prompt.template,
+        examples: prompt.examples,
+        exampleTemplate: prompt.exampleTemplate,
+        exampleSeparator: prompt.exampleSeparator,
+      };
+    }
+    return { templates, partials: { ...this.partials } };
+  }
+
+  /**
+   * Replace every template and partial with a captured set
+   * @param {Object} state - Output of snapshot()
+   */
+  restore(state) {
+    // Templates hold a reference to the partials object, so update it in place
+    Object.keys(this.partials).forEach((name) => delete this.partials[name]);
+    Object.assign(this.partials, state.partials);
+
+    this.templates.clear();
+    for (const [name, { template, ...options }] of Object.entries(state.templates)) {
+      this.register(name, template, options);
+    }
+  }
+
+  /**
+   * Load templates from a directory.
+   * - name.prompt / name.txt: template text
+   * - name.json: { "template": "...", "examples": [...], "exampleTemplate": "..." }
+   * - Files whose name starts with an underscore are registered as partials
+   *   (without the underscore), e.g. _tone.prompt becomes {{> tone}}.
+   * @param {string} directory - Directory to read
+   * @returns {PromptRegistry} - This registry
+   */
+  loadFromDirectory(directory) {
+    const files = fs.readdirSync(directory).sort();
+
+    for (const file of files) {
+      const ext = path.extname(file);
+      if (!['.prompt', '.txt', '.json'].includes(ext)) {
+        continue;
+      }
+
+      const base = path.basename(file, ext);
+      const content = fs.readFileSync(path.join(directory, file), 'utf8');
+      const definition = ext === '.json' ? JSON.parse(content) : { template: content };
+
+      if (base.startsWith('_')) {
+        this.registerPartial(base.slice(1), definition.template);
+      } else {
+        const { template, ...options } = definition;
+        this.register(base, template, options);
+      }
+    }
+
+    return this;
+  }
+}
+
+/**
+ * Replace {{variable}} placeholders using a resolver
+ * @param {string} text - Text containing placeholders
+ * @param {Function} resolve - Returns the value for a variable name
+ * @returns {string} - Text with placeholders replaced
+ */
+function substitute(text, resolve) {
+  return text.replace(VARIABLE_PATTERN, (_match, name) => {
+    const value = resolve(name);
+
+    if (value === undefined || value === null) {
+      throw new Error(`Missing value for prompt variable: ${name}`);
+    }
+    if (!['string', 'number', 'boolean'].includes(typeof value)) {
+      throw new TypeError(`Prompt variable "${name}" must be a string, number or boolean`);
+    }
+
+    return String(value);
+  });
+}
+
+function hasVariable(text, name) {
+  return Array.from(text.matchAll(VARIABLE_PATTERN)).some((match) => match[1] === name);
+}
+
+function lookup(values, name) {
+  return name.split('.').reduce((value, key) => (value == null ? undefined : value[key]), values);
+}
+
+module.exports = {
+  PromptTemplate,
+  PromptRegistry,
+};
@@ -0,0 +1,112 @@
+/**
+ * RateLimiter Class
+ * Token-bucket rate limiter with a cap on in-flight requests. A single
+ * instance can be passed to several clients so they share the same limits.
+ */
+class RateLimiter {
+  /**
+   * @param {Object} options - Limiter options
+   * @param {number} options.requestsPerMinute - Sustained request rate (omit for no rate limit)
+   * @param {number} options.burst - Bucket size; defaults to requestsPerMinute
+   * @param {number} options.maxConcurrent - Maximum requests in flight (omit for no limit)
+   */
+  constructor(options = {}) {
+    const { requestsPerMinute = null, burst = null, maxConcurrent = null } = options;
+
+    for (const [name, value] of Object.entries({ requestsPerMinute, burst, maxConcurrent })) {
+      if (value !== null && !(Number.isFinite(value) && value > 0)) {
+        throw new Error(`RateLimiter ${name} must be a positive number`);
+      }
+    }
+
+    this.requestsPerMinute = requestsPerMinute;
+    this.maxConcurrent = maxConcurrent;
+    this.capacity = burst || requestsPerMinute;
+    this.tokens = this.capacity;
+    this.lastRefill = Date.now();
+    this.inFlight = 0;
+    this.waiting = [];
+    this.tokenChain = Promise.resolve();
+  }
+
+  /**
+   * Run a task once a token and a concurrency slot are available
+   * @param {Function} task - Async function to run
+   * @returns {Promise<*>} - The task result
+   */
+  async schedule(task) {
+    await this._acquireSlot();
+    try {
+      await this._acquireToken();
+      return await task();
+    } finally {
+      this._releaseSlot();
+    }
+  }
+
+  /**
+   * Get the current limiter state
+   * @returns {Object} - In-flight, queued and available token counts
+   */
+  getStats() {
+    this._refill();
+    return {
+      inFlight: this.inFlight,
+      queued: this.waiting.length,
+      availableTokens: this.requestsPerMinute ? Math.floor(this.tokens) : null,
+    };
+  }
+
+  _acquireSlot() {
+    if (!this.maxConcurrent || this.inFlight < this.maxConcurrent) {
+      this.inFlight++;
+      return Promise.resolve();
+    }
+
+    return new Promise((resolve) => {
+      this.waiting.push(resolve);
+    });
+  }
+
+  _releaseSlot() {
+    const next = this.waiting.shift();
+    if (next) {
+      // Hand the slot straight to the next waiter so inFlight stays accurate
+      next();
+    } else {
+      this.inFlight--;
+    }
+  }
+
+  _acquireToken() {
+    if (!this.requestsPerMinute) {
+      return Promise.resolve();
+    }
+
+    // Chain acquisitions so waiters are served in FIFO order
+    const acquired = this.tokenChain.then(async () => {
+      this._refill();
+      if (this.tokens < 1) {
+        const msPerToken = 60000 / this.requestsPerMinute;
+        await new Promise((resolve) => setTimeout(resolve, Math.ceil((1 - this.tokens) * msPerToken)));
+        this._refill();
+      }
+      this.tokens -= 1;
+    });
+
+    this.tokenChain = acquired;
+    return acquired;
+  }
+
+  _refill() {
+    const now = Date.now();
+    const elapsed = now - this.lastRefill;
+    this.lastRefill = now;
+
+    if (this.requestsPerMinute) {
+      this.tokens = Math.min(this.capacity, this.tokens + (elapsed * this.requestsPerMinute) / 60000);
+    }
+  }
+}
+
+module.exports = RateLimiter;
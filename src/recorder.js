@@ -0,0 +1,205 @@
+const crypto = require('crypto');
+const fs = require('fs');
+const { stableStringify } = require('./utils');
+
+/**
+ * MemoryJournal Class
+ * Keeps recorded interactions in memory
+ */
+class MemoryJournal {
+  constructor(entries = []) {
+    this.entries = [...entries];
+  }
+
+  async append(entry) {
+    this.entries.push(entry);
+  }
+
+  async load() {
+    return [...this.entries];
+  }
+}
+
+/**
+ * FileJournal Class
+ * Appends recorded interactions to a JSON Lines file
+ */
+class FileJournal {
+  /**
+   * @param {string} filePath - Journal file path
+   */
+  constructor(filePath) {
+    this.filePath = filePath;
+  }
+
+  async append(entry) {
+    await fs.promises.appendFile(this.filePath, `${JSON.stringify(entry)}\n`, 'utf8');
+  }
+
+  async load() {
+    let content;
+    try {
+      content = await fs.promises.readFile(this.filePath, 'utf8');
+    } catch (error) {
+      if (error.code === 'ENOENT') {
+        return [];
+      }
+      throw error;
+    }
+
+    return content
+      .split('\n')
+      .filter((line) => line.trim())
+      .map((line) => JSON.parse(line));
+  }
+}
+
+// Request fields filled from the clock (OpenClaw events and workflow
+// triggers, Replit export metadata); they would make every key unique
+const VOLATILE_FIELDS = ['timestamp', 'triggered_at', 'generatedAt'];
+
+function omitFields(value, fields) {
+  if (Array.isArray(value)) {
+    return value.map((item) => omitFields(item, fields));
+  }
+  if (value && typeof value === 'object' && !(value instanceof Date)) {
+    return Object.fromEntries(Object.entries(value)
+      .filter(([name]) => !fields.includes(name))
+      .map(([name, item]) => [name, omitFields(item, fields)]));
+  }
+  return value;
+}
+
+/**
+ * Recorder Class
+ * Records every request and response of the clients it is attached to,
+ * and can later serve those recordings back instead of calling the network.
+ *
+ * Modes:
+ * - 'record': call through and append each interaction to the journal
+ * - 'replay': answer from the journal; unrecorded requests throw
+ * - 'off': call through without recording
+ *
+ * Identical requests replay their recorded responses in order; once they
+ * run out the last one is repeated. Fields that change on every call, such
+ * as the timestamps OpenClaw and Replit payloads carry, are left out of the
+ * match; pass volatileFields or a normalize function for other payloads.
+ */
+class Recorder {
+  /**
+   * @param {Object} options - Recorder options
+   * @param {string} options.mode - 'record', 'replay' or 'off' (default 'record')
+   * @param {Object} options.journal - Journal with async append(entry) and load() (default in-memory)
+   * @param {Array<string>} options.volatileFields - Field names ignored when matching requests (default timestamp, triggered_at, generatedAt)
+   * @param {Function} options.normalize - (request, { provider, operation }) => request used for matching, applied after volatileFields
+   */
+  constructor(options = {}) {
+    const { mode = 'record' } = options;
+    if (!['record', 'replay', 'off'].includes(mode)) {
+      throw new Error(`Unsupported recorder mode: ${mode}. Use 'record', 'replay' or 'off'.`);
+    }
+
+    this.mode = mode;
+    this.journal = options.journal || new MemoryJournal();
+    this.volatileFields = options.volatileFields || VOLATILE_FIELDS;
+    this.normalize = options.normalize || null;
+    this.replayIndex = null;
+    this.replayCursor = new Map();
+  }
+
+  /**
+   * Run a request through the recorder
+   * @param {string} provider - Provider name (e.g. 'openai')
+   * @param {string} operation - Operation name (e.g. 'POST /chat/completions')
+   * @param {*} request - Request payload, used to match recordings
+   * @param {Function} task - Async function performing the real request
+   * @param {Object} options - Capture options
+   * @param {AbortSignal} options.signal - Signal of the real request; cancelled requests are not recorded
+   * @returns {Promise<*>} - The real or recorded response
+   */
+  async capture(provider, operation, request, task, options = {}) {
+    if (this.mode === 'off') {
+      return task();
+    }
+
+    const key = this.keyFor(provider, operation, request);
+
+    if (this.mode === 'replay') {
+      return this._replay(key, provider, operation);
+    }
+
+    const entry = { key, provider, operation, request, timestamp: new Date().toISOString() };
+    try {
+      const response = await task();
+      await this.journal.append({ ...entry, response });
+      return response;
+    } catch (error) {
+      if (!(options.signal && options.signal.aborted)) {
+        await this.journal.append({ ...entry, error: error.message });
+      }
+      throw error;
+    }
+  }
+
+  /**
+   * Build the key used to match a request to its recordings
+   * @param {string} provider - Provider name
+   * @param {string} operation - Operation name
+   * @param {*} request - Request payload
+   * @returns {string} - Request key
+   */
+  keyFor(provider, operation, request) {
+    let matched = request === undefined ? null : omitFields(request, this.volatileFields);
+    if (this.normalize) {
+      matched = this.normalize(matched, { provider, operation });
+    }
+    return crypto
+      .createHash('sha256')
+      .update(stableStringify({ provider, operation, request: matched === undefined ? null : matched }))
+      .digest('hex');
+  }
+
+  /**
+   * Switch modes; entering replay reloads the journal
+   * @param {string} mode - 'record', 'replay' or 'off'
+   */
+  setMode(mode) {
+    if (!['record', 'replay', 'off'].includes(mode)) {
+      throw new Error(`Unsupported recorder mode: ${mode}. Use 'record', 'replay' or 'off'.`);
+    }
+    this.mode = mode;
+    this.replayIndex = null;
+    this.replayCursor.clear();
+  }
+
+  async _replay(key, provider, operation) {
+    if (!this.replayIndex) {
+      this.replayIndex = new Map();
+      for (const entry of await this.journal.load()) {
+        if (!this.replayIndex.has(entry.key)) {
+          this.replayIndex.set(entry.key, []);
+        }
+        this.replayIndex.get(entry.key).push(entry);
+      }
+    }
+
+    const recordings = this.replayIndex.get(key);
+    if (!recordings) {
+      throw new Error(`No recording for ${provider} ${operation} (key ${key.slice(0, 12)})`);
+    }
+
+    const cursor = this.replayCursor.get(key) || 0;
+    const entry = recordings[Math.min(cursor, recordings.length - 1)];
+    this.replayCursor.set(key, cursor + 1);
+
+    if (entry.error !== undefined) {
+      throw new Error(entry.error);
+    }
+    return entry.response;
+  }
+}
+
+module.exports = Recorder;
+module.exports.MemoryJournal = MemoryJournal;
+module.exports.FileJournal = FileJournal;
+module.exports.VOLATILE_FIELDS = VOLATILE_FIELDS;
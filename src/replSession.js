@@ -0,0 +1,105 @@
+const fs = require('fs');
+const Snapshot = require('./snapshot');
+
+const HELP = `Commands:
+  /memory          Show the messages the agent remembers
+  /usage           Show tokens and cost so far
+  /save <file>     Save the session to a file
+  /load <file>     Restore a session saved with /save
+  /reset           Forget the conversation
+  /exit            Leave the session
+Anything else is sent to the agent.
+`;
+
+/**
+ * ReplSession Class
+ * One interactive agent session: plain lines go to an Agent with memory,
+ * slash-commands inspect or persist the session. Used by `aitm repl`, which
+ * feeds it lines from the terminal.
+ */
+class ReplSession {
+  /**
+   * @param {Object} options - Session options
+   * @param {Agent} options.agent - Agent created with memory enabled
+   * @param {UsageTracker} options.usageTracker - Tracker attached to the agent's client
+   */
+  constructor(options = {}) {
+    if (!options.agent) {
+      throw new Error('ReplSession requires an agent');
+    }
+    this.agent = options.agent;
+    this.usageTracker = options.usageTracker || null;
+    this.closed = false;
+  }
+
+  /**
+   * Handle one line of input
+   * @param {string} line - User input
+   * @returns {Promise<string>} - Text to print
+   */
+  async handle(line) {
+    const input = line.trim();
+    if (!input) {
+      return '';
+    }
+    if (!input.startsWith('/')) {
+      const { output } = await this.agent.run(input);
+      return `${output}\n`;
+    }
+
+    const [command, ...args] = input.split(/\s+/);
+    switch (command) {
+      case '/help':
+        return HELP;
+
+      case '/memory':
+        return this.agent.messages
+          .map((message) => `[${message.role}] ${message.content || (message.tool_calls ? '(tool calls)' : '')}`)
+          .join('\n') + '\n';
+
+      case '/usage': {
+        if (!this.usageTracker) {
+          return 'Usage tracking is not enabled.\n';
+        }
+        const totals = this.usageTracker.getTotals();
+        return `${totals.requests} requests, ${totals.totalTokens} tokens, $${totals.cost.toFixed(4)}\n`;
+      }
+
+      case '/save': {
+        if (!args[0]) {
+          return 'Usage: /save <file>\n';
+        }
+        const snapshot = Snapshot.capture(this._components());
+        await fs.promises.writeFile(args[0], JSON.stringify(snapshot), 'utf8');
+        return `Saved session to ${args[0]}\n`;
+      }
+
+      case '/load': {
+        if (!args[0]) {
+          return 'Usage: /load <file>\n';
+        }
+        const snapshot = Snapshot.fromJSON(await fs.promises.readFile(args[0], 'utf8'));
+        await snapshot.restore(this._components());
+        return `Restored session from ${args[0]} (saved ${snapshot.takenAt})\n`;
+      }
+
+      case '/reset':
+        this.agent.reset();
+        return 'Conversation cleared.\n';
+
+      case '/exit':
+      case '/quit':
+        this.closed = true;
+        return '';
+
+      default:
+        return `Unknown command: ${command}. Type /help for commands.\n`;
+    }
+  }
+
+  _components() {
+    return this.usageTracker ? { agent: this.agent, usage: this.usageTracker } : { agent: this.agent };
+  }
+}
+
+module.exports = ReplSession;
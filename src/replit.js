@@ -6,7 +6,13 @@ require('dotenv').config();
  * Provides an interface to deploy and manage AI apps on Replit
  */
 class Replit {
-  constructor(apiToken = null) {
+  /**
+   * @param {string} apiToken - Replit API token (falls back to REPLIT_API_TOKEN)
+   * @param {Object} options - Client options
+   * @param {ConnectionPool} options.pool - Optional keep-alive connection pool
+   * @param {Recorder} options.recorder - Optional recorder for capturing or replaying calls
+   */
+  constructor(apiToken = null, options = {}) {
     const token = apiToken || process.env.REPLIT_API_TOKEN;
 
     if (!token) {
@@ -18,6 +24,8 @@ class Replit {
     this.apiToken = token;
     this.baseUrl = 'https://replit.com/api/v0';
     this.source = 'ai-time-machines';
+    this.pool = options.pool || null;
+    this.recorder = options.recorder || null;
   }
 
   /**
@@ -122,13 +130,27 @@ class Replit {
   }
 
   /**
-   * Make an HTTPS request to the Replit API
+   * Make a request to the Replit API, through the recorder when one is configured
    * @param {string} method - HTTP method
    * @param {string} path - API path
    * @param {Object} body - Request body
    * @returns {Promise<Object>} - Parsed response
    */
   _request(method, path, body = null) {
+    if (this.recorder) {
+      return this.recorder.capture('replit', `${method} ${path}`, body, () => this._httpRequest(method, path, body));
+    }
+    return this._httpRequest(method, path, body);
+  }
+
+  /**
+   * Make an HTTPS request to the Replit API
+   * @param {string} method - HTTP method
+   * @param {string} path - API path
+   * @param {Object} body - Request body
+   * @returns {Promise<Object>} - Parsed response
+   */
+  _httpRequest(method, path, body = null) {
     return new Promise((resolve, reject) => {
       const url = new URL(this.baseUrl + path);
       const bodyStr = body ? JSON.stringify(body) : null;
@@ -138,6 +160,7 @@ class Replit {
         port: url.port || 443,
         path: url.pathname + url.search,
         method,
+        agent: this.pool ? this.pool.agentFor(url.protocol) : undefined,
         headers: {
           Authorization: `Bearer ${this.apiToken}`,
           'Content-Type': 'application/json',
@@ -162,13 +185,13 @@ class Replit {
             if (res.statusCode >= 200 && res.statusCode < 300) {
               resolve(parsed);
             } else {
-              reject(new Error(`Replit API Error: ${res.statusCode} - ${parsed.message || data}`));
+              reject(Object.assign(new Error(`Replit API Error: ${res.statusCode} - ${parsed.message || data}`), { statusCode: res.statusCode }));
             }
           } catch {
             if (res.statusCode >= 200 && res.statusCode < 300) {
               resolve({ raw: data });
             } else {
-              reject(new Error(`Replit API Error: ${res.statusCode} - ${data}`));
+              reject(Object.assign(new Error(`Replit API Error: ${res.statusCode} - ${data}`), { statusCode: res.statusCode }));
             }
           }
         });
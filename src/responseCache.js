@@ -0,0 +1,124 @@
+const crypto = require('crypto');
+const { stableStringify } = require('./utils');
+
+/**
+ * MemoryStore Class
+ * In-memory LRU store with per-entry expiry
+ */
+class MemoryStore {
+  /**
+   * @param {number} maxEntries - Maximum entries before the least recently used is evicted
+   */
+  constructor(maxEntries = 500) {
+    this.maxEntries = maxEntries;
+    this.entries = new Map();
+  }
+
+  async get(key) {
+    const entry = this.entries.get(key);
+    if (!entry) {
+      return undefined;
+    }
+
+    if (entry.expiresAt !== null && entry.expiresAt <= Date.now()) {
+      this.entries.delete(key);
+      return undefined;
+    }
+
+    // Re-insert so Map iteration order tracks recency
+    this.entries.delete(key);
+    this.entries.set(key, entry);
+    return entry.value;
+  }
+
+  async set(key, value, ttl) {
+    this.entries.delete(key);
+    this.entries.set(key, { value, expiresAt: ttl ? Date.now() + ttl : null });
+
+    while (this.entries.size > this.maxEntries) {
+      this.entries.delete(this.entries.keys().next().value);
+    }
+  }
+
+  async delete(key) {
+    this.entries.delete(key);
+  }
+
+  async clear() {
+    this.entries.clear();
+  }
+}
+
+/**
+ * ResponseCache Class
+ * Caches completion responses keyed on provider, model and request
+ * parameters. Uses an in-memory LRU by default; any store exposing async
+ * get(key), set(key, value, ttl), delete(key) and clear() can be used instead
+ * (for example a thin Redis adapter).
+ */
+class ResponseCache {
+  /**
+   * @param {Object} options - Cache options
+   * @param {number} options.ttl - Entry lifetime in milliseconds (0 for no expiry)
+   * @param {number} options.maxEntries - Size of the default in-memory store
+   * @param {Object} options.store - Custom backing store
+   */
+  constructor(options = {}) {
+    this.ttl = options.ttl !== undefined ? options.ttl : 3600000;
+    this.store = options.store || new MemoryStore(options.maxEntries);
+    this.stats = { hits: 0, misses: 0 };
+  }
+
+  /**
+   * Build a cache key for a request
+   * @param {string} provider - Provider name
+   * @param {Object} params - Request parameters, including model and messages
+   * @returns {string} - Cache key
+   */
+  keyFor(provider, params) {
+    const hash = crypto.createHash('sha256').update(stableStringify(params)).digest('hex');
+    return `${provider}:${params.model}:${hash}`;
+  }
+
+  /**
+   * Look up a cached response
+   * @param {string} key - Cache key
+   * @returns {Promise<*>} - Cached value, or undefined on a miss
+   */
+  async get(key) {
+    const value = await this.store.get(key);
+    if (value === undefined) {
+      this.stats.misses++;
+    } else {
+      this.stats.hits++;
+    }
+    return value;
+  }
+
+  /**
+   * Store a response
+   * @param {string} key - Cache key
+   * @param {*} value - Value to cache
+   */
+  async set(key, value) {
+    await this.store.set(key, value, this.ttl);
+  }
+
+  /**
+   * Remove every cached response
+   */
+  async clear() {
+    await this.store.clear();
+  }
+
+  /**
+   * Get hit and miss counts
+   * @returns {Object} - Cache statistics
+   */
+  getStats() {
+    return { ...this.stats };
+  }
+}
+
+module.exports = ResponseCache;
+module.exports.MemoryStore = MemoryStore;
@@ -0,0 +1,109 @@
+/**
+ * Saga Class
+ * Runs a sequence of writes that span several systems (e.g. upload an
+ * artifact, insert a database row, post a message) and undoes the completed
+ * ones when a later step fails, so a partial failure does not leave
+ * dangling state behind.
+ *
+ * Each step has an action and an optional compensation. Actions receive
+ * { input, results } where results maps earlier step names to their
+ * outputs. Compensations receive the same context plus the step's own
+ * output, and run in reverse order.
+ *
+ * Example:
+ *   const saga = new Saga('publish')
+ *     .step('upload', ({ input }) => storage.put(input.key, input.body), ({ output }) => storage.remove(output.key))
+ *     .step('record', ({ results }) => db.insert(results.upload), ({ output }) => db.delete(output.id))
+ *     .step('notify', ({ results }) => chat.post(results.record.id));
+ *   const { results } = await saga.run(artifact);
+ */
+class Saga {
+  /**
+   * @param {string} name - Saga name used in error messages
+   */
+  constructor(name = 'saga') {
+    this.name = name;
+    this.steps = [];
+  }
+
+  /**
+   * Add a step
+   * @param {string} name - Step name, unique within the saga
+   * @param {Function} action - Async (context) => output
+   * @param {Function} compensate - Async (context) => void that undoes the action
+   * @returns {Saga} - This saga, for chaining
+   */
+  step(name, action, compensate = null) {
+    if (this.steps.some((step) => step.name === name)) {
+      throw new Error(`Saga ${this.name} has duplicate step: ${name}`);
+    }
+    if (typeof action !== 'function') {
+      throw new Error(`Saga ${this.name} step ${name} needs an action function`);
+    }
+    this.steps.push({ name, action, compensate });
+    return this;
+  }
+
+  /**
+   * Run the steps in order, compensating completed steps if one fails
+   * @param {*} input - Input passed to every step
+   * @param {Object} options - Run options
+   * @param {Function} options.onStep - Called with { name, status, output, error } where status is 'completed', 'failed', 'compensated' or 'compensation_failed'
+   * @returns {Promise<Object>} - { results } keyed by step name
+   */
+  async run(input, options = {}) {
+    const results = {};
+    const completed = [];
+
+    const report = (event) => {
+      if (typeof options.onStep === 'function') {
+        options.onStep(event);
+      }
+    };
+
+    for (const step of this.steps) {
+      try {
+        results[step.name] = await step.action({ input, results: { ...results } });
+      } catch (error) {
+        report({ name: step.name, status: 'failed', error });
+        const { compensated, errors } = await this._compensate(completed, input, results, report);
+
+        const failure = new Error(`Saga ${this.name} failed at step ${step.name}: ${error.message}`);
+        failure.step = step.name;
+        failure.cause = error;
+        failure.compensated = compensated;
+        failure.compensationErrors = errors;
+        throw failure;
+      }
+
+      completed.push(step);
+      report({ name: step.name, status: 'completed', output: results[step.name] });
+    }
+
+    return { results };
+  }
+
+  async _compensate(completed, input, results, report) {
+    const compensated = [];
+    const errors = [];
+
+    // Undo in reverse order; keep going when a compensation fails so the others still run
+    for (const step of [...completed].reverse()) {
+      if (!step.compensate) {
+        continue;
+      }
+      try {
+        await step.compensate({ input, results: { ...results }, output: results[step.name] });
+        compensated.push(step.name);
+        report({ name: step.name, status: 'compensated' });
+      } catch (error) {
+        errors.push({ step: step.name, error });
+        report({ name: step.name, status: 'compensation_failed', error });
+      }
+    }
+
+    return { compensated, errors };
+  }
+}
+
+module.exports = Saga;
@@ -0,0 +1,331 @@
+const { paginate } = require('./pagination');
+
+const CRON_FIELDS = [
+  { name: 'minute', min: 0, max: 59 },
+  { name: 'hour', min: 0, max: 23 },
+  { name: 'day of month', min: 1, max: 31 },
+  { name: 'month', min: 1, max: 12 },
+  { name: 'day of week', min: 0, max: 6 },
+];
+
+const STATE_KEY = 'scheduler:state';
+
+/**
+ * Parse a five-field cron expression (minute hour day-of-month month day-of-week).
+ * Supports *, numbers, ranges (1-5), lists (1,15) and steps (star/15, 0-30/5).
+ * Day of week 7 is accepted as Sunday.
+ * @param {string} expression - Cron expression
+ * @returns {Object} - Sets of allowed values per field
+ */
+function parseCron(expression) {
+  const parts = String(expression).trim().split(/\s+/);
+  if (parts.length !== 5) {
+    throw new Error(`Invalid cron expression "${expression}": expected 5 fields`);
+  }
+
+  const [minutes, hours, daysOfMonth, months, daysOfWeek] = parts.map((part, i) => {
+    const field = CRON_FIELDS[i];
+    const max = i === 4 ? 7 : field.max;
+    const values = new Set();
+
+    for (const item of part.split(',')) {
+      // Each list item is *, N or N-M, optionally followed by /step; empty items such as "1,,2" are rejected
+      if (!/^(\*|\d+(-\d+)?)(\/\d+)?$/.test(item)) {
+        throw new Error(`Invalid cron expression "${expression}": bad ${field.name} field "${part}"`);
+      }
+      const [range, stepText] = item.split('/');
+      const step = stepText === undefined ? 1 : Number(stepText);
+      let start;
+      let end;
+
+      if (range === '*') {
+        start = field.min;
+        end = field.max;
+      } else if (range.includes('-')) {
+        [start, end] = range.split('-').map(Number);
+      } else {
+        start = Number(range);
+        end = stepText === undefined ? start : field.max;
+      }
+
+      if (![start, end, step].every(Number.isInteger) || step < 1 || start < field.min || end > max || start > end) {
+        throw new Error(`Invalid cron expression "${expression}": bad ${field.name} field "${part}"`);
+      }
+
+      for (let value = start; value <= end; value += step) {
+        values.add(i === 4 && value === 7 ? 0 : value);
+      }
+    }
+
+    return values;
+  });
+
+  return {
+    minutes,
+    hours,
+    daysOfMonth,
+    months,
+    daysOfWeek,
+    // As in standard cron, a day field starting with * (including steps such as */2) counts as unrestricted
+    anyDayOfMonth: parts[2].startsWith('*'),
+    anyDayOfWeek: parts[4].startsWith('*'),
+  };
+}
+
+/**
+ * Find the next time a cron expression fires, strictly after a given time
+ * @param {Object|string} cron - Parsed cron or expression (evaluated in local time)
+ * @param {number} after - Timestamp in milliseconds
+ * @returns {number} - Next fire time in milliseconds
+ */
+function nextCronTime(cron, after) {
+  const spec = typeof cron === 'string' ? parseCron(cron) : cron;
+  const date = new Date(after);
+  date.setSeconds(0, 0);
+  date.setMinutes(date.getMinutes() + 1);
+
+  const dayMatches = () => {
+    const dom = spec.daysOfMonth.has(date.getDate());
+    const dow = spec.daysOfWeek.has(date.getDay());
+    // Standard cron: when both day fields are restricted, either may match
+    if (!spec.anyDayOfMonth && !spec.anyDayOfWeek) {
+      return dom || dow;
+    }
+    return dom && dow;
+  };
+
+  // Bounded search: any valid expression fires within a few years
+  const limit = after + 5 * 366 * 24 * 60 * 60 * 1000;
+  while (date.getTime() <= limit) {
+    if (!spec.months.has(date.getMonth() + 1)) {
+      date.setMonth(date.getMonth() + 1, 1);
+      date.setHours(0, 0, 0, 0);
+    } else if (!dayMatches()) {
+      date.setDate(date.getDate() + 1);
+      date.setHours(0, 0, 0, 0);
+    } else if (!spec.hours.has(date.getHours())) {
+      date.setHours(date.getHours() + 1, 0, 0, 0);
+    } else if (!spec.minutes.has(date.getMinutes())) {
+      date.setMinutes(date.getMinutes() + 1, 0, 0);
+    } else {
+      return date.getTime();
+    }
+  }
+
+  throw new Error('Cron expression never fires');
+}
+
+/**
+ * Scheduler Class
+ * Runs async jobs (for example agent.run calls) on cron expressions or
+ * fixed intervals. When a store is given, last/next run times are saved
+ * so that after a restart a job whose run was missed fires once to catch up.
+ */
+class Scheduler {
+  /**
+   * @param {Object} options - Scheduler options
+   * @param {Object} options.store - Optional store with async get(key) and set(key, value)
+   * @param {number} options.tickInterval - Milliseconds between due-job checks (default 1000)
+   * @param {boolean} options.catchUp - Run missed jobs once on start (default true)
+   * @param {Function} options.now - Clock returning milliseconds (for tests)
+   */
+  constructor(options = {}) {
+    this.store = options.store || null;
+    this.tickInterval = options.tickInterval || 1000;
+    this.catchUp = options.catchUp !== undefined ? options.catchUp : true;
+    this.now = options.now || Date.now;
+    this.jobs = new Map();
+    this.timer = null;
+  }
+
+  /**
+   * Add a job
+   * @param {string} name - Unique job name
+   * @param {Object} trigger - { cron: '0 9 * * 1-5' } or { every: milliseconds }
+   * @param {Function} job - Async function to run
+   * @returns {Scheduler} - This scheduler
+   */
+  schedule(name, trigger, job) {
+    if (this.jobs.has(name)) {
+      throw new Error(`Job already scheduled: ${name}`);
+    }
+    if (typeof job !== 'function') {
+      throw new Error(`Job ${name} must be a function`);
+    }
+
+    let cron = null;
+    if (trigger.cron) {
+      cron = parseCron(trigger.cron);
+    } else if (!(Number.isFinite(trigger.every) && trigger.every > 0)) {
+      throw new Error(`Job ${name} needs a cron expression or a positive every interval`);
+    }
+
+    this.jobs.set(name, {
+      name,
+      trigger,
+      cron,
+      job,
+      lastRun: null,
+      nextRun: null,
+      running: false,
+      lastError: null,
+    });
+    return this;
+  }
+
+  /**
+   * Remove a job
+   * @param {string} name - Job name
+   */
+  unschedule(name) {
+    this.jobs.delete(name);
+  }
+
+  /**
+   * Load saved state, catch up on missed runs and start ticking
+   */
+  async start() {
+    const saved = this.store ? await this._loadState() : {};
+    const now = this.now();
+
+    for (const entry of this.jobs.values()) {
+      const state = saved[entry.name];
+      entry.lastRun = state ? state.lastRun : null;
+
+      if (state && state.nextRun !== null && state.nextRun <= now) {
+        entry.nextRun = this.catchUp ? now : this._nextRun(entry, now);
+      } else {
+        entry.nextRun = state && state.nextRun !== null ? state.nextRun : this._nextRun(entry, now);
+      }
+    }
+
+    await this._saveState();
+    await this.tick();
+
+    if (!this.timer) {
+      this.timer = setInterval(() => {
+        this.tick().catch((error) => console.error(`Scheduler tick failed: ${error.message}`));
+      }, this.tickInterval);
+    }
+  }
+
+  /**
+   * Stop ticking; running jobs are allowed to finish
+   */
+  stop() {
+    if (this.timer) {
+      clearInterval(this.timer);
+      this.timer = null;
+    }
+  }
+
+  /**
+   * Run every job that is due
+   */
+  async tick() {
+    const now = this.now();
+    const due = Array.from(this.jobs.values()).filter(
+      (entry) => !entry.running && entry.nextRun !== null && entry.nextRun <= now
+    );
+
+    await Promise.all(due.map((entry) => this._run(entry)));
+  }
+
+  /**
+   * Run a job immediately, outside its schedule
+   * @param {string} name - Job name
+   */
+  async runNow(name) {
+    const entry = this.jobs.get(name);
+    if (!entry) {
+      throw new Error(`Unknown job: ${name}`);
+    }
+    if (entry.running) {
+      throw new Error(`Job is already running: ${name}`);
+    }
+    await this._run(entry);
+  }
+
+  /**
+   * List jobs with their run times
+   * @param {Object} options - { cursor, limit }
+   * @returns {Object} - Page { items, nextCursor } of job summaries
+   */
+  list(options = {}) {
+    const jobs = Array.from(this.jobs.values()).map(({ name, trigger, lastRun, nextRun, running, lastError }) => ({
+      name,
+      trigger,
+      lastRun,
+      nextRun,
+      running,
+      lastError,
+    }));
+    return paginate(jobs, options);
+  }
+
+  /**
+   * Capture job run times for a Snapshot
+   * @returns {Object} - { lastRun, nextRun } keyed by job name
+   */
+  snapshot() {
+    const state = {};
+    for (const entry of this.jobs.values()) {
+      state[entry.name] = { lastRun: entry.lastRun, nextRun: entry.nextRun };
+    }
+    return state;
+  }
+
+  /**
+   * Restore job run times from a snapshot; unknown jobs are ignored
+   * @param {Object} state - Output of snapshot()
+   */
+  async restore(state) {
+    for (const [name, times] of Object.entries(state)) {
+      const entry = this.jobs.get(name);
+      if (entry) {
+        entry.lastRun = times.lastRun;
+        entry.nextRun = times.nextRun;
+      }
+    }
+    await this._saveState();
+  }
+
+  async _run(entry) {
+    entry.running = true;
+    const startedAt = this.now();
+
+    try {
+      await entry.job();
+      entry.lastError = null;
+    } catch (error) {
+      entry.lastError = error.message;
+      console.error(`Scheduled job ${entry.name} failed: ${error.message}`);
+    } finally {
+      entry.running = false;
+      entry.lastRun = startedAt;
+      entry.nextRun = this._nextRun(entry, Math.max(startedAt, this.now()));
+      await this._saveState();
+    }
+  }
+
+  _nextRun(entry, after) {
+    return entry.cron ? nextCronTime(entry.cron, after) : after + entry.trigger.every;
+  }
+
+  async _loadState() {
+    const stored = await this.store.get(STATE_KEY);
+    return stored ? JSON.parse(stored) : {};
+  }
+
+  async _saveState() {
+    if (!this.store) {
+      return;
+    }
+
+    await this.store.set(STATE_KEY, JSON.stringify(this.snapshot()));
+  }
+}
+
+module.exports = Scheduler;
+module.exports.parseCron = parseCron;
+module.exports.nextCronTime = nextCronTime;
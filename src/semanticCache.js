@@ -0,0 +1,206 @@
+const crypto = require('crypto');
+const { stableStringify } = require('./utils');
+
+/**
+ * Cosine similarity of two vectors
+ * @param {Array<number>} a - First vector
+ * @param {Array<number>} b - Second vector
+ * @returns {number} - Similarity between -1 and 1 (0 when either vector is empty)
+ */
+function cosineSimilarity(a, b) {
+  if (a.length !== b.length) {
+    throw new Error(`Embedding dimensions differ: ${a.length} and ${b.length}`);
+  }
+
+  let dot = 0;
+  let normA = 0;
+  let normB = 0;
+  for (let i = 0; i < a.length; i++) {
+    dot += a[i] * b[i];
+    normA += a[i] * a[i];
+    normB += b[i] * b[i];
+  }
+  return normA === 0 || normB === 0 ? 0 : dot / Math.sqrt(normA * normB);
+}
+
+/**
+ * SemanticCache Class
+ * Reuses answers for prompts that mean the same thing rather than only for
+ * identical requests (see ResponseCache). Prompts are embedded and compared
+ * with the cached ones by cosine similarity; a match at or above the
+ * threshold returns the stored answer without calling the model.
+ *
+ * Entries are scoped by everything other than the prompt itself (the
+ * wrapped client's provider and model, request options and earlier
+ * messages), so a different provider, model or system prompt never reuses
+ * an answer. The index is kept in memory and searched linearly,
+ * which suits FAQ-style workloads of a few thousand entries.
+ *
+ * Example:
+ *   const cache = new SemanticCache({ embedder: chatgpt, threshold: 0.93 });
+ *   const bot = cache.wrap(chatgpt);
+ *   await bot.chat('How do I reset my password?');
+ *   await bot.chat('how can I reset my password'); // served from the cache
+ */
+class SemanticCache {
+  /**
+   * @param {Object} options - Cache options
+   * @param {Object} options.embedder - Client with embed(text), such as ChatGPT
+   * @param {Function} options.embed - Async (text) => vector; used instead of an embedder
+   * @param {number} options.threshold - Minimum cosine similarity for a hit (default 0.92)
+   * @param {number} options.maxEntries - Entries kept before the least recently used is evicted (default 500)
+   * @param {number} options.ttl - Entry lifetime in milliseconds (0 for no expiry, the default)
+   */
+  constructor(options = {}) {
+    if (typeof options.embed === 'function') {
+      this.embed = options.embed;
+    } else if (options.embedder && typeof options.embedder.embed === 'function') {
+      this.embed = (text) => options.embedder.embed(text);
+    } else {
+      throw new Error('SemanticCache requires an embedder with embed(text) or an embed function');
+    }
+
+    this.threshold = options.threshold !== undefined ? options.threshold : 0.92;
+    this.maxEntries = options.maxEntries || 500;
+    this.ttl = options.ttl || 0;
+    this.entries = [];
+    this.stats = { hits: 0, misses: 0 };
+  }
+
+  /**
+   * Find the closest cached answer
+   * @param {string} prompt - Prompt text
+   * @param {string} scope - Scope the prompt belongs to (see scopeFor)
+   * @param {Array<number>} embedding - Precomputed embedding of the prompt
+   * @returns {Promise<Object|undefined>} - { response, similarity, prompt }, or undefined on a miss
+   */
+  async lookup(prompt, scope = '', embedding = null) {
+    const vector = embedding || await this.embed(prompt);
+    const now = Date.now();
+    this.entries = this.entries.filter((entry) => entry.expiresAt === null || entry.expiresAt > now);
+
+    let best = null;
+    let bestSimilarity = -Infinity;
+    for (const entry of this.entries) {
+      if (entry.scope !== scope) {
+        continue;
+      }
+      const similarity = cosineSimilarity(vector, entry.embedding);
+      if (similarity > bestSimilarity) {
+        best = entry;
+        bestSimilarity = similarity;
+      }
+    }
+
+    if (!best || bestSimilarity < this.threshold) {
+      this.stats.misses++;
+      return undefined;
+    }
+
+    this.stats.hits++;
+    // Move to the end so eviction removes the least recently used entry
+    this.entries.splice(this.entries.indexOf(best), 1);
+    this.entries.push(best);
+    return { response: best.response, similarity: bestSimilarity, prompt: best.prompt };
+  }
+
+  /**
+   * Store an answer
+   * @param {string} prompt - Prompt text
+   * @param {*} response - Answer to reuse
+   * @param {string} scope - Scope the prompt belongs to
+   * @param {Array<number>} embedding - Precomputed embedding of the prompt
+   */
+  async store(prompt, response, scope = '', embedding = null) {
+    const vector = embedding || await this.embed(prompt);
+    this.entries.push({
+      prompt,
+      response,
+      scope,
+      embedding: vector,
+      expiresAt: this.ttl > 0 ? Date.now() + this.ttl : null,
+    });
+    if (this.entries.length > this.maxEntries) {
+      this.entries.shift();
+    }
+  }
+
+  /**
+   * Build the scope for a request from everything except its last prompt
+   * @param {Object} context - Request options, earlier messages, etc.
+   * @returns {string} - Scope key
+   */
+  scopeFor(context) {
+    return crypto.createHash('sha256').update(stableStringify(context)).digest('hex');
+  }
+
+  /**
+   * Wrap an AI client so chat and conversation answers are reused for similar prompts
+   * @param {Object} client - Client with chat and/or conversation
+   * @returns {Object} - Client with the same methods and properties; tool conversations and streams are not cached
+   */
+  wrap(client) {
+    // Clients wrapped on one cache must never answer for each other
+    const provider = client.provider || (client.constructor && client.constructor.name) || null;
+    const scopeOf = (context) => ({ provider, model: context.options.model || client.model || null, ...context });
+
+    const cached = async (prompt, context, send) => {
+      const scope = this.scopeFor(scopeOf(context));
+      const embedding = await this.embed(prompt);
+      const hit = await this.lookup(prompt, scope, embedding);
+      if (hit) {
+        return hit.response;
+      }
+
+      const response = await send();
+      await this.store(prompt, response, scope, embedding);
+      return response;
+    };
+
+    const methods = {
+      chat: (message, requestOptions = {}) => cached(
+        message,
+        { options: requestOptions },
+        () => client.chat(message, requestOptions)
+      ),
+      conversation: (messages, requestOptions = {}) => {
+        const last = messages[messages.length - 1];
+        // Only plain-text user turns can be compared by meaning
+        if (!last || last.role !== 'user' || typeof last.content !== 'string') {
+          return client.conversation(messages, requestOptions);
+        }
+        return cached(
+          last.content,
+          { options: requestOptions, history: messages.slice(0, -1) },
+          () => client.conversation(messages, requestOptions)
+        );
+      },
+    };
+
+    return new Proxy(client, {
+      get: (target, property, receiver) => (
+        Object.prototype.hasOwnProperty.call(methods, property) && typeof target[property] === 'function'
+          ? methods[property]
+          : Reflect.get(target, property, receiver)
+      ),
+    });
+  }
+
+  /**
+   * Remove every cached answer
+   */
+  clear() {
+    this.entries = [];
+  }
+
+  /**
+   * Get hit and miss counts
+   * @returns {Object} - { hits, misses, entries }
+   */
+  getStats() {
+    return { ...this.stats, entries: this.entries.length };
+  }
+}
+
+module.exports = SemanticCache;
+module.exports.cosineSimilarity = cosineSimilarity;
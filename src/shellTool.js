@@ -0,0 +1,157 @@
+const { spawn } = require('child_process');
+
+/**
+ * Shell command tool for agents
+ *
+ * Runs allow-listed programs without a shell, so arguments are never
+ * interpreted (no globbing, pipes or substitution). Each allowed command
+ * lists patterns its arguments must match; a command without patterns takes
+ * no arguments. Runs are killed after the timeout, output is cut off at
+ * maxOutputBytes per stream, and every call, allowed or refused, is recorded
+ * in the audit log, any object with an async record(event) method.
+ *
+ * Example:
+ *   const tool = shellTool({
+ *     // ls paths must be relative and free of '..', so listings stay inside cwd
+ *     commands: { git: { args: [/^(status|log|diff)$/, '--oneline'] }, ls: { args: [/^(?!\/|.*\.\.)[\w./-]+$/] } },
+ *     cwd: './workspace',
+ *     auditLog,
+ *   });
+ */
+
+const DEFAULT_MAX_OUTPUT_BYTES = 10 * 1024;
+
+/**
+ * Build a shell command tool
+ * @param {Object} options - Tool options
+ * @param {Object} options.commands - Allowed programs, each { args: [patterns] } with strings or RegExps
+ * @param {Object} options.auditLog - Log with async record(event) that every invocation is recorded in (required)
+ * @param {string} options.cwd - Working directory (default process.cwd())
+ * @param {Object} options.env - Environment for the commands (default process.env)
+ * @param {number} options.timeout - Milliseconds before a command is killed (default 10000)
+ * @param {number} options.maxOutputBytes - Largest stdout and stderr returned (default 10 KB each)
+ * @param {string} options.name - Tool name (default 'run_command')
+ * @returns {Object} - Tool definition { name, description, parameters, handler }
+ */
+function shellTool(options = {}) {
+  const commands = options.commands || {};
+  if (Object.keys(commands).length === 0) {
+    throw new Error('shellTool requires at least one allowed command');
+  }
+  if (!options.auditLog || typeof options.auditLog.record !== 'function') {
+    throw new Error('shellTool requires an auditLog with record(event)');
+  }
+
+  const auditLog = options.auditLog;
+  const limits = {
+    cwd: options.cwd || process.cwd(),
+    env: options.env || process.env,
+    timeout: options.timeout || 10000,
+    maxOutputBytes: options.maxOutputBytes || DEFAULT_MAX_OUTPUT_BYTES,
+  };
+
+  const refusal = (command, args) => {
+    if (!Object.prototype.hasOwnProperty.call(commands, command)) {
+      return `command ${command} is not allowed`;
+    }
+    const patterns = commands[command].args || [];
+    const bad = args.find((arg) => !patterns.some((pattern) => (
+      pattern instanceof RegExp ? pattern.test(arg) : pattern === arg
+    )));
+    return bad !== undefined ? `argument ${JSON.stringify(bad)} is not allowed for ${command}` : null;
+  };
+
+  return {
+    name: options.name || 'run_command',
+    description: `Run a command without a shell. Allowed commands: ${Object.keys(commands).join(', ')}`,
+    parameters: {
+      type: 'object',
+      properties: {
+        command: { type: 'string', enum: Object.keys(commands) },
+        args: { type: 'array', items: { type: 'string' } },
+      },
+      required: ['command'],
+    },
+    handler: async ({ command, args = [] }) => {
+      const argv = Array.isArray(args) ? args.map(String) : [];
+      const reason = refusal(String(command), argv);
+      if (reason) {
+        await auditLog.record({ component: 'shell', action: 'denied', tags: [String(command)], data: { command, args: argv, reason } });
+        throw new Error(`Denied: ${reason}`);
+      }
+
+      const started = Date.now();
+      const result = await run(command, argv, limits);
+      await auditLog.record({
+        component: 'shell',
+        action: 'run',
+        tags: [command],
+        data: {
+          command,
+          args: argv,
+          exitCode: result.exitCode,
+          signal: result.signal,
+          timedOut: result.timedOut,
+          durationMs: Date.now() - started,
+        },
+      });
+      return result;
+    },
+  };
+}
+
+/**
+ * Run a program and collect its output up to the limits
+ * @param {string} command - Program
+ * @param {Array<string>} args - Arguments
+ * @param {Object} limits - { cwd, env, timeout, maxOutputBytes }
+ * @returns {Promise<Object>} - { exitCode, signal, stdout, stderr, truncated, timedOut }
+ */
+function run(command, args, { cwd, env, timeout, maxOutputBytes }) {
+  return new Promise((resolve) => {
+    const child = spawn(command, args, { cwd, env, shell: false, stdio: ['ignore', 'pipe', 'pipe'] });
+    const output = { stdout: [], stderr: [] };
+    const sizes = { stdout: 0, stderr: 0 };
+    let truncated = false;
+    let timedOut = false;
+
+    for (const stream of ['stdout', 'stderr']) {
+      child[stream].on('data', (chunk) => {
+        const room = maxOutputBytes - sizes[stream];
+        if (room <= 0) {
+          truncated = true;
+          return;
+        }
+        if (chunk.length > room) {
+          truncated = true;
+        }
+        output[stream].push(chunk.subarray(0, room));
+        sizes[stream] += Math.min(chunk.length, room);
+      });
+    }
+
+    const timer = setTimeout(() => {
+      timedOut = true;
+      child.kill('SIGKILL');
+    }, timeout);
+
+    const finish = (exitCode, signal, error) => {
+      clearTimeout(timer);
+      resolve({
+        exitCode,
+        signal,
+        stdout: Buffer.concat(output.stdout).toString('utf8'),
+        stderr: error ? error.message : Buffer.concat(output.stderr).toString('utf8'),
+        truncated,
+        timedOut,
+      });
+    };
+
+    child.on('error', (error) => finish(null, null, error));
+    child.on('close', (code, signal) => finish(code, signal, null));
+  });
+}
+
+module.exports = {
+  shellTool,
+};
@@ -0,0 +1,106 @@
+/**
+ * Snapshot Class
+ * Point-in-time capture of long-running state such as conversation memory,
+ * agent memory, usage counters and scheduler run times, so it can be rolled
+ * back later or moved to another process.
+ *
+ * Components are any objects exposing snapshot() and restore(state); the
+ * state they return must be JSON-serializable. Conversation, Agent,
+ * UsageTracker, Scheduler and PromptRegistry all qualify.
+ *
+ * Example:
+ *   const snapshot = Snapshot.capture({ chat: conversation, usage: tracker });
+ *   await snapshot.save(store, 'before-import');
+ *   ...
+ *   (await Snapshot.load(store, 'before-import')).restore({ chat: conversation, usage: tracker });
+ */
+class Snapshot {
+  /**
+   * @param {Object} state - Captured state keyed by component name
+   * @param {string} takenAt - ISO timestamp of the capture
+   */
+  constructor(state = {}, takenAt = new Date().toISOString()) {
+    this.state = state;
+    this.takenAt = takenAt;
+  }
+
+  /**
+   * Capture the state of a set of components
+   * @param {Object} components - Components keyed by name
+   * @returns {Snapshot} - The snapshot
+   */
+  static capture(components) {
+    const state = {};
+    for (const [name, component] of Object.entries(components)) {
+      if (!component || typeof component.snapshot !== 'function') {
+        throw new Error(`Snapshot component ${name} must have a snapshot() method`);
+      }
+      // Round-trip through JSON so later mutations cannot leak into the capture
+      state[name] = JSON.parse(JSON.stringify(component.snapshot()));
+    }
+    return new Snapshot(state);
+  }
+
+  /**
+   * Restore captured state into a set of components
+   * @param {Object} components - Components keyed by name; names absent from the snapshot are left alone
+   * @returns {Promise<Array<string>>} - Names of the components that were restored
+   */
+  async restore(components) {
+    const restored = [];
+    for (const [name, component] of Object.entries(components)) {
+      if (!(name in this.state)) {
+        continue;
+      }
+      if (!component || typeof component.restore !== 'function') {
+        throw new Error(`Snapshot component ${name} must have a restore(state) method`);
+      }
+      await component.restore(JSON.parse(JSON.stringify(this.state[name])));
+      restored.push(name);
+    }
+    return restored;
+  }
+
+  /**
+   * Serialize the snapshot
+   * @returns {Object} - { takenAt, state }
+   */
+  toJSON() {
+    return { takenAt: this.takenAt, state: this.state };
+  }
+
+  /**
+   * Rebuild a snapshot from toJSON() output
+   * @param {string|Object} json - Serialized snapshot
+   * @returns {Snapshot} - The snapshot
+   */
+  static fromJSON(json) {
+    const data = typeof json === 'string' ? JSON.parse(json) : json;
+    return new Snapshot(data.state, data.takenAt);
+  }
+
+  /**
+   * Persist the snapshot to a store with async set(key, value)
+   * @param {Object} store - Store such as ResponseCache.MemoryStore or a database adapter
+   * @param {string} id - Snapshot identifier
+   */
+  async save(store, id) {
+    await store.set(`snapshot:${id}`, JSON.stringify(this));
+  }
+
+  /**
+   * Load a snapshot from a store with async get(key)
+   * @param {Object} store - Store used with save()
+   * @param {string} id - Snapshot identifier
+   * @returns {Promise<Snapshot|null>} - The snapshot, or null if not found
+   */
+  static async load(store, id) {
+    const stored = await store.get(`snapshot:${id}`);
+    if (stored === undefined || stored === null) {
+      return null;
+    }
+    return Snapshot.fromJSON(stored);
+  }
+}
+
+module.exports = Snapshot;
@@ -0,0 +1,124 @@
+/**
+ * StreamTee Class
+ * Wraps a token stream (such as Claude.stream()) so every chunk is appended
+ * to a journal before it is handed to the consumer. If the consumer stops
+ * early, e.g. because the HTTP client disconnected, the rest of the stream
+ * is still read into the journal so the run record is complete.
+ *
+ * Journal entries:
+ * - { runId, type: 'chunk', index, text }
+ * - { runId, type: 'end', status, text, error? } where status is
+ *   'completed', 'disconnected' or 'failed'
+ *
+ * Example:
+ *   const tee = new StreamTee(claude.stream(messages), { journal, runId: 'run-42' });
+ *   for await (const text of tee) {
+ *     res.write(text);
+ *   }
+ *   const record = await tee.finished;
+ */
+class StreamTee {
+  /**
+   * @param {AsyncIterable<string>} source - Token stream
+   * @param {Object} options - Tee options
+   * @param {Object} options.journal - Journal with async append(entry), e.g. Recorder.MemoryJournal or FileJournal
+   * @param {string} options.runId - Identifier stored with every entry
+   */
+  constructor(source, options = {}) {
+    if (!options.journal) {
+      throw new Error('StreamTee requires a journal');
+    }
+
+    this.source = source;
+    this.journal = options.journal;
+    this.runId = options.runId || null;
+    this.chunks = [];
+    this.started = false;
+
+    this.finished = new Promise((resolve, reject) => {
+      this._resolveFinished = resolve;
+      this._rejectFinished = reject;
+    });
+    // Callers that never await finished should not see an unhandled rejection
+    this.finished.catch(() => {});
+  }
+
+  [Symbol.asyncIterator]() {
+    if (this.started) {
+      throw new Error('StreamTee can only be iterated once');
+    }
+    this.started = true;
+    return this._iterate();
+  }
+
+  async *_iterate() {
+    const iterator = this.source[Symbol.asyncIterator]();
+    let status = 'disconnected';
+
+    try {
+      while (true) {
+        const { value, done } = await iterator.next();
+        if (done) {
+          status = 'completed';
+          break;
+        }
+        await this._append(value);
+        yield value;
+      }
+    } catch (error) {
+      status = 'failed';
+      await this._end('failed', error);
+      throw error;
+    } finally {
+      if (status === 'completed') {
+        await this._end('completed');
+      } else if (status === 'disconnected') {
+        // Keep reading in the background so the consumer is released immediately
+        this._drain(iterator);
+      }
+    }
+  }
+
+  async _drain(iterator) {
+    try {
+      while (true) {
+        const { value, done } = await iterator.next();
+        if (done) {
+          break;
+        }
+        await this._append(value);
+      }
+      await this._end('disconnected');
+    } catch (error) {
+      await this._end('failed', error).catch(() => {});
+    }
+  }
+
+  async _append(text) {
+    const index = this.chunks.length;
+    this.chunks.push(text);
+    await this.journal.append({ runId: this.runId, type: 'chunk', index, text });
+  }
+
+  async _end(status, error = null) {
+    const record = {
+      runId: this.runId,
+      type: 'end',
+      status,
+      text: this.chunks.join(''),
+    };
+    if (error) {
+      record.error = error.message;
+    }
+
+    try {
+      await this.journal.append(record);
+      this._resolveFinished(record);
+    } catch (journalError) {
+      this._rejectFinished(journalError);
+      throw journalError;
+    }
+  }
+}
+
+module.exports = StreamTee;
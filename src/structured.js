@@ -0,0 +1,167 @@
+/**
+ * Structured output helpers
+ *
+ * completeJSON() asks a model for JSON matching a JSON schema, validates
+ * the reply, and asks the model to repair it when parsing or validation
+ * fails. validateSchema() covers the commonly used subset of JSON schema:
+ * type, enum, const, properties, required, additionalProperties, items,
+ * minItems/maxItems, minLength/maxLength and minimum/maximum.
+ */
+
+const typeOf = (value) => {
+  if (value === null) return 'null';
+  if (Array.isArray(value)) return 'array';
+  return typeof value;
+};
+
+/**
+ * Validate a value against a JSON schema
+ * @param {*} value - Value to check
+ * @param {Object} schema - JSON schema
+ * @param {string} path - Location used in error messages
+ * @returns {Array<string>} - Validation errors, empty when valid
+ */
+function validateSchema(value, schema, path = '$') {
+  const errors = [];
+  const actual = typeOf(value);
+
+  if (schema.type) {
+    const allowed = Array.isArray(schema.type) ? schema.type : [schema.type];
+    const matches = allowed.some((type) => (
+      type === actual ||
+      (type === 'integer' && Number.isInteger(value)) ||
+      (type === 'number' && actual === 'number')
+    ));
+    if (!matches) {
+      return [`${path} should be ${allowed.join(' or ')}, got ${actual}`];
+    }
+  }
+
+  if (schema.enum && !schema.enum.some((option) => JSON.stringify(option) === JSON.stringify(value))) {
+    errors.push(`${path} should be one of ${JSON.stringify(schema.enum)}`);
+  }
+  if (schema.const !== undefined && JSON.stringify(schema.const) !== JSON.stringify(value)) {
+    errors.push(`${path} should equal ${JSON.stringify(schema.const)}`);
+  }
+
+  if (actual === 'string') {
+    if (schema.minLength !== undefined && value.length < schema.minLength) {
+      errors.push(`${path} should have at least ${schema.minLength} characters`);
+    }
+    if (schema.maxLength !== undefined && value.length > schema.maxLength) {
+      errors.push(`${path} should have at most ${schema.maxLength} characters`);
+    }
+  }
+
+  if (actual === 'number') {
+    if (schema.minimum !== undefined && value < schema.minimum) {
+      errors.push(`${path} should be >= ${schema.minimum}`);
+    }
+    if (schema.maximum !== undefined && value > schema.maximum) {
+      errors.push(`${path} should be <= ${schema.maximum}`);
+    }
+  }
+
+  if (actual === 'array') {
+    if (schema.minItems !== undefined && value.length < schema.minItems) {
+      errors.push(`${path} should have at least ${schema.minItems} items`);
+    }
+    if (schema.maxItems !== undefined && value.length > schema.maxItems) {
+      errors.push(`${path} should have at most ${schema.maxItems} items`);
+    }
+    if (schema.items) {
+      value.forEach((item, index) => errors.push(...validateSchema(item, schema.items, `${path}[${index}]`)));
+    }
+  }
+
+  if (actual === 'object') {
+    const properties = schema.properties || {};
+    for (const name of schema.required || []) {
+      if (!(name in value)) {
+        errors.push(`${path}.${name} is required`);
+      }
+    }
+    for (const [name, propertyValue] of Object.entries(value)) {
+      if (properties[name]) {
+        errors.push(...validateSchema(propertyValue, properties[name], `${path}.${name}`));
+      } else if (schema.additionalProperties === false) {
+        errors.push(`${path}.${name} is not allowed`);
+      }
+    }
+  }
+
+  return errors;
+}
+
+/**
+ * Parse JSON from a model reply, tolerating Markdown code fences
+ * @param {string} content - Model reply
+ * @returns {*} - Parsed value
+ */
+function parseJSONReply(content) {
+  const text = String(content || '').trim();
+  const fenced = text.match(/^```(?:json)?\s*([\s\S]*?)\s*```$/i);
+  return JSON.parse(fenced ? fenced[1] : text);
+}
+
+/**
+ * Ask a model for JSON matching a schema
+ * @param {Object} client - AI client with conversation(messages, options)
+ * @param {string|Array} prompt - User prompt, or a full message list
+ * @param {Object} schema - JSON schema the reply must satisfy
+ * @param {Object} options - Options
+ * @param {number} options.retries - Repair attempts after the first reply (default 2)
+ * @param {string} options.name - Schema name used with json_schema response formats (default 'response')
+ * @param {string} options.responseFormat - Native JSON mode to request: 'json_object' or 'json_schema' (OpenAI-compatible clients only)
+ * @param {Object} options.requestOptions - Options passed to the client (model, temperature, etc.)
+ * @returns {Promise<*>} - The validated value
+ */
+async function completeJSON(client, prompt, schema, options = {}) {
+  const retries = options.retries !== undefined ? options.retries : 2;
+  const requestOptions = { ...(options.requestOptions || {}) };
+
+  if (options.responseFormat === 'json_object') {
+    requestOptions.response_format = { type: 'json_object' };
+  } else if (options.responseFormat === 'json_schema') {
+    requestOptions.response_format = {
+      type: 'json_schema',
+      json_schema: { name: options.name || 'response', schema },
+    };
+  }
+
+  const messages = [
+    {
+      role: 'system',
+      content: `Respond only with JSON that matches this JSON schema, with no other text:\n${JSON.stringify(schema)}`,
+    },
+    ...(typeof prompt === 'string' ? [{ role: 'user', content: prompt }] : prompt),
+  ];
+
+  let errors = [];
+  for (let attempt = 0; attempt <= retries; attempt++) {
+    const content = await client.conversation(messages, requestOptions);
+
+    try {
+      const value = parseJSONReply(content);
+      errors = validateSchema(value, schema);
+      if (errors.length === 0) {
+        return value;
+      }
+    } catch (error) {
+      errors = [`invalid JSON (${error.message})`];
+    }
+
+    messages.push(
+      { role: 'assistant', content: String(content) },
+      { role: 'user', content: `That reply was not valid: ${errors.join('; ')}. Reply again with corrected JSON only.` }
+    );
+  }
+
+  throw new Error(`Structured output failed after ${retries + 1} attempts: ${errors.join('; ')}`);
+}
+
+module.exports = {
+  completeJSON,
+  validateSchema,
+  parseJSONReply,
+};
@@ -0,0 +1,137 @@
+const { runWithTools } = require('./tools');
+
+/**
+ * Test helpers for applications built on these integrations.
+ * Not exported from the main entry point; require 'ai-time-machines/src/testing'.
+ */
+
+/**
+ * MockAIProvider Class
+ * Drop-in stand-in for ChatGPT or Grok that never touches the network.
+ * Replies are scripted in order; each reply may be a string, an assistant
+ * message object (e.g. one with tool_calls), an Error to throw, or a
+ * function (messages, options) => reply. Once the script runs out the
+ * default response is returned. Every call is recorded in `calls`.
+ */
+class MockAIProvider {
+  /**
+   * @param {Object} options - Mock options
+   * @param {Array} options.responses - Scripted replies, consumed in order
+   * @param {string} options.defaultResponse - Reply once the script is exhausted
+   * @param {string} options.model - Reported model name
+   */
+  constructor(options = {}) {
+    this.responses = [...(options.responses || [])];
+    this.defaultResponse = options.defaultResponse !== undefined ? options.defaultResponse : 'mock response';
+    this.model = options.model || 'mock-model';
+    this.calls = [];
+  }
+
+  /**
+   * Build an assistant message that calls a tool
+   * @param {string} name - Tool name
+   * @param {Object} args - Tool arguments
+   * @param {string} id - Tool call id
+   * @returns {Object} - Assistant message with a single tool call
+   */
+  static toolCall(name, args = {}, id = `call_${name}`) {
+    return {
+      role: 'assistant',
+      content: null,
+      tool_calls: [{ id, type: 'function', function: { name, arguments: JSON.stringify(args) } }],
+    };
+  }
+
+  /**
+   * Queue replies
+   * @param {...*} responses - Replies to append to the script
+   * @returns {MockAIProvider} - This mock
+   */
+  reply(...responses) {
+    this.responses.push(...responses);
+    return this;
+  }
+
+  /**
+   * Make the next call fail
+   * @param {Error|string} error - Error to throw
+   * @returns {MockAIProvider} - This mock
+   */
+  failNext(error = 'Mock provider failure') {
+    this.responses.unshift(error instanceof Error ? error : new Error(error));
+    return this;
+  }
+
+  /**
+   * Send a message and get the next scripted reply
+   * @param {string} message - The message to send
+   * @param {Object} options - Request options (recorded only)
+   * @returns {Promise<string>} - Reply content
+   */
+  async chat(message, options = {}) {
+    const messages = [{ role: 'user', content: message }];
+    return (await this._next('chat', messages, options)).content;
+  }
+
+  /**
+   * Continue a conversation and get the next scripted reply
+   * @param {Array} messages - Array of message objects with role and content
+   * @param {Object} options - Request options (recorded only)
+   * @returns {Promise<string>} - Reply content
+   */
+  async conversation(messages, options = {}) {
+    return (await this._next('conversation', messages, options)).content;
+  }
+
+  /**
+   * Run a tool-calling conversation against scripted replies
+   * @param {Array} messages - Array of message objects with role and content
+   * @param {ToolRegistry} tools - Tools the model may call
+   * @param {Object} options - Additional options (autoExecute, maxIterations)
+   * @returns {Promise<Object>} - { content, toolCalls, messages }
+   */
+  async chatWithTools(messages, tools, options = {}) {
+    const { autoExecute, maxIterations, ...requestOptions } = options;
+
+    return runWithTools(
+      (history) => this._next('chatWithTools', history, requestOptions),
+      messages,
+      tools,
+      { autoExecute, maxIterations }
+    );
+  }
+
+  /**
+   * Set the reported model
+   * @param {string} model - The model name
+   */
+  setModel(model) {
+    this.model = model;
+  }
+
+  /**
+   * Clear the script and the recorded calls
+   */
+  reset() {
+    this.responses = [];
+    this.calls = [];
+  }
+
+  async _next(method, messages, options) {
+    this.calls.push({ method, messages: messages.map((m) => ({ ...m })), options });
+
+    let response = this.responses.length > 0 ? this.responses.shift() : this.defaultResponse;
+    if (typeof response === 'function') {
+      response = await response(messages, options);
+    }
+    if (response instanceof Error) {
+      throw response;
+    }
+
+    return typeof response === 'string' ? { role: 'assistant', content: response } : response;
+  }
+}
+
+module.exports = {
+  MockAIProvider,
+};
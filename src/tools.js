@@ -0,0 +1,114 @@
+/**
+ * ToolRegistry Class
+ * Functions the model may call through OpenAI-style tool calling. Each tool
+ * has a name, a description, a JSON-schema for its parameters and a handler.
+ */
+class ToolRegistry {
+  constructor() {
+    this.tools = new Map();
+  }
+
+  /**
+   * Register a tool
+   * @param {Object} tool - Tool definition
+   * @param {string} tool.name - Tool name (letters, digits, underscores and dashes)
+   * @param {string} tool.description - What the tool does, shown to the model
+   * @param {Object} tool.parameters - JSON-schema describing the arguments object
+   * @param {Function} tool.handler - Async (args) => result; non-string results are JSON-encoded
+   * @returns {ToolRegistry} - This registry
+   */
+  register({ name, description = '', parameters = { type: 'object', properties: {} }, handler }) {
+    if (!name || !/^[A-Za-z0-9_-]{1,64}$/.test(name)) {
+      throw new Error(`Invalid tool name: ${name}`);
+    }
+    if (typeof handler !== 'function') {
+      throw new Error(`Tool ${name} requires a handler function`);
+    }
+
+    this.tools.set(name, { name, description, parameters, handler });
+    return this;
+  }
+
+  /**
+   * Check whether a tool is registered
+   * @param {string} name - Tool name
+   * @returns {boolean} - True when registered
+   */
+  has(name) {
+    return this.tools.has(name);
+  }
+
+  /**
+   * Get tool definitions in the chat completions `tools` format
+   * @returns {Array<Object>} - Tool definitions
+   */
+  definitions() {
+    return Array.from(this.tools.values()).map(({ name, description, parameters }) => ({
+      type: 'function',
+      function: { name, description, parameters },
+    }));
+  }
+
+  /**
+   * Execute a tool call returned by the model
+   * @param {Object} toolCall - Tool call with id and function { name, arguments }
+   * @returns {Promise<Object>} - Tool result message ({ role: 'tool', tool_call_id, content })
+   */
+  async execute(toolCall) {
+    const { name, arguments: rawArguments } = toolCall.function;
+    const tool = this.tools.get(name);
+    let content;
+
+    // Failures are reported back to the model so it can correct itself
+    if (!tool) {
+      content = `Error: unknown tool ${name}`;
+    } else {
+      try {
+        const args = rawArguments ? JSON.parse(rawArguments) : {};
+        const result = await tool.handler(args);
+        content = typeof result === 'string' ? result : JSON.stringify(result);
+      } catch (error) {
+        content = `Error: ${error.message}`;
+      }
+    }
+
+    return { role: 'tool', tool_call_id: toolCall.id, content };
+  }
+}
+
+/**
+ * Run a tool-calling conversation
+ * @param {Function} send - Async (messages) => assistant message from the model
+ * @param {Array} messages - Starting messages
+ * @param {ToolRegistry} tools - Available tools
+ * @param {Object} options - Loop options
+ * @param {boolean} options.autoExecute - Execute tool calls and continue (default true)
+ * @param {number} options.maxIterations - Maximum model calls before giving up (default 5)
+ * @returns {Promise<Object>} - { content, toolCalls, messages }
+ */
+async function runWithTools(send, messages, tools, options = {}) {
+  const autoExecute = options.autoExecute !== undefined ? options.autoExecute : true;
+  const maxIterations = options.maxIterations || 5;
+  const history = [...messages];
+
+  for (let iteration = 0; iteration < maxIterations; iteration++) {
+    const message = await send(history);
+    const toolCalls = message.tool_calls || [];
+    history.push(message);
+
+    if (toolCalls.length === 0 || !autoExecute) {
+      return { content: message.content, toolCalls, messages: history };
+    }
+
+    for (const toolCall of toolCalls) {
+      history.push(await tools.execute(toolCall));
+    }
+  }
+
+  throw new Error(`Tool calling did not finish within ${maxIterations} iterations`);
+}
+
+module.exports = {
+  ToolRegistry,
+  runWithTools,
+};
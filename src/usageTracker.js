@@ -0,0 +1,239 @@
+/**
+ * Default pricing in USD per 1K tokens
+ */
+const DEFAULT_PRICING = {
+  'gpt-4': { prompt: 0.03, completion: 0.06 },
+  'gpt-4o': { prompt: 0.005, completion: 0.015 },
+  'gpt-4-turbo-preview': { prompt: 0.01, completion: 0.03 },
+  'gpt-3.5-turbo': { prompt: 0.0005, completion: 0.0015 },
+  'gpt-3.5-turbo-16k': { prompt: 0.003, completion: 0.004 },
+  'grok-beta': { prompt: 0.005, completion: 0.015 },
+  'grok-vision-beta': { prompt: 0.005, completion: 0.015 },
+  'claude-3-5-sonnet': { prompt: 0.003, completion: 0.015 },
+  'claude-3-5-haiku': { prompt: 0.0008, completion: 0.004 },
+  'claude-3-opus': { prompt: 0.015, completion: 0.075 },
+  'text-embedding-3-small': { prompt: 0.00002, completion: 0 },
+  'text-embedding-3-large': { prompt: 0.00013, completion: 0 },
+};
+
+/**
+ * UsageTracker Class
+ * Records token usage per request, converts it to cost using a pricing
+ * table, and optionally enforces a spending budget.
+ */
+class UsageTracker {
+  /**
+   * @param {Object} options - Tracker options
+   * @param {Object} options.pricing - Pricing overrides keyed by model (USD per 1K tokens)
+   * @param {number} options.maxSpend - Budget in USD; requests are refused once it is reached
+   */
+  constructor(options = {}) {
+    this.pricing = { ...DEFAULT_PRICING, ...(options.pricing || {}) };
+    this.maxSpend = options.maxSpend !== undefined ? options.maxSpend : null;
+    this.entries = [];
+    this.tags = [];
+    this.tenant = null;
+    this.listeners = [];
+  }
+
+  /**
+   * Get a view of this tracker that tags every recorded request.
+   * The view shares entries and budget with the tracker it came from.
+   * @param {string} tag - Tag to apply (e.g. 'ingestion')
+   * @returns {UsageTracker} - Tagged tracker
+   */
+  withTag(tag) {
+    const tagged = Object.create(this);
+    tagged.tags = [...this.tags, tag];
+    return tagged;
+  }
+
+  /**
+   * Get a view of this tracker that attributes every recorded request to a
+   * tenant (a customer, team or API key). Shares entries and budget like withTag().
+   * @param {string} tenant - Tenant identifier
+   * @returns {UsageTracker} - Tenant-scoped tracker
+   */
+  withTenant(tenant) {
+    const scoped = Object.create(this);
+    scoped.tenant = tenant;
+    return scoped;
+  }
+
+  /**
+   * Register a listener called with every recorded entry
+   * @param {Function} listener - (entry) => void
+   * @returns {Function} - Call to remove the listener
+   */
+  onRecord(listener) {
+    this.listeners.push(listener);
+    return () => {
+      const index = this.listeners.indexOf(listener);
+      if (index !== -1) {
+        this.listeners.splice(index, 1);
+      }
+    };
+  }
+
+  /**
+   * Record the token usage of a completed request
+   * @param {string} model - Model that served the request
+   * @param {Object} usage - Usage object with prompt_tokens and completion_tokens
+   * @returns {Object|null} - The recorded entry, or null when no usage was reported
+   */
+  record(model, usage) {
+    if (!usage) {
+      return null;
+    }
+
+    const promptTokens = usage.prompt_tokens || 0;
+    const completionTokens = usage.completion_tokens || 0;
+    const entry = {
+      model,
+      promptTokens,
+      completionTokens,
+      cost: this.estimateCost(model, promptTokens, completionTokens),
+      tags: this.tags,
+      tenant: this.tenant,
+      timestamp: new Date().toISOString(),
+    };
+
+    this.entries.push(entry);
+    for (const listener of this.listeners) {
+      try {
+        listener(entry);
+      } catch (error) {
+        console.error(`Usage listener failed: ${error.message}`);
+      }
+    }
+    return entry;
+  }
+
+  /**
+   * Estimate the cost of a request
+   * @param {string} model - Model name; versioned names fall back to their base model
+   * @param {number} promptTokens - Prompt token count
+   * @param {number} completionTokens - Completion token count
+   * @returns {number} - Cost in USD (0 for models without pricing)
+   */
+  estimateCost(model, promptTokens, completionTokens) {
+    const price = this._priceFor(model);
+    if (!price) {
+      return 0;
+    }
+
+    return (promptTokens * price.prompt + completionTokens * price.completion) / 1000;
+  }
+
+  /**
+   * Throw if the budget has been spent
+   */
+  checkBudget() {
+    if (this.maxSpend === null) {
+      return;
+    }
+
+    const spent = this.getTotals().cost;
+    if (spent >= this.maxSpend) {
+      throw Object.assign(
+        new Error(`Usage budget exceeded: spent $${spent.toFixed(4)} of $${this.maxSpend.toFixed(2)}`),
+        { statusCode: 429 }
+      );
+    }
+  }
+
+  /**
+   * Get running totals across all requests
+   * @param {Date|number|string} since - Only count requests recorded at or after this time
+   * @returns {Object} - Request, token and cost totals
+   */
+  getTotals(since = null) {
+    if (since === null) {
+      return summarize(this.entries);
+    }
+    const from = new Date(since).getTime();
+    return summarize(this.entries.filter((entry) => Date.parse(entry.timestamp) >= from));
+  }
+
+  /**
+   * Get totals grouped by tag or model
+   * @param {string} by - 'tag' or 'model'
+   * @returns {Object} - Totals keyed by tag or model name
+   */
+  getBreakdown(by = 'tag') {
+    if (by !== 'tag' && by !== 'model') {
+      throw new Error(`Unsupported breakdown: ${by}. Use 'tag' or 'model'.`);
+    }
+
+    const groups = {};
+    for (const entry of this.entries) {
+      const keys = by === 'model' ? [entry.model] : entry.tags;
+      for (const key of keys) {
+        (groups[key] = groups[key] || []).push(entry);
+      }
+    }
+
+    return Object.fromEntries(
+      Object.entries(groups).map(([key, entries]) => [key, summarize(entries)])
+    );
+  }
+
+  /**
+   * Clear all recorded usage
+   */
+  reset() {
+    this.entries.length = 0;
+  }
+
+  /**
+   * Capture recorded usage for a Snapshot
+   * @returns {Object} - { entries }
+   */
+  snapshot() {
+    return { entries: this.entries.map((entry) => ({ ...entry, tags: [...entry.tags] })) };
+  }
+
+  /**
+   * Replace recorded usage with a captured one.
+   * Tagged views keep sharing the restored entries.
+   * @param {Object} state - Output of snapshot()
+   */
+  restore(state) {
+    this.entries.length = 0;
+    this.entries.push(...state.entries.map((entry) => ({ ...entry, tags: [...entry.tags] })));
+  }
+
+  _priceFor(model) {
+    if (this.pricing[model]) {
+      return this.pricing[model];
+    }
+
+    // Match versioned names such as gpt-4-0613 to the longest known prefix
+    const base = Object.keys(this.pricing)
+      .filter((name) => model && model.startsWith(`${name}-`))
+      .sort((a, b) => b.length - a.length)[0];
+
+    return base ? this.pricing[base] : null;
+  }
+}
+
+/**
+ * Sum a list of usage entries
+ * @param {Array<Object>} entries - Usage entries
+ * @returns {Object} - Aggregated totals
+ */
+function summarize(entries) {
+  const totals = { requests: 0, promptTokens: 0, completionTokens: 0, totalTokens: 0, cost: 0 };
+
+  for (const entry of entries) {
+    totals.requests++;
+    totals.promptTokens += entry.promptTokens;
+    totals.completionTokens += entry.completionTokens;
+    totals.totalTokens += entry.promptTokens + entry.completionTokens;
+    totals.cost += entry.cost;
+  }
+
+  return totals;
+}
+
+module.exports = UsageTracker;
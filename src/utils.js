@@ -0,0 +1,108 @@
+const crypto = require('crypto');
+
+/**
+ * JSON-encode a value with object keys sorted so equal params hash equally
+ * @param {*} value - Value to encode
+ * @returns {string} - Canonical JSON
+ */
+function stableStringify(value) {
+  if (Array.isArray(value)) {
+    return `[${value.map(stableStringify).join(',')}]`;
+  }
+
+  if (value && typeof value === 'object') {
+    const keys = Object.keys(value).filter((key) => value[key] !== undefined).sort();
+    return `{${keys.map((key) => `${JSON.stringify(key)}:${stableStringify(value[key])}`).join(',')}}`;
+  }
+
+  return JSON.stringify(value);
+}
+
+/**
+ * Compare two secrets in constant time
+ * @param {string} a - Received value
+ * @param {string} b - Expected value
+ * @returns {boolean} - Whether they are equal
+ */
+function safeEqual(a, b) {
+  const left = Buffer.from(a);
+  const right = Buffer.from(b);
+  return left.length === right.length && crypto.timingSafeEqual(left, right);
+}
+
+/**
+ * Check that an HTTP request declares a JSON body
+ * @param {IncomingMessage} req - Incoming request
+ * @returns {boolean} - Whether the Content-Type is application/json
+ */
+function isJSONRequest(req) {
+  return /^application\/json\s*(;|$)/i.test(req.headers['content-type'] || '');
+}
+
+/**
+ * Read an HTTP request body
+ * @param {IncomingMessage} req - Incoming request
+ * @param {number} maxBytes - Largest accepted body; larger ones reject with 'Body too large' (statusCode 413)
+ * @returns {Promise<string>} - Body as UTF-8 text
+ */
+function readBody(req, maxBytes) {
+  return new Promise((resolve, reject) => {
+    const tooLarge = () => Object.assign(new Error('Body too large'), { statusCode: 413 });
+    if (Number(req.headers['content-length']) > maxBytes) {
+      reject(tooLarge());
+      return;
+    }
+
+    const chunks = [];
+    let size = 0;
+
+    const onData = (chunk) => {
+      size += chunk.length;
+      if (size > maxBytes) {
+        // Stop reading at once; the caller answers with sendJSONAndClose, which drops the connection
+        req.removeListener('data', onData);
+        req.pause();
+        reject(tooLarge());
+        return;
+      }
+      chunks.push(chunk);
+    };
+    req.on('data', onData);
+    req.on('end', () => resolve(Buffer.concat(chunks).toString('utf8')));
+    req.on('error', reject);
+  });
+}
+
+/**
+ * Send a JSON response
+ * @param {ServerResponse} res - Response
+ * @param {number} statusCode - HTTP status
+ * @param {*} body - Value to encode
+ * @param {Object} headers - Extra response headers
+ */
+function sendJSON(res, statusCode, body, headers = {}) {
+  res.writeHead(statusCode, { 'Content-Type': 'application/json', ...headers });
+  res.end(JSON.stringify(body));
+}
+
+/**
+ * Send a JSON response and close the connection, for requests whose body was
+ * left unread (such as one over the size limit)
+ * @param {IncomingMessage} req - Incoming request
+ * @param {ServerResponse} res - Response
+ * @param {number} statusCode - HTTP status
+ * @param {*} body - Value to encode
+ */
+function sendJSONAndClose(req, res, statusCode, body) {
+  res.on('finish', () => req.destroy());
+  sendJSON(res, statusCode, body, { Connection: 'close' });
+}
+
+module.exports = {
+  stableStringify,
+  safeEqual,
+  isJSONRequest,
+  readBody,
+  sendJSON,
+  sendJSONAndClose,
+};
@@ -0,0 +1,255 @@
+const crypto = require('crypto');
+const http = require('http');
+const { safeEqual, readBody, sendJSON, sendJSONAndClose } = require('./utils');
+
+// DER prefix turning a raw 32-byte Ed25519 public key into SPKI
+const ED25519_SPKI_PREFIX = Buffer.from('302a300506032b6570032100', 'hex');
+
+/**
+ * WebhookServer Class
+ * Receives inbound callbacks, verifies their signatures and dispatches them
+ * to registered handlers (for example an agent run):
+ * - POST /slack: Slack Events API, signed with the app's signing secret
+ * - POST /discord: Discord interactions, signed with Ed25519
+ * - POST /webhook: generic JSON webhooks with an X-Webhook-Timestamp header
+ *   (Unix seconds) and an X-Signature-256 header of the form
+ *   sha256=<hex HMAC of "<timestamp>.<body>">
+ *
+ * Only sources with a configured secret are served, and signed timestamps
+ * older than toleranceSeconds are rejected so captured requests cannot be
+ * replayed. Slack URL verification and Discord pings are answered
+ * automatically.
+ *
+ * Slack retries any event not acknowledged within three seconds, so Slack
+ * events are acknowledged before their handlers run and handler results are
+ * ignored; use idle() to wait for them. Discord and generic webhook handlers
+ * run before the reply and may return its JSON body.
+ *
+ * Example:
+ *   const server = new WebhookServer({ slackSigningSecret: process.env.SLACK_SIGNING_SECRET });
+ *   server.on('slack', async (payload) => { await agent.run(payload.event.text); });
+ *   await server.listen(3001);
+ */
+class WebhookServer {
+  /**
+   * @param {Object} options - Server options
+   * @param {string} options.slackSigningSecret - Enables POST /slack
+   * @param {string} options.discordPublicKey - Application public key (hex); enables POST /discord
+   * @param {string} options.secret - Shared HMAC secret; enables POST /webhook
+   * @param {number} options.toleranceSeconds - Maximum age of signed timestamps (default 300)
+   * @param {number} options.maxBodyBytes - Largest accepted body (default 1 MB)
+   * @param {Function} options.now - Clock returning milliseconds (for tests)
+   */
+  constructor(options = {}) {
+    this.slackSigningSecret = options.slackSigningSecret || null;
+    this.discordPublicKey = options.discordPublicKey
+      ? crypto.createPublicKey({
+        key: Buffer.concat([ED25519_SPKI_PREFIX, Buffer.from(options.discordPublicKey, 'hex')]),
+        format: 'der',
+        type: 'spki',
+      })
+      : null;
+    this.secret = options.secret || null;
+    this.toleranceSeconds = options.toleranceSeconds || 300;
+    this.maxBodyBytes = options.maxBodyBytes || 1024 * 1024;
+    this.now = options.now || Date.now;
+    this.handlers = { slack: [], discord: [], webhook: [] };
+    this.pending = new Set();
+  }
+
+  /**
+   * Register a handler for a source
+   * @param {string} source - 'slack', 'discord' or 'webhook'
+   * @param {Function} handler - Async (payload, { headers }) => optional JSON response body
+   * @returns {WebhookServer} - This server, for chaining
+   */
+  on(source, handler) {
+    if (!this.handlers[source]) {
+      throw new Error(`Unsupported webhook source: ${source}. Use 'slack', 'discord' or 'webhook'.`);
+    }
+    this.handlers[source].push(handler);
+    return this;
+  }
+
+  /**
+   * Check a Slack request signature
+   * @param {string} body - Raw request body
+   * @param {Object} headers - Request headers (lowercase names)
+   * @returns {boolean} - Whether the request is authentic and recent
+   */
+  verifySlack(body, headers) {
+    const timestamp = headers['x-slack-request-timestamp'];
+    const signature = headers['x-slack-signature'];
+    if (!timestamp || !signature || !this._isRecent(timestamp)) {
+      return false;
+    }
+    const expected = `v0=${crypto.createHmac('sha256', this.slackSigningSecret).update(`v0:${timestamp}:${body}`).digest('hex')}`;
+    return safeEqual(signature, expected);
+  }
+
+  /**
+   * Check a Discord interaction signature
+   * @param {string} body - Raw request body
+   * @param {Object} headers - Request headers (lowercase names)
+   * @returns {boolean} - Whether the request is authentic and recent
+   */
+  verifyDiscord(body, headers) {
+    const timestamp = headers['x-signature-timestamp'];
+    const signature = headers['x-signature-ed25519'];
+    if (!timestamp || !signature || !this._isRecent(timestamp)) {
+      return false;
+    }
+    try {
+      return crypto.verify(null, Buffer.from(timestamp + body), this.discordPublicKey, Buffer.from(signature, 'hex'));
+    } catch {
+      return false;
+    }
+  }
+
+  /**
+   * Check a generic webhook signature
+   * @param {string} body - Raw request body
+   * @param {Object} headers - Request headers (lowercase names)
+   * @returns {boolean} - Whether the request is authentic and recent
+   */
+  verifyWebhook(body, headers) {
+    const timestamp = headers['x-webhook-timestamp'];
+    const signature = headers['x-signature-256'];
+    if (!timestamp || !signature || !this._isRecent(timestamp)) {
+      return false;
+    }
+    const expected = `sha256=${crypto.createHmac('sha256', this.secret).update(`${timestamp}.${body}`).digest('hex')}`;
+    return safeEqual(signature, expected);
+  }
+
+  /**
+   * Wait for Slack events that were acknowledged but are still being handled
+   * @returns {Promise<void>} - Resolves once no dispatch is in flight
+   */
+  async idle() {
+    while (this.pending.size > 0) {
+      await Promise.all(this.pending);
+    }
+  }
+
+  /**
+   * Create a request handler for the webhook routes
+   * @returns {Function} - (req, res) handler for http.createServer
+   */
+  handler() {
+    return (req, res) => {
+      this._handle(req, res).catch(() => {
+        if (!res.headersSent) {
+          sendJSON(res, 500, { error: 'Internal error' });
+        }
+      });
+    };
+  }
+
+  /**
+   * Start a standalone webhook server
+   * @param {number} port - Port to listen on
+   * @param {string} host - Interface to bind
+   * @returns {Promise<http.Server>} - The listening server
+   */
+  listen(port = 3001, host = '127.0.0.1') {
+    const server = http.createServer(this.handler());
+
+    return new Promise((resolve, reject) => {
+      server.once('error', reject);
+      server.listen(port, host, () => resolve(server));
+    });
+  }
+
+  async _handle(req, res) {
+    const source = this._sourceFor(req);
+    if (!source) {
+      sendJSON(res, 404, { error: 'Not found' });
+      return;
+    }
+
+    let body;
+    try {
+      body = await readBody(req, this.maxBodyBytes);
+    } catch (error) {
+      sendJSONAndClose(req, res, 413, { error: error.message });
+      return;
+    }
+
+    const verify = { slack: this.verifySlack, discord: this.verifyDiscord, webhook: this.verifyWebhook }[source];
+    if (!verify.call(this, body, req.headers)) {
+      sendJSON(res, 401, { error: 'Invalid signature' });
+      return;
+    }
+
+    let payload;
+    try {
+      payload = JSON.parse(body);
+    } catch {
+      sendJSON(res, 400, { error: 'Body must be JSON' });
+      return;
+    }
+
+    if (source === 'slack' && payload.type === 'url_verification') {
+      sendJSON(res, 200, { challenge: payload.challenge });
+      return;
+    }
+    if (source === 'discord' && payload.type === 1) {
+      sendJSON(res, 200, { type: 1 });
+      return;
+    }
+    if (source === 'slack') {
+      sendJSON(res, 200, { ok: true });
+      this._dispatchLater(source, payload, req.headers);
+      return;
+    }
+
+    let response;
+    try {
+      for (const handler of this.handlers[source]) {
+        const result = await handler(payload, { headers: req.headers });
+        if (result !== undefined && response === undefined) {
+          response = result;
+        }
+      }
+    } catch {
+      sendJSON(res, 500, { error: 'Handler failed' });
+      return;
+    }
+
+    sendJSON(res, 200, response === undefined ? { ok: true } : response);
+  }
+
+  _dispatchLater(source, payload, headers) {
+    const dispatch = (async () => {
+      for (const handler of this.handlers[source]) {
+        await handler(payload, { headers });
+      }
+    })()
+      .catch((error) => {
+        console.error(`${source} handler failed: ${error.message}`);
+      })
+      .finally(() => {
+        this.pending.delete(dispatch);
+      });
+    this.pending.add(dispatch);
+  }
+
+  _sourceFor(req) {
+    if (req.method !== 'POST') {
+      return null;
+    }
+    const path = (req.url || '/').split('?')[0];
+    if (path === '/slack' && this.slackSigningSecret) return 'slack';
+    if (path === '/discord' && this.discordPublicKey) return 'discord';
+    if (path === '/webhook' && this.secret) return 'webhook';
+    return null;
+  }
+
+  _isRecent(timestamp) {
+    const age = Math.abs(this.now() / 1000 - Number(timestamp));
+    return Number.isFinite(age) && age <= this.toleranceSeconds;
+  }
+}
+
+module.exports = WebhookServer;
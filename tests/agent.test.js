@@ -0,0 +1,232 @@
+const Agent = require('../src/agent');
+const { ToolRegistry } = require('../src/tools');
+const PolicyEngine = require('../src/policyEngine');
+const ApprovalQueue = require('../src/approvalQueue');
+
+const toolCall = (id, name, args = {}) => ({
+  id,
+  type: 'function',
+  function: { name, arguments: JSON.stringify(args) },
+});
+
+/**
+ * Fake client replaying a scripted list of assistant messages
+ */
+const scriptedClient = (script) => ({
+  chatWithTools: jest.fn(async (messages) => {
+    const message = script.shift();
+    return {
+      content: message.content,
+      toolCalls: message.tool_calls || [],
+      messages: [...messages, message],
+    };
+  }),
+});
+
+describe('Agent', () => {
+  describe('Constructor', () => {
+    test('should throw error without a tool-calling client', () => {
+      expect(() => new Agent({ client: {} })).toThrow('requires a client with a chatWithTools');
+    });
+  });
+
+  describe('run', () => {
+    test('should answer directly when no tools are called', async () => {
+      const client = scriptedClient([{ role: 'assistant', content: 'Done' }]);
+      const agent = new Agent({ client, systemPrompt: 'You forecast.' });
+
+      const result = await agent.run('Say done');
+
+      expect(result.output).toBe('Done');
+      expect(result.steps).toBe(1);
+      expect(client.chatWithTools.mock.calls[0][0][0]).toEqual({ role: 'system', content: 'You forecast.' });
+      expect(client.chatWithTools.mock.calls[0][2]).toMatchObject({ autoExecute: false });
+    });
+
+    test('should execute tools and report every step through hooks', async () => {
+      const client = scriptedClient([
+        { role: 'assistant', content: null, tool_calls: [toolCall('c1', 'latest_value', { series: 'sales' })] },
+        { role: 'assistant', content: 'Sales are at 120.' },
+      ]);
+      const tools = new ToolRegistry().register({ name: 'latest_value', handler: () => '120' });
+      const hooks = {
+        onStep: jest.fn(),
+        onToolCall: jest.fn(),
+        onToolResult: jest.fn(),
+        onFinish: jest.fn(),
+      };
+      const agent = new Agent({ client, tools, hooks });
+
+      const result = await agent.run('What are sales?');
+
+      expect(result.output).toBe('Sales are at 120.');
+      expect(result.steps).toBe(2);
+      expect(hooks.onStep).toHaveBeenCalledTimes(2);
+      expect(hooks.onToolCall).toHaveBeenCalledWith(expect.objectContaining({ step: 1 }));
+      expect(hooks.onToolResult).toHaveBeenCalledWith(expect.objectContaining({ step: 1, result: '120' }));
+      expect(hooks.onFinish).toHaveBeenCalledWith({ output: 'Sales are at 120.', steps: 2 });
+      expect(result.messages.map((m) => m.role)).toEqual(['user', 'assistant', 'tool', 'assistant']);
+    });
+
+    test('should stop at the step limit', async () => {
+      const looping = { role: 'assistant', content: null, tool_calls: [toolCall('c', 'noop')] };
+      const client = scriptedClient([looping, looping, looping]);
+      const tools = new ToolRegistry().register({ name: 'noop', handler: () => 'ok' });
+      const agent = new Agent({ client, tools, maxSteps: 2 });
+
+      await expect(agent.run('Loop')).rejects.toThrow('did not finish within 2 steps');
+      expect(client.chatWithTools).toHaveBeenCalledTimes(2);
+    });
+
+    test('should stop when a hook throws', async () => {
+      const client = scriptedClient([
+        { role: 'assistant', content: null, tool_calls: [toolCall('c1', 'transfer')] },
+      ]);
+      const handler = jest.fn();
+      const tools = new ToolRegistry().register({ name: 'transfer', handler });
+      const agent = new Agent({
+        client,
+        tools,
+        hooks: {
+          onToolCall: () => {
+            throw new Error('Tool call rejected');
+          },
+        },
+      });
+
+      await expect(agent.run('Move funds')).rejects.toThrow('Tool call rejected');
+      expect(handler).not.toHaveBeenCalled();
+    });
+
+    test('should be cancellable with an AbortSignal', async () => {
+      const client = scriptedClient([{ role: 'assistant', content: 'never' }]);
+      const agent = new Agent({ client });
+      const controller = new AbortController();
+      controller.abort();
+
+      await expect(agent.run('Task', { signal: controller.signal })).rejects.toThrow('Agent run cancelled');
+      expect(client.chatWithTools).not.toHaveBeenCalled();
+    });
+
+    test('should pass the signal on to model calls', async () => {
+      const client = scriptedClient([{ role: 'assistant', content: 'Done' }]);
+      const agent = new Agent({ client });
+      const controller = new AbortController();
+
+      await agent.run('Task', { signal: controller.signal, requestOptions: { temperature: 0 } });
+
+      expect(client.chatWithTools.mock.calls[0][2]).toMatchObject({
+        temperature: 0,
+        signal: controller.signal,
+        autoExecute: false,
+        maxIterations: 1,
+      });
+    });
+  });
+
+  describe('memory', () => {
+    test('should keep messages between runs when enabled', async () => {
+      const client = scriptedClient([
+        { role: 'assistant', content: 'First' },
+        { role: 'assistant', content: 'Second' },
+      ]);
+      const agent = new Agent({ client, memory: true });
+
+      await agent.run('one');
+      await agent.run('two');
+
+      expect(client.chatWithTools.mock.calls[1][0].map((m) => m.content)).toEqual(['one', 'First', 'two', 'Second']);
+
+      agent.reset();
+      expect(agent.messages).toEqual([]);
+    });
+
+    test('should not keep an unfinished step in memory', async () => {
+      const client = scriptedClient([
+        { role: 'assistant', content: 'Looked it up' },
+        { role: 'assistant', content: null, tool_calls: [toolCall('c1', 'lookup')] },
+        { role: 'assistant', content: 'Recovered' },
+      ]);
+      const tools = new ToolRegistry().register({ name: 'lookup', handler: () => 'ok' });
+      let reject = false;
+      const agent = new Agent({
+        client,
+        tools,
+        memory: true,
+        hooks: {
+          onToolCall: () => {
+            if (reject) {
+              throw new Error('Tool call rejected');
+            }
+          },
+        },
+      });
+
+      await agent.run('one');
+      reject = true;
+      await expect(agent.run('two')).rejects.toThrow('Tool call rejected');
+
+      expect(agent.messages.map((m) => m.content)).toEqual(['one', 'Looked it up']);
+      expect(agent.messages.some((m) => m.tool_calls)).toBe(false);
+    });
+
+    test('should drop the user turn when the first model call fails', async () => {
+      const client = {
+        chatWithTools: jest.fn(async () => {
+          throw new Error('Service unavailable');
+        }),
+      };
+      const agent = new Agent({ client, memory: true });
+
+      await expect(agent.run('one')).rejects.toThrow('Service unavailable');
+
+      expect(agent.messages).toEqual([]);
+    });
+
+    test('should enforce a policy on the task and on tool calls', async () => {
+      const client = scriptedClient([
+        { role: 'assistant', content: null, tool_calls: [toolCall('c1', 'delete_series', { series: 'sales' })] },
+        { role: 'assistant', content: 'I am not allowed to delete it.' },
+      ]);
+      const handler = jest.fn();
+      const tools = new ToolRegistry().register({ name: 'delete_series', handler });
+      const policy = new PolicyEngine({ rules: { deniedTools: ['delete_series'], bannedPatterns: ['drop table'] } });
+      const agent = new Agent({ client, tools, policy });
+
+      await expect(agent.run('DROP TABLE series')).rejects.toThrow('Denied by policy');
+      expect(client.chatWithTools).not.toHaveBeenCalled();
+
+      const result = await agent.run('Delete the sales series');
+
+      expect(handler).not.toHaveBeenCalled();
+      expect(result.messages[2]).toEqual({
+        role: 'tool',
+        tool_call_id: 'c1',
+        content: 'Error: denied by policy: tool delete_series is not allowed',
+      });
+      expect(policy.auditLog.entries.map((entry) => entry.type)).toEqual(['prompt', 'tool']);
+    });
+
+    test('should wait for approval of tool calls the policy holds', async () => {
+      const client = scriptedClient([
+        { role: 'assistant', content: null, tool_calls: [toolCall('c1', 'refund', { amount: 80 }), toolCall('c2', 'refund', { amount: 500 })] },
+        { role: 'assistant', content: 'Refunded 80.' },
+      ]);
+      const handler = jest.fn(() => 'ok');
+      const tools = new ToolRegistry().register({ name: 'refund', handler });
+      const policy = new PolicyEngine({ rules: { tools: { refund: { approveAbove: { amount: 50 } } } } });
+      const approvals = new ApprovalQueue({
+        notify: (request) => setImmediate(() => (request.action.args.amount < 100
+          ? approvals.approve(request.id, 'ops')
+          : approvals.reject(request.id, 'ops', 'too large'))),
+      });
+      const agent = new Agent({ client, tools, policy, approvals });
+
+      const result = await agent.run('Refund both orders');
+
+      expect(handler).toHaveBeenCalledTimes(1);
+      expect(handler).toHaveBeenCalledWith({ amount: 80 });
+      expect(result.messages[3].content).toBe('Error: not approved: too large');
+    });
+  });
+});
@@ -0,0 +1,83 @@
+const Anonymizer = require('../src/anonymizer');
+const Conversation = require('../src/conversation');
+
+describe('Anonymizer', () => {
+  describe('scrub', () => {
+    test('should replace common PII with placeholders', () => {
+      const anonymizer = new Anonymizer();
+      const text = 'Mail jane.doe@example.com or call +1 555-123-4567 from 192.168.0.12, card 4111 1111 1111 1111, SSN 123-45-6789.';
+
+      expect(anonymizer.scrub(text)).toBe('Mail [EMAIL] or call [PHONE] from [IP], card [CARD], SSN [SSN].');
+    });
+
+    test('should apply custom patterns and leave non-strings alone', () => {
+      const anonymizer = new Anonymizer({ patterns: [{ name: 'ACCOUNT', pattern: /ACCT-\d+/g }] });
+
+      expect(anonymizer.scrub('Account ACCT-00042 is overdue')).toBe('Account [ACCOUNT] is overdue');
+      expect(anonymizer.scrub(null)).toBeNull();
+    });
+  });
+
+  describe('conversations', () => {
+    test('should scrub text and multimodal messages', () => {
+      const scrubbed = new Anonymizer().scrubMessages([
+        { role: 'user', content: 'I am bob@example.com' },
+        { role: 'user', content: [{ type: 'text', text: 'bob@example.com' }, { type: 'image_url', image_url: { url: 'https://x' } }] },
+      ]);
+
+      expect(scrubbed[0].content).toBe('I am [EMAIL]');
+      expect(scrubbed[1].content[0].text).toBe('[EMAIL]');
+      expect(scrubbed[1].content[1].image_url.url).toBe('https://x');
+    });
+
+    test('should export fine-tuning JSON Lines', () => {
+      const conversation = new Conversation({ conversation: jest.fn() }, { systemPrompt: 'Be brief.' });
+      conversation.messages.push({ role: 'user', content: 'Email me at a@b.io' }, { role: 'assistant', content: 'Done.' });
+
+      const lines = new Anonymizer().toFineTuningJSONL([conversation, [{ role: 'user', content: 'hi' }]]).split('\n');
+
+      expect(lines).toHaveLength(2);
+      expect(JSON.parse(lines[0]).messages[1]).toEqual({ role: 'user', content: 'Email me at [EMAIL]' });
+    });
+
+    test('should pseudonymize identifiers stably per salt', () => {
+      const a = new Anonymizer({ salt: 'one' });
+      const b = new Anonymizer({ salt: 'two' });
+
+      expect(a.pseudonymize('user-1')).toBe(a.pseudonymize('user-1'));
+      expect(a.pseudonymize('user-1')).not.toBe(a.pseudonymize('user-2'));
+      expect(a.pseudonymize('user-1')).not.toBe(b.pseudonymize('user-1'));
+    });
+  });
+
+  describe('kAnonymize', () => {
+    test('should suppress records in groups smaller than k', () => {
+      const records = [
+        { age: '30-39', region: 'EU', topic: 'sales' },
+        { age: '30-39', region: 'EU', topic: 'ops' },
+        { age: '60-69', region: 'APAC', topic: 'sales' },
+      ];
+
+      const result = new Anonymizer().kAnonymize(records, ['age', 'region'], 2);
+
+      expect(result.suppressed).toBe(1);
+      expect(result.records.map((r) => r.topic)).toEqual(['sales', 'ops']);
+    });
+  });
+
+  describe('addNoise', () => {
+    test('should add Laplace noise scaled by sensitivity over epsilon', () => {
+      expect(new Anonymizer({ random: () => 0.5 }).addNoise(100)).toBe(100);
+
+      const noisy = new Anonymizer({ random: () => 0.75 }).addNoise(100, { epsilon: 0.5 });
+      expect(noisy).toBeCloseTo(100 + 2 * Math.log(2));
+    });
+
+    test('should resample when random() returns 0', () => {
+      const random = jest.fn().mockReturnValueOnce(0).mockReturnValueOnce(0.75);
+
+      expect(new Anonymizer({ random }).addNoise(100)).toBeCloseTo(100 + Math.log(2));
+      expect(random).toHaveBeenCalledTimes(2);
+    });
+  });
+});
@@ -0,0 +1,45 @@
+const ApprovalQueue = require('../src/approvalQueue');
+
+describe('ApprovalQueue', () => {
+  test('should require a notify function', () => {
+    expect(() => new ApprovalQueue()).toThrow('ApprovalQueue requires a notify(request) function');
+  });
+
+  test('should resolve once an authorized approver approves', async () => {
+    const notify = jest.fn();
+    const approvals = new ApprovalQueue({ approvers: ['alice'], notify });
+
+    const decision = approvals.request({ tool: 'send_payment', args: { amount: 50 } });
+    await Promise.resolve();
+
+    const [request] = approvals.list().items;
+    expect(notify).toHaveBeenCalledWith(request);
+    expect(request.action.tool).toBe('send_payment');
+
+    expect(() => approvals.approve(request.id, 'mallory')).toThrow('mallory is not an authorized approver');
+    approvals.approve(request.id, 'alice');
+
+    expect(await decision).toEqual({ approved: true, approver: 'alice', reason: null });
+    expect(approvals.list().items).toEqual([]);
+    expect(() => approvals.approve(request.id, 'alice')).toThrow(`No pending approval request: ${request.id}`);
+  });
+
+  test('should report rejections with a reason', async () => {
+    const approvals = new ApprovalQueue({ notify: (request) => setImmediate(() => approvals.reject(request.id, 'bob', 'too large')) });
+    expect(await approvals.request({ tool: 'transfer' })).toEqual({ approved: false, approver: 'bob', reason: 'too large' });
+  });
+
+  test('should reject when nobody answers in time', async () => {
+    const approvals = new ApprovalQueue({ notify: jest.fn(), timeout: 10 });
+    expect(await approvals.request({ tool: 'transfer' })).toEqual({ approved: false, approver: null, reason: 'timed out' });
+  });
+
+  test('should reject when the notification cannot be sent', async () => {
+    const approvals = new ApprovalQueue({
+      notify: async () => {
+        throw new Error('slack down');
+      },
+    });
+    expect(await approvals.request({ tool: 'transfer' })).toMatchObject({ approved: false, reason: 'notification failed: slack down' });
+  });
+});
@@ -0,0 +1,109 @@
+const fs = require('fs');
+const os = require('os');
+const path = require('path');
+const AuditLog = require('../src/auditLog');
+const PolicyEngine = require('../src/policyEngine');
+const { MemoryJournal, FileJournal } = require('../src/recorder');
+
+describe('AuditLog', () => {
+  test('should chain entries by hash', async () => {
+    const log = new AuditLog();
+    const first = await log.record({ component: 'openai', action: 'chat.completions', tags: ['gpt-4'] });
+    const second = await log.record({ component: 'github', action: 'createIssue', data: { number: 5 } });
+
+    expect(first.prevHash).toBe('0'.repeat(64));
+    expect(second.prevHash).toBe(first.hash);
+    expect(await log.verify()).toEqual({ valid: true, entries: 2, brokenAt: null });
+  });
+
+  test('should keep order for concurrent records', async () => {
+    const log = new AuditLog();
+    await Promise.all([1, 2, 3].map((n) => log.record({ component: 'tool', action: `call-${n}` })));
+
+    const entries = await log.load();
+    expect(entries.map((entry) => entry.action)).toEqual(['call-1', 'call-2', 'call-3']);
+    expect((await log.verify()).valid).toBe(true);
+  });
+
+  test('should detect tampering', async () => {
+    const journal = new MemoryJournal();
+    const log = new AuditLog({ journal });
+    await log.record({ component: 'payments', action: 'refund', data: { amount: 10 } });
+    await log.record({ component: 'payments', action: 'refund', data: { amount: 20 } });
+    await log.record({ component: 'payments', action: 'refund', data: { amount: 30 } });
+
+    journal.entries[1].data.amount = 2000;
+    expect(await log.verify()).toEqual({ valid: false, entries: 3, brokenAt: 1 });
+
+    journal.entries[1].data.amount = 20;
+    journal.entries.splice(1, 1);
+    expect((await log.verify()).brokenAt).toBe(1);
+  });
+
+  test('should query by component, action, tag and time range', async () => {
+    const log = new AuditLog();
+    await log.record({ component: 'openai', action: 'chat.completions', tags: ['gpt-4'] });
+    await log.record({ component: 'github', action: 'comment', tags: ['repo:octo'] });
+    await log.record({ component: 'openai', action: 'moderations', tags: ['gpt-4'] });
+
+    expect((await log.query({ component: 'openai' })).items).toHaveLength(2);
+    expect((await log.query({ action: 'comment' })).items).toHaveLength(1);
+    expect((await log.query({ tag: 'repo:octo' })).items).toHaveLength(1);
+    expect((await log.query({ from: '2000-01-01', to: new Date(Date.now() + 1000) })).items).toHaveLength(3);
+    expect((await log.query({ to: '2000-01-01' })).items).toHaveLength(0);
+  });
+
+  test('should page through query results', async () => {
+    const log = new AuditLog();
+    for (const action of ['a', 'b', 'c']) {
+      await log.record({ component: 'openai', action });
+    }
+
+    const first = await log.query({ component: 'openai', limit: 2 });
+    const second = await log.query({ component: 'openai', limit: 2, cursor: first.nextCursor });
+
+    expect(first.items.map((entry) => entry.action)).toEqual(['a', 'b']);
+    expect(second.items.map((entry) => entry.action)).toEqual(['c']);
+    expect(second.nextCursor).toBeNull();
+  });
+
+  test('should continue the chain from an existing file', async () => {
+    const file = path.join(fs.mkdtempSync(path.join(os.tmpdir(), 'audit-')), 'audit.jsonl');
+    await new AuditLog({ journal: new FileJournal(file) }).record({ component: 'a', action: 'one' });
+
+    const reopened = new AuditLog({ journal: new FileJournal(file) });
+    await reopened.record({ component: 'a', action: 'two' });
+
+    expect(await reopened.verify()).toEqual({ valid: true, entries: 2, brokenAt: null });
+  });
+
+  test('should verify entries read back from a file journal', async () => {
+    const file = path.join(fs.mkdtempSync(path.join(os.tmpdir(), 'audit-')), 'audit.jsonl');
+    const log = new AuditLog({ journal: new FileJournal(file) });
+
+    const entry = await log.record({ component: 'app', action: 'export', data: { at: new Date(0), items: [1, undefined], skipped: undefined } });
+
+    expect(entry.data).toEqual({ at: '1970-01-01T00:00:00.000Z', items: [1, null] });
+    expect(await new AuditLog({ journal: new FileJournal(file) }).verify()).toEqual({ valid: true, entries: 1, brokenAt: null });
+  });
+
+  test('should record client calls as an observer', async () => {
+    const log = new AuditLog();
+    log.onResponse({ provider: 'anthropic', operation: 'messages', model: 'claude', latencyMs: 12, usage: { prompt_tokens: 3 } });
+    log.onError({ provider: 'xai', operation: 'chat', model: 'grok', latencyMs: 5, error: new Error('503') });
+
+    const entries = await log.load();
+    expect(entries[0]).toMatchObject({ component: 'anthropic', action: 'messages', tags: ['claude'], data: { status: 'ok', latencyMs: 12 } });
+    expect(entries[1].data).toMatchObject({ status: 'error', error: '503' });
+  });
+
+  test('should serve as the audit journal of a policy engine', async () => {
+    const log = new AuditLog({ component: 'policy' });
+    const policy = new PolicyEngine({ rules: { deniedTools: ['delete'] }, auditLog: log });
+
+    await policy.checkToolCall('delete');
+
+    const { items: [entry] } = await log.query({ component: 'policy' });
+    expect(entry).toMatchObject({ action: 'tool', data: { tool: 'delete', rule: 'tool' } });
+  });
+});
@@ -0,0 +1,123 @@
+const BatchJob = require('../src/batchJob');
+const ProgressHandle = require('../src/progressHandle');
+const { MemoryJournal } = require('../src/recorder');
+
+describe('BatchJob', () => {
+  const requests = [
+    { id: 'a', message: 'one' },
+    { id: 'b', message: 'two' },
+    { id: 'c', messages: [{ role: 'user', content: 'three' }], options: { temperature: 0 } },
+  ];
+
+  const echoClient = () => ({
+    conversation: jest.fn(async (messages) => `re: ${messages[messages.length - 1].content}`),
+  });
+
+  test('should validate the client and request ids', () => {
+    expect(() => new BatchJob(requests, {})).toThrow('requires a client');
+    expect(() => new BatchJob([{ id: 'a' }, { id: 'a' }], { client: echoClient() })).toThrow('must be unique');
+  });
+
+  test('should run every request and journal the outcomes', async () => {
+    const client = echoClient();
+    client.conversation.mockRejectedValueOnce(new Error('Grok API Error: 500 - down'));
+    const journal = new MemoryJournal();
+    const progress = new ProgressHandle();
+
+    const summary = await new BatchJob(requests, { client, journal, concurrency: 1 }).run({ progress });
+
+    expect(summary.completed).toBe(2);
+    expect(summary.failed).toBe(1);
+    expect(summary.results).toEqual([
+      { id: 'a', status: 'failed', error: 'Grok API Error: 500 - down' },
+      { id: 'b', status: 'completed', content: 're: two' },
+      { id: 'c', status: 'completed', content: 're: three' },
+    ]);
+    expect(client.conversation).toHaveBeenCalledWith([{ role: 'user', content: 'three' }], { temperature: 0 });
+    expect(await journal.load()).toHaveLength(3);
+    expect(progress.toJSON()).toMatchObject({ status: 'completed', completed: 3, total: 3 });
+  });
+
+  test('should resume from the journal and retry only failures', async () => {
+    const journal = new MemoryJournal([
+      { type: 'result', id: 'a', content: 'earlier' },
+      { type: 'error', id: 'b', error: 'timed out' },
+    ]);
+    const client = echoClient();
+
+    const summary = await new BatchJob(requests, { client, journal }).run();
+
+    expect(client.conversation).toHaveBeenCalledTimes(2);
+    expect(summary.results.map((result) => result.content)).toEqual(['earlier', 're: two', 're: three']);
+  });
+
+  test('should go through the rate limiter', async () => {
+    const rateLimiter = { schedule: jest.fn((task) => task()) };
+
+    await new BatchJob(requests, { client: echoClient(), rateLimiter }).run();
+
+    expect(rateLimiter.schedule).toHaveBeenCalledTimes(3);
+  });
+
+  test('should stop starting requests when cancelled', async () => {
+    const progress = new ProgressHandle();
+    const client = {
+      conversation: jest.fn(async () => {
+        progress.cancel('user stopped');
+        return 'ok';
+      }),
+    };
+
+    await expect(new BatchJob(requests, { client, concurrency: 1 }).run({ progress })).rejects.toThrow('Operation cancelled: user stopped');
+    expect(client.conversation).toHaveBeenCalledTimes(1);
+  });
+
+  describe('native batches', () => {
+    const nativeClient = (statuses) => ({
+      conversation: jest.fn(),
+      submitBatch: jest.fn().mockResolvedValue({ id: 'batch-1', status: 'validating' }),
+      getBatch: jest.fn(async () => statuses.shift()),
+      getBatchResults: jest.fn().mockResolvedValue([
+        { id: 'a', content: 'A' },
+        { id: 'b', error: 'expired' },
+      ]),
+    });
+
+    test('should submit pending requests and poll until the batch is done', async () => {
+      const client = nativeClient([{ status: 'in_progress', done: false }, { status: 'completed', done: true }]);
+      const sleep = jest.fn().mockResolvedValue();
+      const journal = new MemoryJournal();
+
+      const summary = await new BatchJob(requests, { client, journal, native: true, sleep, pollInterval: 5 }).run();
+
+      expect(client.conversation).not.toHaveBeenCalled();
+      expect(client.submitBatch.mock.calls[0][0].map((request) => request.id)).toEqual(['a', 'b', 'c']);
+      expect(sleep).toHaveBeenCalledWith(5);
+      expect(summary.results).toEqual([
+        { id: 'a', status: 'completed', content: 'A' },
+        { id: 'b', status: 'failed', error: 'expired' },
+        { id: 'c', status: 'failed', error: 'batch completed without a result' },
+      ]);
+      expect((await journal.load()).map((entry) => entry.type)).toEqual(['batch', 'result', 'error', 'error', 'batch_collected']);
+    });
+
+    test('should collect an already submitted batch on resume', async () => {
+      const client = nativeClient([{ status: 'completed', done: true }]);
+      const journal = new MemoryJournal([{ type: 'batch', batchId: 'batch-0', ids: ['a', 'b'] }]);
+
+      const summary = await new BatchJob(requests.slice(0, 2), { client, journal, native: true }).run();
+
+      expect(client.submitBatch).not.toHaveBeenCalled();
+      expect(client.getBatchResults).toHaveBeenCalledWith('batch-0');
+      expect(summary.completed).toBe(1);
+    });
+
+    test('should fall back to scheduled requests without batch support', async () => {
+      const client = echoClient();
+
+      const summary = await new BatchJob(requests, { client, native: true }).run();
+
+      expect(summary.completed).toBe(3);
+    });
+  });
+});
@@ -0,0 +1,88 @@
+const BillingMeter = require('../src/billingMeter');
+const UsageTracker = require('../src/usageTracker');
+
+describe('BillingMeter', () => {
+  test('should require a sink', () => {
+    expect(() => new BillingMeter()).toThrow('BillingMeter requires a sink function');
+  });
+
+  describe('meter', () => {
+    test('should build Stripe-style meter events', async () => {
+      const meter = new BillingMeter({ sink: jest.fn() });
+      const event = await meter.meter('cus_123', 'storage_bytes', 2048, new Date('2024-06-01T00:00:00Z'));
+
+      expect(event).toEqual({
+        event_name: 'storage_bytes',
+        identifier: expect.any(String),
+        timestamp: 1717200000,
+        payload: { stripe_customer_id: 'cus_123', value: '2048' },
+      });
+      expect(meter.pending).toBe(1);
+    });
+
+    test('should reject events without a tenant or with invalid values', async () => {
+      const meter = new BillingMeter({ sink: jest.fn() });
+      await expect(meter.meter(null, 'tx_count', 1)).rejects.toThrow('require a tenant');
+      await expect(meter.meter('cus_1', 'tx_count', -1)).rejects.toThrow('Invalid billing value for tx_count');
+    });
+
+    test('should flush automatically when the batch is full', async () => {
+      const sink = jest.fn().mockResolvedValue();
+      const meter = new BillingMeter({ sink, batchSize: 2, customerField: 'customer' });
+
+      await meter.meter('acme', 'tx_count', 1);
+      expect(sink).not.toHaveBeenCalled();
+      await meter.meter('acme', 'tx_count', 1);
+
+      expect(sink).toHaveBeenCalledTimes(1);
+      expect(sink.mock.calls[0][0]).toHaveLength(2);
+      expect(sink.mock.calls[0][0][0].payload.customer).toBe('acme');
+      expect(meter.pending).toBe(0);
+    });
+  });
+
+  describe('flush', () => {
+    test('should keep events buffered when the sink fails', async () => {
+      const sink = jest.fn().mockRejectedValueOnce(new Error('Stripe unavailable')).mockResolvedValue();
+      const meter = new BillingMeter({ sink });
+      await meter.meter('cus_1', 'tx_count', 3);
+
+      await expect(meter.flush()).rejects.toThrow('Stripe unavailable');
+      expect(meter.pending).toBe(1);
+
+      expect(await meter.flush()).toBe(1);
+      expect(await meter.flush()).toBe(0);
+    });
+  });
+
+  describe('attach', () => {
+    test('should meter token usage per tenant', async () => {
+      const sink = jest.fn().mockResolvedValue();
+      const meter = new BillingMeter({ sink });
+      const tracker = new UsageTracker();
+      meter.attach(tracker);
+
+      tracker.withTenant('cus_a').record('gpt-4', { prompt_tokens: 100, completion_tokens: 50 });
+      tracker.withTag('chat').withTenant('cus_b').record('gpt-4', { prompt_tokens: 10, completion_tokens: 0 });
+      tracker.record('gpt-4', { prompt_tokens: 999, completion_tokens: 0 });
+      await meter.flush();
+
+      const events = sink.mock.calls[0][0];
+      expect(events.map((e) => [e.event_name, e.payload.stripe_customer_id, e.payload.value])).toEqual([
+        ['ai_tokens', 'cus_a', '150'],
+        ['ai_tokens', 'cus_b', '10'],
+      ]);
+    });
+
+    test('should stop metering once detached', () => {
+      const meter = new BillingMeter({ sink: jest.fn() });
+      const tracker = new UsageTracker();
+      const detach = meter.attach(tracker);
+
+      detach();
+      tracker.withTenant('cus_a').record('gpt-4', { prompt_tokens: 1, completion_tokens: 1 });
+
+      expect(meter.pending).toBe(0);
+    });
+  });
+});
@@ -0,0 +1,206 @@
+const { calendarTools, freeSlots } = require('../src/calendarTools');
+
+jest.mock('https');
+
+function mockResponses(...responses) {
+  const https = require('https');
+  const requests = [];
+
+  https.request = jest.fn((options, cb) => {
+    const [statusCode, body] = responses.shift();
+    const request = { options, body: null };
+    requests.push(request);
+
+    cb({
+      statusCode,
+      on: jest.fn((event, handler) => {
+        if (event === 'data') handler(JSON.stringify(body));
+        if (event === 'end') handler();
+      }),
+    });
+
+    return {
+      write: jest.fn((data) => { request.body = data; }),
+      end: jest.fn(),
+      on: jest.fn(),
+    };
+  });
+
+  return requests;
+}
+
+const byName = (tools) => Object.fromEntries(tools.map((tool) => [tool.name, tool]));
+
+describe('freeSlots', () => {
+  test('should return the gaps between merged busy intervals', () => {
+    const busy = [
+      { start: '2026-03-02T10:30:00Z', end: '2026-03-02T11:00:00Z' },
+      { start: '2026-03-02T09:00:00Z', end: '2026-03-02T10:00:00Z' },
+      { start: '2026-03-02T09:30:00Z', end: '2026-03-02T10:15:00Z' },
+    ];
+
+    expect(freeSlots(busy, '2026-03-02T08:00:00Z', '2026-03-02T12:00:00Z', 30)).toEqual([
+      { start: '2026-03-02T08:00:00.000Z', end: '2026-03-02T09:00:00.000Z' },
+      { start: '2026-03-02T11:00:00.000Z', end: '2026-03-02T12:00:00.000Z' },
+    ]);
+  });
+});
+
+describe('calendarTools', () => {
+  test('should require a known provider and OAuth credentials', () => {
+    expect(() => calendarTools({ provider: 'ical', accessToken: 't' })).toThrow('Unsupported calendar provider: ical');
+    expect(() => calendarTools({ provider: 'google', oauth: { clientId: 'id' } })).toThrow('requires an accessToken or oauth');
+    expect(calendarTools({ provider: 'google', accessToken: 't', readOnly: true }).map((tool) => tool.name))
+      .toEqual(['list_events', 'find_free_slots']);
+  });
+
+  describe('google', () => {
+    test('should refresh the access token and reuse it', async () => {
+      const requests = mockResponses(
+        [200, { access_token: 'fresh', expires_in: 3600 }],
+        [200, { items: [] }],
+        [200, { items: [] }]
+      );
+      const tools = byName(calendarTools({
+        provider: 'google',
+        oauth: { clientId: 'id', clientSecret: 'secret', refreshToken: 'refresh' },
+      }));
+
+      await tools.list_events.handler({ start: '2026-03-02T00:00:00Z', end: '2026-03-03T00:00:00Z' });
+      await tools.list_events.handler({ start: '2026-03-02T00:00:00Z', end: '2026-03-03T00:00:00Z' });
+
+      expect(requests).toHaveLength(3);
+      expect(requests[0].options.hostname).toBe('oauth2.googleapis.com');
+      expect(requests[0].body).toBe('grant_type=refresh_token&client_id=id&client_secret=secret&refresh_token=refresh');
+      expect(requests[1].options.headers.Authorization).toBe('Bearer fresh');
+      expect(requests[2].options.headers.Authorization).toBe('Bearer fresh');
+    });
+
+    test('should list events in a time range', async () => {
+      const requests = mockResponses([200, {
+        items: [{
+          id: 'e1',
+          summary: 'Standup',
+          start: { dateTime: '2026-03-02T09:00:00Z' },
+          end: { dateTime: '2026-03-02T09:15:00Z' },
+          attendees: [{ email: 'ana@example.com' }],
+        }],
+      }]);
+      const tools = byName(calendarTools({ provider: 'google', accessToken: 'token', calendarId: 'team@example.com' }));
+
+      const events = await tools.list_events.handler({ start: '2026-03-02T00:00:00Z', end: '2026-03-03T00:00:00Z', limit: 500 });
+
+      expect(events).toEqual([{
+        id: 'e1',
+        title: 'Standup',
+        start: '2026-03-02T09:00:00Z',
+        end: '2026-03-02T09:15:00Z',
+        location: null,
+        attendees: ['ana@example.com'],
+      }]);
+      const url = new URL(`https://host${requests[0].options.path}`);
+      expect(url.pathname).toBe('/calendar/v3/calendars/team%40example.com/events');
+      expect(url.searchParams.get('timeMin')).toBe('2026-03-02T00:00:00.000Z');
+      expect(url.searchParams.get('maxResults')).toBe('50');
+      expect(requests[0].options.headers.Authorization).toBe('Bearer token');
+    });
+
+    test('should find free slots from the free/busy query', async () => {
+      const requests = mockResponses([200, {
+        calendars: { primary: { busy: [{ start: '2026-03-02T09:00:00Z', end: '2026-03-02T11:00:00Z' }] } },
+      }]);
+      const tools = byName(calendarTools({ provider: 'google', accessToken: 'token' }));
+
+      const slots = await tools.find_free_slots.handler({
+        start: '2026-03-02T08:00:00Z',
+        end: '2026-03-02T12:00:00Z',
+        durationMinutes: 60,
+      });
+
+      expect(slots).toEqual([
+        { start: '2026-03-02T08:00:00.000Z', end: '2026-03-02T09:00:00.000Z' },
+        { start: '2026-03-02T11:00:00.000Z', end: '2026-03-02T12:00:00.000Z' },
+      ]);
+      expect(requests[0].options.path).toBe('/calendar/v3/freeBusy');
+      expect(JSON.parse(requests[0].body).items).toEqual([{ id: 'primary' }]);
+    });
+
+    test('should reject bad time ranges before calling the API', async () => {
+      const requests = mockResponses();
+      const tools = byName(calendarTools({ provider: 'google', accessToken: 'token' }));
+
+      await expect(tools.create_event.handler({ title: 'Sync', start: '2026-03-02T10:00:00Z', end: '2026-03-02T09:00:00Z' }))
+        .rejects.toThrow('End time must be after start time');
+      await expect(tools.list_events.handler({ start: 'tomorrow', end: '2026-03-02T09:00:00Z' }))
+        .rejects.toThrow('Invalid time range');
+      expect(requests).toHaveLength(0);
+    });
+  });
+
+  describe('outlook', () => {
+    test('should create events through Microsoft Graph in UTC', async () => {
+      const requests = mockResponses(
+        [200, { access_token: 'graph-token', expires_in: 3600 }],
+        [201, {
+          id: 'o1',
+          subject: 'Planning',
+          start: { dateTime: '2026-03-02T14:00:00.0000000', timeZone: 'UTC' },
+          end: { dateTime: '2026-03-02T15:00:00.0000000', timeZone: 'UTC' },
+          location: { displayName: 'Room 4' },
+          attendees: [{ emailAddress: { address: 'ana@example.com' } }],
+        }]
+      );
+      const tools = byName(calendarTools({
+        provider: 'outlook',
+        oauth: { clientId: 'id', clientSecret: 'secret', refreshToken: 'refresh', tenant: 'contoso' },
+      }));
+
+      const event = await tools.create_event.handler({
+        title: 'Planning',
+        start: '2026-03-02T15:00:00+01:00',
+        end: '2026-03-02T16:00:00+01:00',
+        location: 'Room 4',
+        attendees: ['ana@example.com'],
+      });
+
+      expect(event).toEqual({
+        id: 'o1',
+        title: 'Planning',
+        start: '2026-03-02T14:00:00.000Z',
+        end: '2026-03-02T15:00:00.000Z',
+        location: 'Room 4',
+        attendees: ['ana@example.com'],
+      });
+      expect(requests[0].options.path).toBe('/contoso/oauth2/v2.0/token');
+      expect(requests[1].options.path).toBe('/v1.0/me/calendar/events');
+      expect(JSON.parse(requests[1].body).start).toEqual({ dateTime: '2026-03-02T14:00:00.000', timeZone: 'UTC' });
+      expect(requests[1].options.headers.Prefer).toBe('outlook.timezone="UTC"');
+    });
+
+    test('should treat events shown as free as free time', async () => {
+      mockResponses([200, {
+        value: [
+          { id: 'a', showAs: 'busy', start: { dateTime: '2026-03-02T09:00:00' }, end: { dateTime: '2026-03-02T10:00:00' } },
+          { id: 'b', showAs: 'free', start: { dateTime: '2026-03-02T10:00:00' }, end: { dateTime: '2026-03-02T12:00:00' } },
+        ],
+      }]);
+      const tools = byName(calendarTools({ provider: 'outlook', accessToken: 'token' }));
+
+      const slots = await tools.find_free_slots.handler({
+        start: '2026-03-02T09:00:00Z',
+        end: '2026-03-02T12:00:00Z',
+        durationMinutes: 90,
+      });
+
+      expect(slots).toEqual([{ start: '2026-03-02T10:00:00.000Z', end: '2026-03-02T12:00:00.000Z' }]);
+    });
+
+    test('should reject with the API error and status', async () => {
+      mockResponses([403, { error: { code: 'ErrorAccessDenied', message: 'Access is denied.' } }]);
+      const tools = byName(calendarTools({ provider: 'outlook', accessToken: 'token' }));
+
+      await expect(tools.list_events.handler({ start: '2026-03-02T00:00:00Z', end: '2026-03-03T00:00:00Z' }))
+        .rejects.toThrow('Calendar API Error: 403 - Access is denied.');
+    });
+  });
+});
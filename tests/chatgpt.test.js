@@ -1,4 +1,5 @@
 const ChatGPT = require('../src/chatgpt');
+const RateLimiter = require('../src/rateLimiter');
 
 // Mock the OpenAI module
 jest.mock('openai');
@@ -35,6 +36,12 @@ describe('ChatGPT', () => {
       const chatgpt = new ChatGPT('custom-key');
       expect(chatgpt).toBeDefined();
     });
+
+    test('should accept a rate limiter option', () => {
+      const limiter = new RateLimiter({ maxConcurrent: 1 });
+      const chatgpt = new ChatGPT('custom-key', { rateLimiter: limiter });
+      expect(chatgpt.rateLimiter).toBe(limiter);
+    });
   });
 
   describe('setModel', () => {
@@ -112,4 +119,261 @@ describe('ChatGPT', () => {
       expect(response).toBe('Conversation response');
     });
   });
+
+  describe('images', () => {
+    test('should generate images and return artifacts', async () => {
+      const OpenAI = require('openai');
+      const generate = jest.fn().mockResolvedValue({
+        data: [{ url: 'https://images.example/1.png', revised_prompt: 'A clock tower at dusk' }],
+      });
+      OpenAI.mockImplementation(() => ({ images: { generate } }));
+
+      const chatgpt = new ChatGPT('test-key');
+      const images = await chatgpt.generateImage('A clock tower', { size: '1792x1024' });
+
+      expect(generate).toHaveBeenCalledWith({ model: 'dall-e-3', prompt: 'A clock tower', n: 1, size: '1792x1024' });
+      expect(images).toEqual([{
+        url: 'https://images.example/1.png',
+        b64: null,
+        mimeType: 'image/png',
+        revisedPrompt: 'A clock tower at dusk',
+      }]);
+    });
+
+    test('should send images as multimodal content', async () => {
+      const OpenAI = require('openai');
+      const mockCreate = jest.fn().mockResolvedValue({ choices: [{ message: { content: 'A chart' } }] });
+      OpenAI.mockImplementation(() => ({ chat: { completions: { create: mockCreate } } }));
+
+      const chatgpt = new ChatGPT('test-key');
+      const response = await chatgpt.describeImages('What is this?', ['https://images.example/chart.png'], { detail: 'low' });
+
+      expect(response).toBe('A chart');
+      expect(mockCreate.mock.calls[0][0].model).toBe('gpt-4o');
+      expect(mockCreate.mock.calls[0][0].messages[0].content).toEqual([
+        { type: 'text', text: 'What is this?' },
+        { type: 'image_url', image_url: { url: 'https://images.example/chart.png', detail: 'low' } },
+      ]);
+    });
+  });
+
+  describe('audio', () => {
+    test('should transcribe audio with Whisper', async () => {
+      const OpenAI = require('openai');
+      const create = jest.fn().mockResolvedValue({ text: 'Forecast looks good.' });
+      OpenAI.mockImplementation(() => ({ audio: { transcriptions: { create } } }));
+
+      const audio = { path: 'call.mp3' };
+      const text = await new ChatGPT('test-key').transcribe(audio, { language: 'en' });
+
+      expect(text).toBe('Forecast looks good.');
+      expect(create).toHaveBeenCalledWith({ model: 'whisper-1', language: 'en', file: audio });
+    });
+
+    test('should synthesize speech as bytes or a stream', async () => {
+      const OpenAI = require('openai');
+      const body = { pipe: jest.fn() };
+      const create = jest.fn().mockResolvedValue({
+        body,
+        arrayBuffer: async () => new Uint8Array([1, 2, 3]).buffer,
+      });
+      OpenAI.mockImplementation(() => ({ audio: { speech: { create } } }));
+      const chatgpt = new ChatGPT('test-key');
+
+      const audio = await chatgpt.synthesizeSpeech('Hello', { voice: 'nova' });
+      expect(Buffer.isBuffer(audio)).toBe(true);
+      expect(Array.from(audio)).toEqual([1, 2, 3]);
+      expect(create).toHaveBeenCalledWith({ model: 'tts-1', voice: 'nova', input: 'Hello' });
+
+      expect(await chatgpt.synthesizeSpeech('Hello', { stream: true })).toBe(body);
+    });
+  });
+
+  describe('embed', () => {
+    test('should return one vector per input and record usage', async () => {
+      const OpenAI = require('openai');
+      const UsageTracker = require('../src/usageTracker');
+      const create = jest.fn().mockResolvedValue({
+        data: [{ embedding: [0.1, 0.2] }, { embedding: [0.3, 0.4] }],
+        usage: { prompt_tokens: 8, total_tokens: 8 },
+      });
+      OpenAI.mockImplementation(() => ({ embeddings: { create } }));
+      const usageTracker = new UsageTracker();
+      const chatgpt = new ChatGPT('test-key', { usageTracker });
+
+      expect(await chatgpt.embed(['a', 'b'])).toEqual([[0.1, 0.2], [0.3, 0.4]]);
+      expect(await chatgpt.embed('a', { dimensions: 2 })).toEqual([0.1, 0.2]);
+      expect(create).toHaveBeenCalledWith({ model: 'text-embedding-3-small', dimensions: 2, input: 'a' });
+      expect(usageTracker.getTotals().promptTokens).toBe(16);
+    });
+
+    test('should serve repeated inputs from the cache', async () => {
+      const OpenAI = require('openai');
+      const ResponseCache = require('../src/responseCache');
+      const create = jest.fn().mockResolvedValue({ data: [{ embedding: [0.1, 0.2] }], usage: { prompt_tokens: 4 } });
+      OpenAI.mockImplementation(() => ({ embeddings: { create } }));
+      const chatgpt = new ChatGPT('test-key', { cache: new ResponseCache() });
+
+      expect(await chatgpt.embed('a')).toEqual([0.1, 0.2]);
+      expect(await chatgpt.embed('a')).toEqual([0.1, 0.2]);
+      expect(create).toHaveBeenCalledTimes(1);
+
+      await chatgpt.embed('a', { cache: false });
+      expect(create).toHaveBeenCalledTimes(2);
+      expect(create.mock.calls[1][0]).toEqual({ model: 'text-embedding-3-small', input: 'a' });
+    });
+
+    test('should refuse to embed once the budget is spent', async () => {
+      const OpenAI = require('openai');
+      const UsageTracker = require('../src/usageTracker');
+      const create = jest.fn();
+      OpenAI.mockImplementation(() => ({ embeddings: { create } }));
+      const usageTracker = new UsageTracker({ maxSpend: 0 });
+      const chatgpt = new ChatGPT('test-key', { usageTracker });
+
+      await expect(chatgpt.embed('a')).rejects.toThrow('Usage budget exceeded');
+      expect(create).not.toHaveBeenCalled();
+    });
+  });
+
+  describe('batch', () => {
+    test('should upload requests and create a batch', async () => {
+      const OpenAI = require('openai');
+      OpenAI.toFile = jest.fn(async (data, name) => ({ data, name }));
+      const files = { create: jest.fn().mockResolvedValue({ id: 'file-1' }) };
+      const batches = { create: jest.fn().mockResolvedValue({ id: 'batch-1', status: 'validating' }) };
+      OpenAI.mockImplementation(() => ({ files, batches }));
+
+      const batch = await new ChatGPT('test-key').submitBatch([
+        { id: 'a', messages: [{ role: 'user', content: 'Hi' }], options: { temperature: 0 } },
+      ]);
+
+      expect(batch).toEqual({ id: 'batch-1', status: 'validating' });
+      const [data] = OpenAI.toFile.mock.calls[0];
+      expect(JSON.parse(data.toString())).toEqual({
+        custom_id: 'a',
+        method: 'POST',
+        url: '/v1/chat/completions',
+        body: { model: 'gpt-4', temperature: 0, messages: [{ role: 'user', content: 'Hi' }] },
+      });
+      expect(files.create).toHaveBeenCalledWith({ file: expect.any(Object), purpose: 'batch' });
+      expect(batches.create).toHaveBeenCalledWith({ input_file_id: 'file-1', endpoint: '/v1/chat/completions', completion_window: '24h' });
+    });
+
+    test('should read results and errors of a finished batch', async () => {
+      const OpenAI = require('openai');
+      const output = [
+        { custom_id: 'a', response: { status_code: 200, body: { model: 'gpt-4', usage: { total_tokens: 3 }, choices: [{ message: { content: 'Hello' } }] } } },
+        { custom_id: 'b', response: { status_code: 400, body: { error: 'bad' } } },
+      ].map((line) => JSON.stringify(line)).join('\n');
+      const errors = JSON.stringify({ custom_id: 'c', response: null, error: { message: 'expired' } });
+      const batches = {
+        retrieve: jest.fn().mockResolvedValue({ id: 'batch-1', status: 'completed', output_file_id: 'out', error_file_id: 'err', request_counts: { total: 3 } }),
+      };
+      const files = { content: jest.fn(async (id) => ({ text: async () => (id === 'out' ? output : errors) })) };
+      OpenAI.mockImplementation(() => ({ files, batches }));
+      const chatgpt = new ChatGPT('test-key');
+
+      expect(await chatgpt.getBatch('batch-1')).toEqual({ id: 'batch-1', status: 'completed', done: true, requestCounts: { total: 3 } });
+      expect(await chatgpt.getBatchResults('batch-1')).toEqual([
+        { id: 'a', content: 'Hello' },
+        { id: 'b', error: '400 - {"error":"bad"}' },
+        { id: 'c', error: 'expired' },
+      ]);
+    });
+  });
+
+  describe('moderate', () => {
+    test('should return a verdict with the flagged categories', async () => {
+      const OpenAI = require('openai');
+      const create = jest.fn().mockResolvedValue({
+        results: [{
+          flagged: true,
+          categories: { harassment: true, violence: false },
+          category_scores: { harassment: 0.91, violence: 0.02 },
+        }],
+      });
+      OpenAI.mockImplementation(() => ({ moderations: { create } }));
+
+      const verdict = await new ChatGPT('test-key').moderate('some text');
+
+      expect(create).toHaveBeenCalledWith({ input: 'some text' });
+      expect(verdict).toEqual({
+        flagged: true,
+        categories: ['harassment'],
+        scores: { harassment: 0.91, violence: 0.02 },
+      });
+    });
+  });
+
+  describe('Azure OpenAI', () => {
+    const azureEnv = ['AZURE_OPENAI_API_KEY', 'AZURE_OPENAI_ENDPOINT', 'AZURE_OPENAI_DEPLOYMENT', 'AZURE_OPENAI_API_VERSION'];
+    let savedAzureEnv;
+
+    beforeEach(() => {
+      savedAzureEnv = azureEnv.map((name) => process.env[name]);
+      azureEnv.forEach((name) => delete process.env[name]);
+    });
+
+    afterEach(() => {
+      azureEnv.forEach((name, i) => {
+        if (savedAzureEnv[i] === undefined) {
+          delete process.env[name];
+        } else {
+          process.env[name] = savedAzureEnv[i];
+        }
+      });
+    });
+
+    test('should require an endpoint, deployment and key', () => {
+      expect(() => new ChatGPT('key', { azure: true })).toThrow('Azure OpenAI requires an endpoint and a deployment');
+
+      process.env.OPENAI_API_KEY = 'openai-key';
+      expect(() => new ChatGPT(null, { azure: { endpoint: 'https://res.openai.azure.com', deployment: 'gpt4' } }))
+        .toThrow('Azure OpenAI API key is required');
+    });
+
+    test('should point the client at the deployment with api-version and api-key', () => {
+      const OpenAI = require('openai');
+      OpenAI.mockImplementation(() => ({}));
+      process.env.AZURE_OPENAI_API_KEY = 'azure-key';
+      process.env.AZURE_OPENAI_ENDPOINT = 'https://res.openai.azure.com/';
+      process.env.AZURE_OPENAI_DEPLOYMENT = 'gpt4-prod';
+
+      const chatgpt = new ChatGPT(null, { azure: true });
+
+      expect(chatgpt.provider).toBe('azure-openai');
+      expect(OpenAI).toHaveBeenCalledWith({
+        apiKey: 'azure-key',
+        baseURL: 'https://res.openai.azure.com/openai/deployments/gpt4-prod',
+        defaultQuery: { 'api-version': '2024-02-01' },
+        defaultHeaders: { 'api-key': 'azure-key' },
+      });
+    });
+
+    test('should route models to their deployments', async () => {
+      const OpenAI = require('openai');
+      const creates = {};
+      OpenAI.mockImplementation(({ baseURL }) => {
+        const deployment = baseURL.split('/').pop();
+        creates[deployment] = jest.fn().mockResolvedValue({ choices: [{ message: { content: deployment } }] });
+        return { chat: { completions: { create: creates[deployment] } } };
+      });
+
+      const chatgpt = new ChatGPT('azure-key', {
+        azure: {
+          endpoint: 'https://res.openai.azure.com',
+          deployment: 'gpt4-prod',
+          apiVersion: '2024-06-01',
+          deployments: { 'gpt-3.5-turbo': 'chat35' },
+        },
+      });
+
+      expect(await chatgpt.chat('Hi')).toBe('gpt4-prod');
+      expect(await chatgpt.chat('Hi', { model: 'gpt-3.5-turbo' })).toBe('chat35');
+      expect(await chatgpt.chat('Hi', { model: 'gpt-3.5-turbo' })).toBe('chat35');
+      expect(OpenAI).toHaveBeenCalledTimes(2);
+      expect(creates.chat35).toHaveBeenCalledTimes(2);
+    });
+  });
 });
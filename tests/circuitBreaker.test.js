@@ -0,0 +1,150 @@
+const CircuitBreaker = require('../src/circuitBreaker');
+const FailoverProvider = require('../src/failoverProvider');
+const { MockAIProvider } = require('../src/testing');
+
+describe('CircuitBreaker', () => {
+  let time;
+  const now = () => time;
+  const fail = () => Promise.reject(new Error('Claude API Error: 503 - overloaded'));
+  const succeed = () => Promise.resolve('ok');
+
+  beforeEach(() => {
+    time = 1000;
+  });
+
+  const trip = async (breaker, count) => {
+    for (let i = 0; i < count; i++) {
+      await breaker.execute(fail).catch(() => {});
+    }
+  };
+
+  test('should open once the failure rate reaches the threshold', async () => {
+    const breaker = new CircuitBreaker({ name: 'claude', minimumRequests: 4, failureThreshold: 0.5, now });
+
+    await breaker.execute(succeed);
+    await breaker.execute(succeed);
+    await trip(breaker, 1);
+    expect(breaker.state).toBe('closed');
+
+    await trip(breaker, 1);
+    expect(breaker.state).toBe('open');
+    await expect(breaker.execute(succeed)).rejects.toThrow('Circuit open for claude: retry in 30s');
+    expect(breaker.getHealth()).toMatchObject({ name: 'claude', state: 'open', failureRate: 0.5, requests: 4, rejected: 1 });
+  });
+
+  test('should not count client errors as failures', async () => {
+    const breaker = new CircuitBreaker({ minimumRequests: 2, now });
+
+    for (let i = 0; i < 5; i++) {
+      await breaker.execute(() => Promise.reject(new Error('Claude API Error: 400 - bad request'))).catch(() => {});
+    }
+
+    expect(breaker.state).toBe('closed');
+  });
+
+  test('should leave errors that do not count out of the failure rate', async () => {
+    const breaker = new CircuitBreaker({ minimumRequests: 2, failureThreshold: 0.6, now });
+
+    await breaker.execute(() => Promise.reject(new Error('Claude API Error: 400 - bad request'))).catch(() => {});
+    await breaker.execute(() => Promise.reject(new Error('Claude API Error: 400 - bad request'))).catch(() => {});
+    await trip(breaker, 1);
+    expect(breaker.getHealth()).toMatchObject({ state: 'closed', requests: 1, failureRate: 1 });
+
+    await trip(breaker, 1);
+    expect(breaker.state).toBe('open');
+  });
+
+  test('should close after a successful trial in half-open', async () => {
+    const breaker = new CircuitBreaker({ minimumRequests: 2, cooldown: 5000, now });
+    const changes = [];
+    breaker.onStateChange(({ from, to }) => changes.push(`${from}->${to}`));
+    await trip(breaker, 2);
+
+    time += 5000;
+    expect(breaker.state).toBe('half-open');
+
+    let release;
+    const trial = breaker.execute(() => new Promise((resolve) => { release = resolve; }));
+    await expect(breaker.execute(succeed)).rejects.toThrow('Circuit open');
+    release('ok');
+    await trial;
+
+    expect(breaker.state).toBe('closed');
+    expect(breaker.getHealth().requests).toBe(0);
+    expect(changes).toEqual(['closed->open', 'open->half-open', 'half-open->closed']);
+  });
+
+  test('should reopen when the trial fails', async () => {
+    const breaker = new CircuitBreaker({ minimumRequests: 2, cooldown: 5000, now });
+    await trip(breaker, 2);
+    time += 5000;
+
+    await trip(breaker, 1);
+
+    expect(breaker.state).toBe('open');
+    expect(breaker.getHealth().retryAt).toBe(new Date(time + 5000).toISOString());
+  });
+
+  test('should stay half-open when the trial fails with an error that does not count', async () => {
+    const breaker = new CircuitBreaker({ minimumRequests: 2, cooldown: 5000, now });
+    await trip(breaker, 2);
+    time += 5000;
+
+    await breaker.execute(() => Promise.reject(new Error('Claude API Error: 400 - bad request'))).catch(() => {});
+
+    expect(breaker.state).toBe('half-open');
+    await breaker.execute(succeed);
+    expect(breaker.state).toBe('closed');
+  });
+
+  test('should wrap the async methods of a client', async () => {
+    const client = new MockAIProvider().failNext('Grok API Error: 500 - down');
+    const breaker = new CircuitBreaker({ name: 'grok', minimumRequests: 1, now });
+    const wrapped = breaker.wrap(client);
+
+    await expect(wrapped.chat('hi')).rejects.toThrow('500');
+    await expect(wrapped.chat('hi')).rejects.toThrow('Circuit open for grok');
+    expect(client.calls).toHaveLength(1);
+
+    wrapped.setModel('grok-2');
+    expect(client.model).toBe('grok-2');
+    expect(wrapped.circuitBreaker).toBe(breaker);
+  });
+
+  test('should guard streams of a wrapped client', async () => {
+    const client = {
+      async *stream() {
+        yield 'partial';
+        throw new Error('Claude Request Error: socket hang up');
+      },
+    };
+    const breaker = new CircuitBreaker({ name: 'claude', minimumRequests: 1, now });
+    const wrapped = breaker.wrap(client);
+    const chunks = [];
+
+    await expect((async () => {
+      for await (const chunk of wrapped.stream([])) {
+        chunks.push(chunk);
+      }
+    })()).rejects.toThrow('socket hang up');
+
+    expect(chunks).toEqual(['partial']);
+    expect(breaker.state).toBe('open');
+    await expect(wrapped.stream([]).next()).rejects.toThrow('Circuit open for claude');
+  });
+
+  test('should let failover skip a provider with an open circuit and report it', async () => {
+    const primary = new CircuitBreaker({ name: 'primary', minimumRequests: 1, now });
+    await trip(primary, 1);
+    const backup = new MockAIProvider().reply('from backup');
+    const failover = new FailoverProvider([
+      { name: 'primary', client: primary.wrap(new MockAIProvider()) },
+      { name: 'backup', client: backup },
+    ]);
+
+    expect(await failover.chat('hi')).toBe('from backup');
+    const [first, second] = failover.getHealth();
+    expect(first).toMatchObject({ name: 'primary', healthy: false, circuit: 'open' });
+    expect(second).toMatchObject({ name: 'backup', healthy: true, circuit: null });
+  });
+});
@@ -0,0 +1,232 @@
+const { Readable } = require('stream');
+const Claude = require('../src/claude');
+const { ToolRegistry } = require('../src/tools');
+const UsageTracker = require('../src/usageTracker');
+const RateLimiter = require('../src/rateLimiter');
+
+jest.mock('https');
+
+const mockHttps = (statusCode, body) => {
+  const https = require('https');
+  const captured = {};
+  const mockReq = {
+    write: jest.fn((data) => { captured.body = JSON.parse(data); }),
+    end: jest.fn(),
+    on: jest.fn(),
+  };
+  const mockRes = {
+    statusCode,
+    on: jest.fn((event, cb) => {
+      if (event === 'data') cb(JSON.stringify(body));
+      if (event === 'end') cb();
+    }),
+  };
+
+  https.request = jest.fn((options, cb) => {
+    captured.options = options;
+    cb(mockRes);
+    return mockReq;
+  });
+  return captured;
+};
+
+describe('Claude', () => {
+  let originalEnv;
+
+  beforeEach(() => {
+    originalEnv = process.env.ANTHROPIC_API_KEY;
+    jest.clearAllMocks();
+  });
+
+  afterEach(() => {
+    process.env.ANTHROPIC_API_KEY = originalEnv;
+  });
+
+  describe('Constructor', () => {
+    test('should throw error when API key is not provided', () => {
+      delete process.env.ANTHROPIC_API_KEY;
+      expect(() => new Claude()).toThrow('Anthropic API key is required');
+    });
+
+    test('should initialize with API key from environment', () => {
+      process.env.ANTHROPIC_API_KEY = 'test-key';
+      const claude = new Claude();
+      expect(claude.apiKey).toBe('test-key');
+      expect(claude.baseUrl).toBe('https://api.anthropic.com/v1');
+    });
+  });
+
+  describe('chat', () => {
+    test('should send a message with Anthropic headers and return the text', async () => {
+      const captured = mockHttps(200, {
+        content: [{ type: 'text', text: 'Claude response' }],
+        usage: { input_tokens: 10, output_tokens: 5 },
+      });
+
+      const claude = new Claude('test-key');
+      const response = await claude.chat('Hello Claude');
+
+      expect(response).toBe('Claude response');
+      expect(captured.options.path).toBe('/v1/messages');
+      expect(captured.options.headers['x-api-key']).toBe('test-key');
+      expect(captured.options.headers['anthropic-version']).toBe('2023-06-01');
+      expect(captured.body.messages).toEqual([{ role: 'user', content: 'Hello Claude' }]);
+    });
+
+    test('should reject on API error', async () => {
+      mockHttps(401, { error: { message: 'invalid x-api-key' } });
+
+      const claude = new Claude('bad-key');
+      await expect(claude.chat('Hello')).rejects.toThrow('Claude API Error: 401 - invalid x-api-key');
+      await expect(claude.chat('Hello')).rejects.toMatchObject({ statusCode: 401 });
+    });
+  });
+
+  describe('conversation', () => {
+    test('should move system messages into the system prompt', async () => {
+      const captured = mockHttps(200, { content: [{ type: 'text', text: 'ok' }] });
+
+      const claude = new Claude('test-key');
+      await claude.conversation([
+        { role: 'system', content: 'You forecast.' },
+        { role: 'user', content: 'Hi' },
+      ], { system: 'Be brief.' });
+
+      expect(captured.body.system).toBe('Be brief.\n\nYou forecast.');
+      expect(captured.body.messages).toEqual([{ role: 'user', content: 'Hi' }]);
+    });
+
+    test('should record usage in OpenAI terms', async () => {
+      mockHttps(200, {
+        content: [{ type: 'text', text: 'ok' }],
+        usage: { input_tokens: 1000, output_tokens: 1000 },
+      });
+      const usageTracker = new UsageTracker();
+
+      await new Claude('test-key', { usageTracker }).chat('Hi');
+
+      expect(usageTracker.getTotals()).toMatchObject({ promptTokens: 1000, completionTokens: 1000 });
+      expect(usageTracker.getTotals().cost).toBeCloseTo(0.018);
+    });
+  });
+
+  describe('chatWithTools', () => {
+    test('should translate tool definitions, calls and results', async () => {
+      const claude = new Claude('test-key');
+      const sent = [];
+      jest.spyOn(claude, '_request')
+        .mockImplementationOnce(async (method, path, body) => {
+          sent.push(JSON.parse(JSON.stringify(body)));
+          return {
+            content: [
+              { type: 'text', text: 'Checking.' },
+              { type: 'tool_use', id: 'tu_1', name: 'latest_value', input: { series: 'sales' } },
+            ],
+          };
+        })
+        .mockImplementationOnce(async (method, path, body) => {
+          sent.push(body);
+          return { content: [{ type: 'text', text: 'Sales are at 120.' }] };
+        });
+      const tools = new ToolRegistry().register({
+        name: 'latest_value',
+        description: 'Latest value of a series',
+        parameters: { type: 'object', properties: { series: { type: 'string' } } },
+        handler: () => '120',
+      });
+
+      const result = await claude.chatWithTools([{ role: 'user', content: 'What are sales?' }], tools);
+
+      expect(result.content).toBe('Sales are at 120.');
+      expect(sent[0].tools).toEqual([{
+        name: 'latest_value',
+        description: 'Latest value of a series',
+        input_schema: { type: 'object', properties: { series: { type: 'string' } } },
+      }]);
+      expect(sent[1].messages.slice(1)).toEqual([
+        {
+          role: 'assistant',
+          content: [
+            { type: 'text', text: 'Checking.' },
+            { type: 'tool_use', id: 'tu_1', name: 'latest_value', input: { series: 'sales' } },
+          ],
+        },
+        { role: 'user', content: [{ type: 'tool_result', tool_use_id: 'tu_1', content: '120' }] },
+      ]);
+    });
+  });
+
+  describe('stream', () => {
+    test('should yield text deltas and record usage', async () => {
+      const events = [
+        { type: 'message_start', message: { usage: { input_tokens: 12 } } },
+        { type: 'content_block_delta', delta: { type: 'text_delta', text: 'Hel' } },
+        { type: 'content_block_delta', delta: { type: 'text_delta', text: 'lo' } },
+        { type: 'message_delta', usage: { output_tokens: 2 } },
+      ];
+      const sse = events.map((e) => `event: ${e.type}\ndata: ${JSON.stringify(e)}\n\n`).join('');
+      const usageTracker = new UsageTracker();
+      const claude = new Claude('test-key', { usageTracker });
+      // Split mid-event to exercise buffering
+      jest.spyOn(claude, '_openStream').mockResolvedValue(Readable.from([sse.slice(0, 50), sse.slice(50)]));
+
+      const parts = [];
+      for await (const text of claude.stream([{ role: 'user', content: 'Hi' }])) {
+        parts.push(text);
+      }
+
+      expect(parts.join('')).toBe('Hello');
+      expect(claude._openStream.mock.calls[0][1].stream).toBe(true);
+      expect(usageTracker.getTotals()).toMatchObject({ promptTokens: 12, completionTokens: 2 });
+    });
+
+    test('should decode characters split across chunks', async () => {
+      const claude = new Claude('test-key');
+      const sse = Buffer.from(`data: ${JSON.stringify({ type: 'content_block_delta', delta: { type: 'text_delta', text: 'héllo 🌍' } })}\n\n`);
+      const split = sse.indexOf(Buffer.from('🌍')) + 2;
+      jest.spyOn(claude, '_openStream').mockResolvedValue(Readable.from([sse.subarray(0, split), sse.subarray(split)]));
+
+      const parts = [];
+      for await (const text of claude.stream([{ role: 'user', content: 'Hi' }])) {
+        parts.push(text);
+      }
+
+      expect(parts.join('')).toBe('héllo 🌍');
+    });
+
+    test('should hold the limiter slot until the stream ends and keep usage when stopped early', async () => {
+      const rateLimiter = new RateLimiter({ maxConcurrent: 1 });
+      const usageTracker = new UsageTracker();
+      const claude = new Claude('test-key', { rateLimiter, usageTracker });
+      const events = [
+        { type: 'message_start', message: { usage: { input_tokens: 7 } } },
+        { type: 'content_block_delta', delta: { type: 'text_delta', text: 'one' } },
+        { type: 'content_block_delta', delta: { type: 'text_delta', text: 'two' } },
+      ];
+      jest.spyOn(claude, '_openStream').mockResolvedValue(Readable.from(events.map((e) => `data: ${JSON.stringify(e)}\n\n`)));
+
+      for await (const text of claude.stream([{ role: 'user', content: 'Hi' }])) {
+        expect(text).toBe('one');
+        expect(rateLimiter.inFlight).toBe(1);
+        break;
+      }
+      await new Promise((resolve) => setImmediate(resolve));
+
+      expect(rateLimiter.inFlight).toBe(0);
+      expect(usageTracker.getTotals()).toMatchObject({ requests: 1, promptTokens: 7 });
+    });
+
+    test('should throw on error events', async () => {
+      const claude = new Claude('test-key');
+      const sse = `event: error\ndata: ${JSON.stringify({ type: 'error', error: { message: 'Overloaded' } })}\n\n`;
+      jest.spyOn(claude, '_openStream').mockResolvedValue(Readable.from([sse]));
+
+      const consume = async () => {
+        for await (const text of claude.stream([{ role: 'user', content: 'Hi' }])) {
+          expect(text).toBeUndefined();
+        }
+      };
+      await expect(consume()).rejects.toThrow('Claude API Error: Overloaded');
+    });
+  });
+});
@@ -0,0 +1,202 @@
+const fs = require('fs');
+const os = require('os');
+const path = require('path');
+const { run, parseArgs } = require('../src/cli');
+const { MockAIProvider } = require('../src/testing');
+
+const output = () => {
+  const stream = { text: '', write: (chunk) => { stream.text += chunk; } };
+  return stream;
+};
+
+describe('cli', () => {
+  const savedEnv = { ...process.env };
+  let stdout;
+  let stderr;
+
+  beforeEach(() => {
+    stdout = output();
+    stderr = output();
+    for (const name of ['OPENAI_API_KEY', 'GROK_API_KEY', 'ANTHROPIC_API_KEY', 'LOCAL_MODEL_URL', 'AZURE_OPENAI_API_KEY', 'OPENCLAW_API_KEY', 'REPLIT_API_TOKEN', 'GITHUB_TOKEN']) {
+      delete process.env[name];
+    }
+  });
+
+  afterAll(() => {
+    process.env = savedEnv;
+  });
+
+  test('should parse positionals and options', () => {
+    expect(parseArgs(['hello', '--model', 'gpt-4', '--provider=xai', 'world', '--verbose'])).toEqual({
+      positionals: ['hello', 'world'],
+      options: { model: 'gpt-4', provider: 'xai', verbose: true },
+    });
+  });
+
+  test('should print usage for help and unknown commands', async () => {
+    expect(await run(['help'], { stdout, stderr })).toBe(0);
+    expect(stdout.text).toContain('Usage: aitm <command>');
+
+    expect(await run(['launch'], { stdout, stderr })).toBe(2);
+    expect(stderr.text).toContain('Unknown command: launch');
+  });
+
+  describe('chat', () => {
+    test('should send the message to the chosen provider', async () => {
+      const client = new MockAIProvider().reply('Hi there');
+      const createClient = jest.fn(() => client);
+
+      const code = await run(['chat', '--provider', 'anthropic', '--model', 'claude-x', 'Hello', 'you'], { stdout, stderr, createClient });
+
+      expect(code).toBe(0);
+      expect(createClient).toHaveBeenCalledWith('anthropic', { model: 'claude-x' });
+      expect(client.calls[0].messages).toEqual([{ role: 'user', content: 'Hello you' }]);
+      expect(stdout.text).toBe('Hi there\n');
+    });
+
+    test('should default to the first configured provider', async () => {
+      process.env.GROK_API_KEY = 'key';
+      const createClient = jest.fn(() => new MockAIProvider());
+
+      await run(['chat', 'Hi'], { stdout, stderr, createClient });
+      expect(createClient).toHaveBeenCalledWith('xai', {});
+    });
+
+    test('should fail without a message or provider', async () => {
+      expect(await run(['chat'], { stdout, stderr })).toBe(2);
+      expect(await run(['chat', 'Hi'], { stdout, stderr })).toBe(1);
+      expect(stderr.text).toContain('No provider is configured');
+    });
+
+    test('should report client errors', async () => {
+      const createClient = () => new MockAIProvider().failNext('401 unauthorized');
+      expect(await run(['chat', '--provider', 'openai', 'Hi'], { stdout, stderr, createClient })).toBe(1);
+      expect(stderr.text).toBe('Error: 401 unauthorized\n');
+    });
+
+    test('should reject unknown providers', async () => {
+      expect(await run(['chat', '--provider', 'acme', 'Hi'], { stdout, stderr })).toBe(1);
+      expect(stderr.text).toContain('Unknown provider: acme');
+    });
+  });
+
+  describe('repl', () => {
+    test('should run an interactive session until /exit', async () => {
+      const client = new MockAIProvider().reply('Hello!');
+      const createClient = jest.fn(() => client);
+      const input = ['Hi', '/memory', '/exit', 'never sent'];
+
+      const code = await run(['repl', '--provider', 'xai', '--system', 'Be brief.'], { stdout, stderr, createClient, input });
+
+      expect(code).toBe(0);
+      expect(createClient).toHaveBeenCalledWith('xai', { usageTracker: expect.any(Object) });
+      expect(stdout.text).toContain('Hello!\n');
+      expect(stdout.text).toContain('[system] Be brief.');
+      expect(client.calls).toHaveLength(1);
+    });
+
+    test('should print errors and keep going', async () => {
+      const client = new MockAIProvider().failNext('rate limited');
+      const input = ['Hi', 'Hi again'];
+
+      await run(['repl', '--provider', 'xai'], { stdout, stderr, createClient: () => client, input });
+
+      expect(stderr.text).toBe('Error: rate limited\n');
+      expect(stdout.text).toContain('mock response');
+    });
+  });
+
+  describe('config validate', () => {
+    test('should list configured providers without printing secrets', async () => {
+      process.env.ANTHROPIC_API_KEY = 'sk-ant-secret';
+
+      expect(await run(['config', 'validate'], { stdout, stderr })).toBe(0);
+      expect(stdout.text).toContain('✓ anthropic');
+      expect(stdout.text).toContain('✗ openai');
+      expect(stdout.text).not.toContain('sk-ant-secret');
+    });
+
+    test('should fail when nothing is configured', async () => {
+      expect(await run(['config', 'validate'], { stdout, stderr })).toBe(1);
+      expect(await run(['config'], { stdout, stderr })).toBe(2);
+    });
+  });
+
+  describe('eval', () => {
+    test('should run a suite module and fail on failing cases', async () => {
+      process.env.OPENAI_API_KEY = 'sk-test';
+      const suitePath = path.join(os.tmpdir(), `aitm-eval-${process.pid}.js`);
+      fs.writeFileSync(suitePath, `
+        const EvalSuite = require(${JSON.stringify(path.resolve(__dirname, '../src/evalSuite'))});
+        module.exports = new EvalSuite('cli')
+          .add({ name: 'echo', prompt: 'Hi', checks: [{ type: 'contains', value: 'mock' }] })
+          .add({ name: 'strict', prompt: 'Hi', checks: [{ type: 'regex', pattern: '^\\d+$' }] });
+      `);
+
+      try {
+        const code = await run(['eval', suitePath], { stdout, stderr, createClient: () => new MockAIProvider() });
+        expect(code).toBe(1);
+        expect(stdout.text).toContain('openai: 1/2 passed');
+        expect(stdout.text).toContain('✓ echo');
+      } finally {
+        fs.unlinkSync(suitePath);
+      }
+    });
+
+    test('should require a suite module', async () => {
+      expect(await run(['eval'], { stdout, stderr })).toBe(2);
+    });
+  });
+
+  describe('mcp', () => {
+    test('should require a tools module', async () => {
+      expect(await run(['mcp'], { stdout, stderr })).toBe(2);
+      expect(stderr.text).toContain('Usage: aitm mcp --tools module');
+    });
+
+    test('should refuse to serve HTTP without a token', async () => {
+      delete process.env.AITM_MCP_TOKEN;
+      expect(await run(['mcp', '--tools', 'tools.js', '--port', '3002'], { stdout, stderr })).toBe(1);
+      expect(stderr.text).toContain('Set AITM_MCP_TOKEN');
+    });
+  });
+
+  describe('proxy', () => {
+    test('should need a configured provider', async () => {
+      expect(await run(['proxy'], { stdout, stderr })).toBe(1);
+      expect(stderr.text).toContain('No provider is configured');
+    });
+
+    test('should refuse to start without an API key', async () => {
+      process.env.OPENAI_API_KEY = 'sk-test';
+      delete process.env.AITM_PROXY_API_KEY;
+      expect(await run(['proxy'], { stdout, stderr })).toBe(1);
+      expect(stderr.text).toContain('Set AITM_PROXY_API_KEY');
+    });
+  });
+
+  describe('doctor', () => {
+    test('should probe configured providers and fail when one is broken', async () => {
+      process.env.OPENAI_API_KEY = 'sk-test';
+      process.env.ANTHROPIC_API_KEY = 'sk-ant-test';
+      const createClient = (provider) => {
+        const client = new MockAIProvider();
+        if (provider === 'anthropic') {
+          client.failNext('Claude API Error: 401 - invalid x-api-key');
+        }
+        return client;
+      };
+
+      expect(await run(['doctor'], { stdout, stderr, createClient })).toBe(1);
+      expect(stdout.text).toContain('✓ openai');
+      expect(stdout.text).toContain('✗ anthropic');
+      expect(stdout.text).toContain('Check the API key');
+      expect(stdout.text).toContain('1 of 2 providers need attention.');
+    });
+
+    test('should fail when nothing is configured', async () => {
+      expect(await run(['doctor'], { stdout, stderr })).toBe(1);
+      expect(stdout.text).toContain('No providers are configured');
+    });
+  });
+});
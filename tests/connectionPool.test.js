@@ -0,0 +1,67 @@
+const https = require('https');
+const http = require('http');
+const ConnectionPool = require('../src/connectionPool');
+const OpenClaw = require('../src/openclaw');
+
+describe('ConnectionPool', () => {
+  let pool;
+
+  afterEach(() => {
+    pool.destroy();
+  });
+
+  describe('Constructor', () => {
+    test('should create keep-alive agents with defaults', () => {
+      pool = new ConnectionPool();
+      expect(pool.httpsAgent).toBeInstanceOf(https.Agent);
+      expect(pool.httpAgent).toBeInstanceOf(http.Agent);
+      expect(pool.httpsAgent.keepAlive).toBe(true);
+      expect(pool.config).toEqual({ maxSockets: 50, maxFreeSockets: 10, idleTimeout: 30000 });
+    });
+
+    test('should apply custom limits', () => {
+      pool = new ConnectionPool({ maxSockets: 4, maxFreeSockets: 2, idleTimeout: 5000 });
+      expect(pool.httpsAgent.maxSockets).toBe(4);
+      expect(pool.httpsAgent.maxFreeSockets).toBe(2);
+      expect(pool.httpAgent.maxSockets).toBe(4);
+    });
+  });
+
+  describe('agentFor', () => {
+    test('should select the agent by protocol', () => {
+      pool = new ConnectionPool();
+      expect(pool.agentFor('https:')).toBe(pool.httpsAgent);
+      expect(pool.agentFor('http:')).toBe(pool.httpAgent);
+    });
+  });
+
+  describe('client integration', () => {
+    test('should pass the pooled agent to requests', async () => {
+      pool = new ConnectionPool();
+      const originalRequest = https.request;
+
+      const mockRes = {
+        statusCode: 200,
+        on: jest.fn((event, cb) => {
+          if (event === 'data') cb(JSON.stringify({ success: true }));
+          if (event === 'end') cb();
+        }),
+      };
+
+      https.request = jest.fn((options, cb) => {
+        cb(mockRes);
+        return { write: jest.fn(), end: jest.fn(), on: jest.fn() };
+      });
+
+      try {
+        const openclaw = new OpenClaw('test-key', { pool });
+        await openclaw.sendEvent('test.event', {});
+
+        const options = https.request.mock.calls[0][0];
+        expect(options.agent).toBe(pool.httpsAgent);
+      } finally {
+        https.request = originalRequest;
+      }
+    });
+  });
+});
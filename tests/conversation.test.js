@@ -0,0 +1,163 @@
+const Conversation = require('../src/conversation');
+const { MemoryStore } = require('../src/responseCache');
+
+const makeClient = (reply = 'ok') => ({
+  conversation: jest.fn().mockResolvedValue(reply),
+});
+
+describe('Conversation', () => {
+  describe('Constructor', () => {
+    test('should throw error without a conversation client', () => {
+      expect(() => new Conversation({})).toThrow('requires a client');
+    });
+
+    test('should throw error for an unknown strategy', () => {
+      expect(() => new Conversation(makeClient(), { strategy: 'forget' })).toThrow('Unsupported conversation strategy');
+    });
+
+    test('should start with the system prompt', () => {
+      const conversation = new Conversation(makeClient(), { systemPrompt: 'Be helpful.' });
+      expect(conversation.messages).toEqual([{ role: 'system', content: 'Be helpful.' }]);
+    });
+  });
+
+  describe('send', () => {
+    test('should send the history and record the reply', async () => {
+      const client = makeClient('Hi there!');
+      const conversation = new Conversation(client, { systemPrompt: 'Be helpful.' });
+
+      const reply = await conversation.send('Hello', { temperature: 0.2 });
+
+      expect(reply).toBe('Hi there!');
+      expect(client.conversation).toHaveBeenCalledWith(
+        [
+          { role: 'system', content: 'Be helpful.' },
+          { role: 'user', content: 'Hello' },
+        ],
+        { temperature: 0.2 }
+      );
+      expect(conversation.messages).toHaveLength(3);
+      expect(conversation.messages[2]).toEqual({ role: 'assistant', content: 'Hi there!' });
+    });
+
+    test('should leave the history unchanged when the client fails', async () => {
+      const client = { conversation: jest.fn().mockRejectedValue(new Error('ChatGPT API Error: 503')) };
+      const conversation = new Conversation(client, { systemPrompt: 'Be helpful.' });
+
+      await expect(conversation.send('Hello')).rejects.toThrow('ChatGPT API Error: 503');
+
+      expect(conversation.messages).toEqual([{ role: 'system', content: 'Be helpful.' }]);
+    });
+  });
+
+  describe('fitBudget', () => {
+    test('should drop the oldest turns but keep the system prompt', async () => {
+      const conversation = new Conversation(makeClient(), { systemPrompt: 'sys', maxTokens: 30 });
+      for (let i = 0; i < 5; i++) {
+        conversation.addMessage('user', `message number ${i} `.repeat(3));
+      }
+
+      await conversation.fitBudget();
+
+      expect(conversation.estimateTokens()).toBeLessThanOrEqual(30);
+      expect(conversation.messages[0]).toEqual({ role: 'system', content: 'sys' });
+      expect(conversation.messages[conversation.messages.length - 1].content).toContain('message number 4');
+    });
+
+    test('should drop tool calls together with their replies', async () => {
+      const conversation = new Conversation(makeClient(), { maxTokens: 40 });
+      conversation.messages.push(
+        { role: 'user', content: 'What is the weather in Paris and Rome?' },
+        {
+          role: 'assistant',
+          content: null,
+          tool_calls: [
+            { id: 'c1', type: 'function', function: { name: 'weather', arguments: '{"city":"Paris"}' } },
+            { id: 'c2', type: 'function', function: { name: 'weather', arguments: '{"city":"Rome"}' } },
+          ],
+        },
+        { role: 'tool', tool_call_id: 'c1', content: 'Sunny, 24 degrees' },
+        { role: 'tool', tool_call_id: 'c2', content: 'Cloudy, 19 degrees' },
+        { role: 'assistant', content: [{ type: 'text', text: 'Paris is sunny and Rome is cloudy today.' }] },
+        { role: 'user', content: 'Thanks' }
+      );
+
+      expect(conversation.estimateTokens([conversation.messages[1]])).toBeGreaterThan(4);
+      expect(conversation.estimateTokens([conversation.messages[4]])).toBe(14);
+
+      await conversation.fitBudget();
+
+      expect(conversation.messages.map((m) => m.role)).toEqual(['assistant', 'user']);
+      expect(conversation.estimateTokens()).toBeLessThanOrEqual(40);
+    });
+
+    test('should summarize older turns with the summarize strategy', async () => {
+      const summarizer = jest.fn().mockResolvedValue('User asked about sales.');
+      const conversation = new Conversation(makeClient(), {
+        systemPrompt: 'sys',
+        maxTokens: 40,
+        strategy: 'summarize',
+        keepRecent: 2,
+        summarizer,
+      });
+      conversation.addMessage('user', 'a'.repeat(60));
+      conversation.addMessage('assistant', 'b'.repeat(60));
+      conversation.addMessage('user', 'recent question');
+      conversation.addMessage('assistant', 'recent answer');
+
+      await conversation.fitBudget();
+
+      expect(summarizer).toHaveBeenCalledTimes(1);
+      expect(summarizer.mock.calls[0][0]).toHaveLength(2);
+      expect(conversation.messages.map((m) => m.content)).toEqual([
+        'sys',
+        'Summary of the earlier conversation: User asked about sales.',
+        'recent question',
+        'recent answer',
+      ]);
+    });
+
+    test('should use the client to summarize when no summarizer is given', async () => {
+      const client = makeClient('condensed');
+      const conversation = new Conversation(client, { maxTokens: 20, strategy: 'summarize', keepRecent: 1 });
+      conversation.addMessage('user', 'x'.repeat(100));
+      conversation.addMessage('user', 'latest');
+
+      await conversation.fitBudget();
+
+      expect(client.conversation).toHaveBeenCalledTimes(1);
+      expect(conversation.messages[0].content).toBe('Summary of the earlier conversation: condensed');
+    });
+  });
+
+  describe('persistence', () => {
+    test('should round-trip through toJSON and fromJSON', () => {
+      const conversation = new Conversation(makeClient(), { systemPrompt: 'sys', maxTokens: 100 });
+      conversation.addMessage('user', 'Hello');
+
+      const restored = Conversation.fromJSON(conversation.toJSON(), makeClient());
+
+      expect(restored.messages).toEqual(conversation.messages);
+      expect(restored.maxTokens).toBe(100);
+    });
+
+    test('should save to and load from a store', async () => {
+      const store = new MemoryStore();
+      const conversation = new Conversation(makeClient(), { systemPrompt: 'sys' });
+      conversation.addMessage('user', 'Remember me');
+
+      await conversation.save(store, 'session-1');
+      const loaded = await Conversation.load(store, 'session-1', makeClient());
+
+      expect(loaded.messages).toEqual(conversation.messages);
+      expect(await Conversation.load(store, 'missing', makeClient())).toBeNull();
+    });
+
+    test('should clear messages but keep the system prompt', () => {
+      const conversation = new Conversation(makeClient(), { systemPrompt: 'sys' });
+      conversation.addMessage('user', 'Hello');
+      conversation.clear();
+      expect(conversation.messages).toEqual([{ role: 'system', content: 'sys' }]);
+    });
+  });
+});
@@ -0,0 +1,85 @@
+const http = require('http');
+const Dashboard = require('../src/dashboard');
+const Metrics = require('../src/metrics');
+const UsageTracker = require('../src/usageTracker');
+const RateLimiter = require('../src/rateLimiter');
+const FailoverProvider = require('../src/failoverProvider');
+const ProgressHandle = require('../src/progressHandle');
+const { MockAIProvider } = require('../src/testing');
+
+function get(port, path) {
+  return new Promise((resolve, reject) => {
+    http.get(`http://127.0.0.1:${port}${path}`, (res) => {
+      let data = '';
+      res.on('data', (chunk) => {
+        data += chunk;
+      });
+      res.on('end', () => resolve({ statusCode: res.statusCode, headers: res.headers, body: data }));
+    }).on('error', reject);
+  });
+}
+
+describe('Dashboard', () => {
+  test('should report empty sections when nothing is configured', () => {
+    const status = new Dashboard().status();
+    expect(status).toMatchObject({ runs: [], queue: null, jobs: [], errors: [], spendToday: null, providers: [] });
+  });
+
+  test('should collect status from every source', () => {
+    const metrics = new Metrics();
+    metrics.onError({ provider: 'openai', model: 'gpt-4', error: new Error('older') });
+    metrics.onError({ provider: 'xai', model: 'grok-beta', error: new Error('newer') });
+
+    const usageTracker = new UsageTracker({ pricing: { 'gpt-4': { prompt: 0.03, completion: 0.06 } } });
+    usageTracker.record('gpt-4', { prompt_tokens: 1000, completion_tokens: 1000 });
+    usageTracker.entries.push({ timestamp: '2000-01-01T00:00:00Z', promptTokens: 5, completionTokens: 5, cost: 9 });
+
+    const failover = new FailoverProvider([{ name: 'primary', client: new MockAIProvider() }]);
+    const progress = new ProgressHandle({ total: 4, stage: 'embed' });
+    progress.advance();
+
+    const dashboard = new Dashboard({ metrics, usageTracker, rateLimiter: new RateLimiter({ maxConcurrent: 2 }), failover });
+    dashboard.track('backfill', progress);
+
+    const status = dashboard.status();
+    expect(status.runs).toEqual([expect.objectContaining({ id: 'backfill', stage: 'embed', percent: 25 })]);
+    expect(status.queue).toMatchObject({ inFlight: 0, queued: 0 });
+    expect(status.errors.map((error) => error.message)).toEqual(['newer', 'older']);
+    expect(status.spendToday.requests).toBe(1);
+    expect(status.spendToday.cost).toBeCloseTo(0.09);
+    expect(status.providers[0]).toMatchObject({ name: 'primary', healthy: true });
+
+    dashboard.untrack('backfill');
+    expect(dashboard.status().runs).toEqual([]);
+  });
+
+  test('should escape values in the HTML page', () => {
+    const metrics = new Metrics();
+    metrics.onError({ provider: 'openai', model: 'gpt-4', error: new Error('<script>alert(1)</script>') });
+
+    const html = new Dashboard({ metrics, title: 'Ops' }).render();
+    expect(html).toContain('<title>Ops</title>');
+    expect(html).toContain('&lt;script&gt;alert(1)&lt;/script&gt;');
+    expect(html).not.toContain('<script>');
+  });
+
+  test('should serve HTML and JSON over HTTP', async () => {
+    const dashboard = new Dashboard();
+    dashboard.track('ingest', new ProgressHandle());
+    const server = await dashboard.listen(0);
+
+    try {
+      const { port } = server.address();
+      const page = await get(port, '/');
+      const json = await get(port, '/status.json?fresh=1');
+      const missing = await get(port, '/nope');
+
+      expect(page.headers['content-type']).toContain('text/html');
+      expect(page.body).toContain('ingest');
+      expect(JSON.parse(json.body).runs[0].id).toBe('ingest');
+      expect(missing.statusCode).toBe(404);
+    } finally {
+      server.close();
+    }
+  });
+});
@@ -0,0 +1,51 @@
+const { describeConfig } = require('../src/describe');
+const Grok = require('../src/grok');
+const ResponseCache = require('../src/responseCache');
+
+describe('describeConfig', () => {
+  let saved;
+
+  beforeEach(() => {
+    saved = { ...process.env };
+    delete process.env.GROK_API_KEY;
+    delete process.env.ANTHROPIC_API_KEY;
+  });
+
+  afterEach(() => {
+    process.env = saved;
+  });
+
+  test('should report which providers are configured without exposing secrets', () => {
+    process.env.GROK_API_KEY = 'xai-secret';
+
+    const result = describeConfig();
+
+    expect(result.version).toBe(require('../package.json').version);
+    expect(result.runtime.node).toBe(process.version);
+    expect(result.providers.xai).toEqual({ configured: true, env: { GROK_API_KEY: true } });
+    expect(result.providers.anthropic.configured).toBe(false);
+    expect(JSON.stringify(result)).not.toContain('xai-secret');
+  });
+
+  test('should describe client instances and their attached features', () => {
+    const grok = new Grok('xai-secret', { cache: new ResponseCache() });
+
+    const { clients } = describeConfig({ analysis: grok });
+
+    expect(clients.analysis).toEqual({
+      type: 'Grok',
+      provider: null,
+      model: 'grok-beta',
+      baseUrl: 'https://api.x.ai/v1',
+      features: {
+        rateLimiter: false,
+        pool: false,
+        observer: false,
+        usageTracker: false,
+        cache: true,
+        recorder: false,
+      },
+    });
+    expect(JSON.stringify(clients)).not.toContain('xai-secret');
+  });
+});
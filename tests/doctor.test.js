@@ -0,0 +1,59 @@
+const { diagnose, formatReport, hintFor } = require('../src/doctor');
+
+describe('doctor', () => {
+  test('should check chat clients with a one-token request', async () => {
+    const client = { provider: 'openai', model: 'gpt-4', chat: jest.fn().mockResolvedValue('pong') };
+
+    const [result] = await diagnose({ openai: client });
+
+    expect(client.chat).toHaveBeenCalledWith('ping', { max_tokens: 1 });
+    expect(result).toMatchObject({ name: 'openai', model: 'gpt-4', ok: true, check: 'chat', error: null });
+  });
+
+  test('should check that the configured model is available', async () => {
+    const client = {
+      provider: 'local',
+      model: 'llama3',
+      listModels: jest.fn().mockResolvedValue([{ name: 'mistral:latest' }]),
+      chat: jest.fn(),
+    };
+
+    const [result] = await diagnose({ local: client });
+
+    expect(client.chat).not.toHaveBeenCalled();
+    expect(result.ok).toBe(false);
+    expect(result.error).toBe('model llama3 not found (available: mistral:latest)');
+    expect(result.hint).toContain('model was not found');
+
+    client.listModels.mockResolvedValue([{ name: 'llama3:latest' }]);
+    expect((await diagnose({ local: client }))[0].ok).toBe(true);
+  });
+
+  test('should fail probes that exceed the timeout', async () => {
+    const client = { chat: () => new Promise(() => {}) };
+
+    const [result] = await diagnose({ slow: client }, { timeout: 10 });
+
+    expect(result.error).toBe('timed out after 10ms');
+    expect(result.hint).toContain('timed out');
+  });
+
+  test('should map common errors to hints', () => {
+    expect(hintFor('Grok API Error: 403 - forbidden')).toContain('API key');
+    expect(hintFor('ChatGPT API Error: 429 You exceeded your current quota')).toContain('quota');
+    expect(hintFor('Local Model Request Error: connect ECONNREFUSED 127.0.0.1:11434')).toContain('could not be reached');
+    expect(hintFor('Claude API Error: 529 - overloaded')).toContain('server error');
+    expect(hintFor('something odd')).toContain('Unexpected error');
+  });
+
+  test('should format a report with a summary', () => {
+    const report = formatReport([
+      { name: 'openai', model: 'gpt-4', ok: true, check: 'chat', latencyMs: 120 },
+      { name: 'xai', model: 'grok-beta', ok: false, error: 'Grok API Error: 401 - bad key', hint: 'Check the API key for this provider.' },
+    ]);
+
+    expect(report).toContain('✓ openai / gpt-4 (chat, 120ms)');
+    expect(report).toContain('✗ xai / grok-beta\n    Grok API Error: 401 - bad key\n    → Check the API key for this provider.');
+    expect(report).toContain('1 of 2 providers need attention.');
+  });
+});
@@ -0,0 +1,80 @@
+const EvalSuite = require('../src/evalSuite');
+const { MockAIProvider } = require('../src/testing');
+
+describe('EvalSuite', () => {
+  test('should validate cases', () => {
+    const suite = new EvalSuite('bot').add({ name: 'a', prompt: 'Hi' });
+
+    expect(() => suite.add({ name: 'a', prompt: 'Hi' })).toThrow('must be unique');
+    expect(() => suite.add({ name: 'b' })).toThrow('needs a prompt or messages');
+    expect(() => suite.add({ name: 'c', prompt: 'Hi', checks: [{ type: 'bleu' }] })).toThrow('Unsupported eval check: bleu');
+    expect(() => suite.add({ name: 'd', prompt: 'Hi', checks: [{ type: 'judge', rubric: 'kind' }] })).toThrow('has no judge client');
+  });
+
+  test('should run deterministic checks against each target', async () => {
+    const good = new MockAIProvider().reply('Refunds are accepted within 30 days.', '{"answer": "yes"}');
+    const bad = new MockAIProvider().reply('No refunds.', 'sure!');
+    const suite = new EvalSuite('support', { concurrency: 1 })
+      .add({
+        name: 'refund policy',
+        prompt: 'Can I get a refund?',
+        checks: [
+          { type: 'contains', value: '30 DAYS', ignoreCase: true },
+          { type: 'regex', pattern: /refunds?/gi },
+        ],
+      })
+      .add({
+        name: 'json answer',
+        messages: [{ role: 'user', content: 'Answer in JSON' }],
+        checks: [{ type: 'schema', schema: { type: 'object', required: ['answer'] } }],
+      });
+
+    const report = await suite.run({ good, bad });
+
+    expect(report.passed).toBe(false);
+    expect(report.targets[0]).toMatchObject({ name: 'good', passed: 2, failed: 0, score: 1 });
+    expect(report.targets[1]).toMatchObject({ name: 'bad', passed: 0, failed: 2, score: 0.25 });
+    expect(report.targets[1].cases[0].checks[0].reason).toBe('reply does not contain "30 DAYS"');
+    expect(report.targets[1].cases[1].checks[0].reason).toContain('invalid JSON');
+  });
+
+  test('should pass target and case options to the client', async () => {
+    const client = { conversation: jest.fn().mockResolvedValue('ok') };
+    const suite = new EvalSuite('options').add({ name: 'a', prompt: 'Hi', options: { temperature: 0 } });
+
+    const report = await suite.run({ small: { client, options: { model: 'gpt-4o-mini' } } });
+
+    expect(report.passed).toBe(true);
+    expect(client.conversation).toHaveBeenCalledWith([{ role: 'user', content: 'Hi' }], { model: 'gpt-4o-mini', temperature: 0 });
+  });
+
+  test('should score replies with a judge model', async () => {
+    const judge = new MockAIProvider().reply('{"score": 0.9, "reason": "polite"}', '{"score": 0.4, "reason": "rude"}');
+    const client = new MockAIProvider().reply('Happy to help!', 'Go away.');
+    const suite = new EvalSuite('tone', { judge, concurrency: 1 })
+      .add({ name: 'greeting', prompt: 'Hello', checks: [{ type: 'judge', rubric: 'Polite' }] })
+      .add({ name: 'complaint', prompt: 'This is broken', checks: [{ type: 'judge', rubric: 'Polite', threshold: 0.5 }] });
+
+    const [target] = (await suite.run({ client })).targets;
+
+    expect(target.cases[0].checks[0]).toEqual({ type: 'judge', passed: true, score: 0.9, reason: 'polite' });
+    expect(target.cases[1].checks[0]).toEqual({ type: 'judge', passed: false, score: 0.4, reason: 'rude' });
+    expect(judge.calls[0].messages[1].content).toContain('Rubric: Polite');
+  });
+
+  test('should report client errors and custom checks', async () => {
+    const client = { conversation: jest.fn().mockRejectedValueOnce(new Error('Grok API Error: 500 - down')).mockResolvedValue('42') };
+    const suite = new EvalSuite('mixed', { concurrency: 1 })
+      .add({ name: 'fails', prompt: 'a' })
+      .add({ name: 'custom', prompt: 'b', checks: [{ type: 'custom', name: 'is number', check: (output) => ({ passed: !Number.isNaN(Number(output)), score: 0.8 }) }] });
+
+    const report = await suite.run({ client });
+    const text = EvalSuite.formatReport(report);
+
+    expect(report.targets[0].cases[0]).toMatchObject({ passed: false, error: 'Grok API Error: 500 - down' });
+    expect(report.targets[0].cases[1]).toMatchObject({ passed: true, score: 0.8 });
+    expect(text).toContain('client: 1/2 passed, score 0.40');
+    expect(text).toContain('✗ fails (0.00)\n      error: Grok API Error: 500 - down');
+    expect(text).toContain('Some cases failed.');
+  });
+});
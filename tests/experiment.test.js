@@ -0,0 +1,87 @@
+const Experiment = require('../src/experiment');
+const UsageTracker = require('../src/usageTracker');
+const { PromptTemplate } = require('../src/prompts');
+const { MockAIProvider } = require('../src/testing');
+
+describe('Experiment', () => {
+  const variants = () => [
+    { name: 'control', client: new MockAIProvider().reply('A') },
+    { name: 'candidate', client: new MockAIProvider().reply('B'), options: { temperature: 0.2 } },
+  ];
+
+  test('should validate variants', () => {
+    expect(() => new Experiment('x', { variants: variants().slice(0, 1) })).toThrow('at least two variants');
+    expect(() => new Experiment('x', { variants: [...variants(), { name: 'control', client: new MockAIProvider() }] })).toThrow('must be unique');
+    expect(() => new Experiment('x', { variants: [...variants(), { name: 'broken', client: {} }] })).toThrow('needs a name and a client');
+  });
+
+  test('should assign by weight at random', () => {
+    const values = [0.1, 0.3, 0.9];
+    const experiment = new Experiment('x', {
+      variants: [{ ...variants()[0], weight: 1 }, { ...variants()[1], weight: 3 }],
+      random: () => values.shift(),
+    });
+
+    expect([experiment.assign(), experiment.assign(), experiment.assign()]).toEqual(['control', 'candidate', 'candidate']);
+  });
+
+  test('should assign the same unit to the same variant', () => {
+    const experiment = new Experiment('x', { variants: variants() });
+    const assigned = experiment.assign('user-42');
+
+    for (let i = 0; i < 5; i++) {
+      expect(experiment.assign('user-42')).toBe(assigned);
+    }
+    const units = Array.from({ length: 200 }, (_, i) => experiment.assign(`user-${i}`));
+    expect(new Set(units)).toEqual(new Set(['control', 'candidate']));
+  });
+
+  test('should run requests through the assigned variant', async () => {
+    const [control, candidate] = variants();
+    const experiment = new Experiment('x', { variants: [control, candidate] });
+
+    const result = await experiment.run('Hello', { variant: 'candidate' });
+
+    expect(result).toMatchObject({ variant: 'candidate', output: 'B' });
+    expect(candidate.client.calls[0].options).toEqual({ temperature: 0.2 });
+    expect(control.client.calls).toHaveLength(0);
+    await expect(experiment.run('Hello', { variant: 'missing' })).rejects.toThrow('Unknown variant: missing');
+  });
+
+  test('should render variant prompt templates', async () => {
+    const client = new MockAIProvider().reply('ok');
+    const experiment = new Experiment('x', {
+      variants: [
+        { name: 'short', client, template: new PromptTemplate('Summarize in one line: {{text}}') },
+        { name: 'long', client, template: new PromptTemplate('Summarize in detail: {{text}}') },
+      ],
+    });
+
+    await experiment.run({ text: 'report' }, { variant: 'short' });
+
+    expect(client.calls[0].messages).toEqual([{ role: 'user', content: 'Summarize in one line: report' }]);
+  });
+
+  test('should aggregate errors, metrics and usage per variant', async () => {
+    const usage = new UsageTracker();
+    const failing = { conversation: jest.fn().mockRejectedValue(new Error('Grok API Error: 500 - down')) };
+    const control = new MockAIProvider().reply('A');
+    const experiment = new Experiment('summary', {
+      usageTracker: usage,
+      variants: [{ name: 'control', client: control }, { name: 'candidate', client: failing }],
+    });
+
+    await experiment.run('Hi', { variant: 'control' });
+    usage.withTag(Experiment.tag('summary', 'control')).record('gpt-4', { prompt_tokens: 1000, completion_tokens: 0 });
+    const error = await experiment.run('Hi', { variant: 'candidate' }).catch((caught) => caught);
+    experiment.recordMetric('control', 'rating', 4);
+    experiment.recordMetric('control', 'rating', 5);
+
+    expect(error.variant).toBe('candidate');
+    const { variants: results } = experiment.results();
+    expect(results.control).toMatchObject({ requests: 1, errors: 0, errorRate: 0, metrics: { rating: { count: 2, mean: 4.5 } } });
+    expect(results.control.usage).toMatchObject({ requests: 1, totalTokens: 1000, cost: 0.03 });
+    expect(results.candidate).toMatchObject({ requests: 1, errors: 1, errorRate: 1, usage: null, metrics: {} });
+    expect(() => experiment.recordMetric('missing', 'rating', 1)).toThrow('Unknown variant');
+  });
+});
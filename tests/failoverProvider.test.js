@@ -0,0 +1,192 @@
+const FailoverProvider = require('../src/failoverProvider');
+const { isRetryableError } = require('../src/failoverProvider');
+const { MockAIProvider } = require('../src/testing');
+
+describe('isRetryableError', () => {
+  test('should retry rate limits, server errors and network failures', () => {
+    expect(isRetryableError(new Error('Grok API Error: 429 - Too many requests'))).toBe(true);
+    expect(isRetryableError(new Error('ChatGPT API Error: 503 Service Unavailable'))).toBe(true);
+    expect(isRetryableError(new Error('Claude Request Error: connect ECONNREFUSED'))).toBe(true);
+    expect(isRetryableError(new Error('Request timed out.'))).toBe(true);
+  });
+
+  test('should retry connection errors, which carry no status code', () => {
+    const error = Object.assign(new Error('ChatGPT API Error: Connection error.'), { statusCode: undefined });
+
+    expect(isRetryableError(error)).toBe(true);
+  });
+
+  test('should decide on the status code when there is one', () => {
+    expect(isRetryableError(Object.assign(new Error('ChatGPT API Error: overloaded'), { statusCode: 529 }))).toBe(true);
+    expect(isRetryableError(Object.assign(new Error('ChatGPT API Error: quota'), { statusCode: 429 }))).toBe(true);
+    expect(isRetryableError(Object.assign(new Error('Local Model Error: 404 - model timed out loading'), { statusCode: 404 })))
+      .toBe(false);
+  });
+
+  test('should not retry client errors', () => {
+    expect(isRetryableError(new Error('Grok API Error: 401 - Unauthorized'))).toBe(false);
+    expect(isRetryableError(new Error('Claude API Error: 400 - max_tokens must be at most 500'))).toBe(false);
+  });
+});
+
+describe('FailoverProvider', () => {
+  test('should validate its arguments', () => {
+    expect(() => new FailoverProvider([])).toThrow('at least one provider');
+    expect(() => new FailoverProvider([new MockAIProvider()], { mode: 'random' })).toThrow('Unsupported failover mode: random');
+  });
+
+  describe('failover mode', () => {
+    test('should fall back on retryable errors and report who served', async () => {
+      const primary = new MockAIProvider().failNext('Grok API Error: 503 - overloaded');
+      const secondary = new MockAIProvider({ defaultResponse: 'from secondary' });
+      const failover = new FailoverProvider([
+        { name: 'grok', client: primary },
+        { name: 'claude', client: secondary },
+      ]);
+
+      expect(await failover.chat('Hi')).toBe('from secondary');
+      expect(failover.lastServedBy).toBe('claude');
+
+      const [grok, claude] = failover.getHealth();
+      expect(grok).toMatchObject({ name: 'grok', healthy: false, failures: 1, lastError: 'Grok API Error: 503 - overloaded' });
+      expect(claude).toMatchObject({ name: 'claude', healthy: true, served: 1 });
+    });
+
+    test('should skip benched providers until the cooldown passes', async () => {
+      const primary = new MockAIProvider({ defaultResponse: 'primary' }).failNext('429 rate limited');
+      const secondary = new MockAIProvider({ defaultResponse: 'secondary' });
+      const failover = new FailoverProvider([primary, secondary], { cooldown: 20 });
+
+      await failover.chat('one');
+      expect(await failover.chat('two')).toBe('secondary');
+      expect(primary.calls).toHaveLength(1);
+
+      await new Promise((resolve) => setTimeout(resolve, 30));
+      expect(await failover.chat('three')).toBe('primary');
+      expect(failover.getHealth()[0]).toMatchObject({ healthy: true, failures: 0 });
+    });
+
+    test('should throw client errors without trying other providers', async () => {
+      const secondary = new MockAIProvider();
+      const failover = new FailoverProvider([new MockAIProvider().failNext('Grok API Error: 401 - bad key'), secondary]);
+
+      await expect(failover.chat('Hi')).rejects.toThrow('401 - bad key');
+      expect(secondary.calls).toHaveLength(0);
+    });
+
+    test('should report every failure when all providers fail', async () => {
+      const failover = new FailoverProvider([
+        { name: 'a', client: new MockAIProvider().failNext('500 boom') },
+        { name: 'b', client: new MockAIProvider().failNext('socket hang up') },
+      ]);
+
+      await expect(failover.conversation([])).rejects.toThrow('All providers failed: a: 500 boom; b: socket hang up');
+    });
+
+    test('should give up on a provider after its timeout and try the next', async () => {
+      let hungSignal;
+      const hung = new MockAIProvider().reply((messages, options) => new Promise(() => {
+        hungSignal = options.signal;
+      }));
+      const failover = new FailoverProvider([
+        { name: 'hung', client: hung, timeout: 20 },
+        { name: 'backup', client: new MockAIProvider({ defaultResponse: 'backup' }) },
+      ]);
+
+      expect(await failover.chat('Hi')).toBe('backup');
+      expect(hungSignal.aborted).toBe(true);
+      expect(failover.getHealth()[0]).toMatchObject({ healthy: false, failures: 1, lastError: 'hung timed out after 20ms' });
+    });
+  });
+
+  describe('load balancing', () => {
+    test('should rotate providers in round-robin mode', async () => {
+      const failover = new FailoverProvider([
+        { name: 'a', client: new MockAIProvider({ defaultResponse: 'a' }) },
+        { name: 'b', client: new MockAIProvider({ defaultResponse: 'b' }) },
+      ], { mode: 'round-robin' });
+
+      const answers = [];
+      for (let i = 0; i < 4; i++) {
+        answers.push(await failover.chat('Hi'));
+      }
+      expect(answers).toEqual(['a', 'b', 'a', 'b']);
+    });
+
+    test('should pick providers by weight in weighted mode', async () => {
+      const rolls = [0.1, 0.5, 0.9];
+      const failover = new FailoverProvider([
+        { name: 'small', client: new MockAIProvider({ defaultResponse: 'small' }), weight: 1 },
+        { name: 'large', client: new MockAIProvider({ defaultResponse: 'large' }), weight: 3 },
+      ], { mode: 'weighted', random: () => rolls.shift() });
+
+      expect(await failover.chat('Hi')).toBe('small');
+      expect(await failover.chat('Hi')).toBe('large');
+      expect(await failover.chat('Hi')).toBe('large');
+    });
+
+    test('should return the first accepted response in race mode', async () => {
+      const slow = new MockAIProvider().reply(() => new Promise((resolve) => setTimeout(() => resolve('slow'), 40)));
+      const fast = new MockAIProvider().reply(() => new Promise((resolve) => setTimeout(() => resolve('fast'), 5)));
+      const failover = new FailoverProvider([
+        { name: 'slow', client: slow },
+        { name: 'fast', client: fast },
+        { name: 'spare', client: new MockAIProvider() },
+      ], { mode: 'race' });
+
+      expect(await failover.chat('Hi')).toBe('fast');
+      expect(failover.lastServedBy).toBe('fast');
+      expect(slow.calls).toHaveLength(1);
+      expect(failover.providers[2].client.calls).toHaveLength(0);
+    });
+
+    test('should cancel the losing requests in race mode', async () => {
+      let loserSignal;
+      const slow = new MockAIProvider().reply((messages, options) => new Promise((resolve, reject) => {
+        loserSignal = options.signal;
+        options.signal.addEventListener('abort', () => reject(new Error('Grok Request Error: aborted')));
+      }));
+      const failover = new FailoverProvider([
+        { name: 'slow', client: slow },
+        { name: 'fast', client: new MockAIProvider({ defaultResponse: 'fast' }) },
+      ], { mode: 'race' });
+
+      expect(await failover.chat('Hi')).toBe('fast');
+      await new Promise((resolve) => setImmediate(resolve));
+
+      expect(loserSignal.aborted).toBe(true);
+      expect(failover.getHealth()[0]).toMatchObject({ healthy: true, failures: 0, lastError: null });
+    });
+
+    test('should skip responses that fail the accept check', async () => {
+      const failover = new FailoverProvider([
+        { name: 'a', client: new MockAIProvider({ defaultResponse: 'UNSAFE' }) },
+        { name: 'b', client: new MockAIProvider().reply(() => new Promise((resolve) => setTimeout(() => resolve('ok'), 10))) },
+      ], { mode: 'race', accept: (result) => result !== 'UNSAFE' });
+
+      expect(await failover.chat('Hi')).toBe('ok');
+      expect(failover.lastServedBy).toBe('b');
+    });
+
+    test('should fail when no raced provider succeeds', async () => {
+      const failover = new FailoverProvider([
+        { name: 'a', client: new MockAIProvider().failNext('503 down') },
+        { name: 'b', client: new MockAIProvider({ defaultResponse: 'bad' }) },
+      ], { mode: 'race', accept: () => false });
+
+      await expect(failover.chat('Hi')).rejects.toThrow(/All providers failed: (a: 503 down; b: response rejected|b: response rejected; a: 503 down)/);
+      expect(failover.getHealth()[0].healthy).toBe(false);
+    });
+
+    test('should pass tool calls through', async () => {
+      const client = new MockAIProvider({ defaultResponse: 'done' });
+      const failover = new FailoverProvider([client]);
+      const tools = { definitions: () => [], execute: jest.fn() };
+
+      const result = await failover.chatWithTools([{ role: 'user', content: 'Go' }], tools);
+
+      expect(result.content).toBe('done');
+      expect(client.calls[0].method).toBe('chatWithTools');
+    });
+  });
+});
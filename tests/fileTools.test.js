@@ -0,0 +1,56 @@
+const fs = require('fs');
+const os = require('os');
+const path = require('path');
+const { fileTools } = require('../src/fileTools');
+
+describe('fileTools', () => {
+  let dir;
+  let root;
+  let tools;
+
+  beforeEach(() => {
+    dir = fs.mkdtempSync(path.join(os.tmpdir(), 'file-tools-'));
+    root = path.join(dir, 'workspace');
+    fs.mkdirSync(root);
+    fs.writeFileSync(path.join(dir, 'secret.md'), 'outside');
+    fs.writeFileSync(path.join(root, 'notes.md'), 'hello');
+    tools = Object.fromEntries(fileTools({ root, extensions: ['.md', '.json'], maxBytes: 50 }).map((tool) => [tool.name, tool]));
+  });
+
+  afterEach(() => {
+    fs.rmSync(dir, { recursive: true, force: true });
+  });
+
+  test('should require a root and allow read-only use', () => {
+    expect(() => fileTools()).toThrow('requires a root directory');
+    expect(fileTools({ root, readOnly: true }).map((tool) => tool.name)).toEqual(['read_file', 'list_files']);
+  });
+
+  test('should read, write and list files under the root', async () => {
+    expect(await tools.read_file.handler({ path: 'notes.md' })).toBe('hello');
+    expect(await tools.write_file.handler({ path: 'out/result.json', content: '{}' })).toEqual({ path: path.join('out', 'result.json'), bytes: 2 });
+    expect(fs.readFileSync(path.join(root, 'out', 'result.json'), 'utf8')).toBe('{}');
+    expect(await tools.list_files.handler({})).toEqual([
+      { name: 'notes.md', type: 'file' },
+      { name: 'out', type: 'directory' },
+    ]);
+  });
+
+  test('should refuse paths outside the root, including through links', async () => {
+    fs.symlinkSync(dir, path.join(root, 'escape'));
+
+    await expect(tools.read_file.handler({ path: '../secret.md' })).rejects.toThrow('outside the sandbox');
+    await expect(tools.read_file.handler({ path: path.join(dir, 'secret.md') })).rejects.toThrow('outside the sandbox');
+    await expect(tools.read_file.handler({ path: 'escape/secret.md' })).rejects.toThrow('outside the sandbox');
+    await expect(tools.write_file.handler({ path: 'escape/new.md', content: 'x' })).rejects.toThrow('outside the sandbox');
+    expect(fs.existsSync(path.join(dir, 'new.md'))).toBe(false);
+  });
+
+  test('should enforce the extension and size policies', async () => {
+    fs.writeFileSync(path.join(root, 'big.md'), 'x'.repeat(100));
+
+    await expect(tools.write_file.handler({ path: 'run.sh', content: 'rm -rf /' })).rejects.toThrow('File type .sh is not allowed');
+    await expect(tools.read_file.handler({ path: 'big.md' })).rejects.toThrow('over the 50 byte limit');
+    await expect(tools.write_file.handler({ path: 'big.json', content: 'x'.repeat(51) })).rejects.toThrow('over the 50 byte limit');
+  });
+});
@@ -0,0 +1,206 @@
+const crypto = require('crypto');
+const GitHub = require('../src/github');
+
+jest.mock('https');
+
+function mockResponses(...responses) {
+  const https = require('https');
+  const requests = [];
+
+  https.request = jest.fn((options, cb) => {
+    const [statusCode, body, headers = {}] = responses.shift();
+    const request = { options, body: null };
+    requests.push(request);
+
+    cb({
+      statusCode,
+      headers,
+      on: jest.fn((event, handler) => {
+        if (event === 'data') handler(JSON.stringify(body));
+        if (event === 'end') handler();
+      }),
+    });
+
+    return {
+      write: jest.fn((data) => { request.body = JSON.parse(data); }),
+      end: jest.fn(),
+      on: jest.fn(),
+    };
+  });
+
+  return requests;
+}
+
+describe('GitHub', () => {
+  const savedEnv = { ...process.env };
+
+  beforeEach(() => {
+    delete process.env.GITHUB_TOKEN;
+    delete process.env.GITHUB_APP_ID;
+    delete process.env.GITHUB_APP_PRIVATE_KEY;
+    delete process.env.GITHUB_APP_INSTALLATION_ID;
+  });
+
+  afterAll(() => {
+    process.env = savedEnv;
+  });
+
+  describe('Constructor', () => {
+    test('should throw error when no credentials are provided', () => {
+      expect(() => new GitHub()).toThrow('GitHub credentials are required');
+    });
+
+    test('should use GITHUB_TOKEN from the environment', () => {
+      process.env.GITHUB_TOKEN = 'env-token';
+      const github = new GitHub();
+      expect(github.token).toBe('env-token');
+      expect(github.baseUrl).toBe('https://api.github.com');
+    });
+
+    test('should accept GitHub App credentials', () => {
+      const github = new GitHub(null, { app: { appId: 1, privateKey: 'line1\\nline2', installationId: 2 } });
+      expect(github.token).toBeNull();
+      expect(github.app.privateKey).toBe('line1\nline2');
+    });
+  });
+
+  describe('issues and pull requests', () => {
+    test('should list issues with query filters', async () => {
+      const requests = mockResponses([200, [{ number: 1 }]]);
+      const github = new GitHub('token');
+
+      const issues = await github.listIssues('octo', 'repo', { state: 'open', labels: ['bug', 'ui'] });
+
+      expect(issues).toEqual({ items: [{ number: 1 }], nextCursor: null });
+      expect(requests[0].options.path).toBe('/repos/octo/repo/issues?state=open&labels=bug%2Cui&per_page=50&page=1');
+      expect(requests[0].options.headers.Authorization).toBe('Bearer token');
+      expect(requests[0].options.headers['User-Agent']).toBe('ai-time-machines');
+    });
+
+    test('should page through pull requests with cursors', async () => {
+      const link = '<https://api.github.com/repos/octo/repo/pulls?per_page=2&page=2>; rel="next", '
+        + '<https://api.github.com/repos/octo/repo/pulls?per_page=2&page=2>; rel="last"';
+      const requests = mockResponses([200, [{ number: 1 }, { number: 2 }], { link }], [200, [{ number: 3 }]]);
+      const github = new GitHub('token');
+
+      const first = await github.listPullRequests('octo', 'repo', { limit: 2 });
+      const second = await github.listPullRequests('octo', 'repo', { limit: 2, cursor: first.nextCursor });
+
+      expect(first.nextCursor).toEqual(expect.any(String));
+      expect(second).toEqual({ items: [{ number: 3 }], nextCursor: null });
+      expect(requests[1].options.path).toBe('/repos/octo/repo/pulls?per_page=2&page=2');
+    });
+
+    test('should end on a full last page when GitHub sends no next link', async () => {
+      mockResponses([200, [{ number: 1 }, { number: 2 }], { link: '<https://api.github.com/repos/octo/repo/pulls?per_page=2&page=1>; rel="prev"' }]);
+
+      const page = await new GitHub('token').listPullRequests('octo', 'repo', { limit: 2, cursor: null });
+
+      expect(page.nextCursor).toBeNull();
+    });
+
+    test('should create issues and comments', async () => {
+      const requests = mockResponses([201, { number: 5 }], [201, { id: 9 }]);
+      const github = new GitHub('token');
+
+      await github.createIssue('octo', 'repo', { title: 'Bug', labels: ['bug'] });
+      await github.comment('octo', 'repo', 5, 'Looking into it');
+
+      expect(requests[0].options.method).toBe('POST');
+      expect(requests[0].body).toEqual({ title: 'Bug', labels: ['bug'] });
+      expect(requests[1].options.path).toBe('/repos/octo/repo/issues/5/comments');
+      expect(requests[1].body).toEqual({ body: 'Looking into it' });
+    });
+
+    test('should encode owner, repository and number in paths', async () => {
+      const requests = mockResponses([201, { id: 9 }], [200, []]);
+      const github = new GitHub('token');
+
+      await github.comment('octo', '../orgs/x', '5/../1', 'Hi');
+      await github.listIssues('o?ct#o', 'repo');
+
+      expect(requests[0].options.path).toBe('/repos/octo/..%2Forgs%2Fx/issues/5%2F..%2F1/comments');
+      expect(requests[1].options.path).toBe('/repos/o%3Fct%23o/repo/issues?per_page=50&page=1');
+    });
+
+    test('should require an issue title', async () => {
+      await expect(new GitHub('token').createIssue('octo', 'repo', {})).rejects.toThrow('Issue title is required');
+    });
+
+    test('should reject on API errors', async () => {
+      mockResponses([404, { message: 'Not Found' }]);
+      await expect(new GitHub('token').listPullRequests('octo', 'missing')).rejects.toThrow('GitHub API Error: 404 - Not Found');
+    });
+  });
+
+  describe('repository content', () => {
+    test('should read and decode a file', async () => {
+      const requests = mockResponses([200, {
+        type: 'file', path: 'docs/read me.md', sha: 'abc', size: 5, encoding: 'base64',
+        content: Buffer.from('hello').toString('base64'),
+      }]);
+
+      const file = await new GitHub('token').getFile('octo', 'repo', 'docs/read me.md', { ref: 'main' });
+
+      expect(file).toEqual({ path: 'docs/read me.md', sha: 'abc', size: 5, content: 'hello' });
+      expect(requests[0].options.path).toBe('/repos/octo/repo/contents/docs/read%20me.md?ref=main');
+    });
+
+    test('should reject directories', async () => {
+      mockResponses([200, [{ path: 'docs/a.md' }]]);
+      await expect(new GitHub('token').getFile('octo', 'repo', 'docs')).rejects.toThrow('GitHub path is not a file: docs');
+    });
+
+    test('should list files under a prefix', async () => {
+      mockResponses([200, {
+        tree: [
+          { path: 'docs', type: 'tree', sha: '1' },
+          { path: 'docs/a.md', type: 'blob', sha: '2', size: 10 },
+          { path: 'src/index.js', type: 'blob', sha: '3', size: 20 },
+        ],
+      }]);
+
+      const files = await new GitHub('token').listFiles('octo', 'repo', { prefix: 'docs/' });
+      expect(files).toEqual([{ path: 'docs/a.md', sha: '2', size: 10 }]);
+    });
+
+    test('should reject trees GitHub truncated', async () => {
+      mockResponses([200, { tree: [{ path: 'a.md', type: 'blob', sha: '1', size: 1 }], truncated: true }]);
+
+      await expect(new GitHub('token').listFiles('octo', 'repo', { ref: 'main' }))
+        .rejects.toThrow('GitHub tree for octo/repo at main is too large to list in one request');
+    });
+  });
+
+  describe('GitHub App authentication', () => {
+    test('should exchange a signed JWT for an installation token and reuse it', async () => {
+      const { privateKey, publicKey } = crypto.generateKeyPairSync('rsa', {
+        modulusLength: 2048,
+        privateKeyEncoding: { type: 'pkcs8', format: 'pem' },
+        publicKeyEncoding: { type: 'spki', format: 'pem' },
+      });
+      const expiresAt = new Date(Date.now() + 3600000).toISOString();
+      const requests = mockResponses(
+        [201, { token: 'installation-token', expires_at: expiresAt }],
+        [200, []],
+        [200, []]
+      );
+
+      const github = new GitHub(null, { app: { appId: 42, privateKey, installationId: 7 } });
+      await github.listIssues('octo', 'repo');
+      await github.listPullRequests('octo', 'repo');
+
+      expect(requests).toHaveLength(3);
+      expect(requests[0].options.path).toBe('/app/installations/7/access_tokens');
+
+      const jwt = requests[0].options.headers.Authorization.replace('Bearer ', '');
+      const [header, payload, signature] = jwt.split('.');
+      const valid = crypto.createVerify('RSA-SHA256').update(`${header}.${payload}`).verify(publicKey, signature, 'base64url');
+      expect(valid).toBe(true);
+      expect(JSON.parse(Buffer.from(payload, 'base64url').toString()).iss).toBe('42');
+
+      expect(requests[1].options.headers.Authorization).toBe('Bearer installation-token');
+      expect(requests[2].options.headers.Authorization).toBe('Bearer installation-token');
+    });
+  });
+});
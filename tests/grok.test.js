@@ -88,6 +88,28 @@ describe('Grok', () => {
       expect(response).toBe('Grok response');
     });
 
+    test('should pass an abort signal to the HTTP request', async () => {
+      const https = require('https');
+      const mockReq = { write: jest.fn(), end: jest.fn(), on: jest.fn() };
+      const mockRes = {
+        statusCode: 200,
+        on: jest.fn((event, cb) => {
+          if (event === 'data') cb(JSON.stringify({ choices: [{ message: { content: 'ok' } }] }));
+          if (event === 'end') cb();
+        }),
+      };
+      https.request = jest.fn((options, cb) => {
+        cb(mockRes);
+        return mockReq;
+      });
+      const controller = new AbortController();
+
+      await new Grok('test-key').chat('Hello', { signal: controller.signal });
+
+      expect(https.request.mock.calls[0][0].signal).toBe(controller.signal);
+      expect(JSON.parse(mockReq.write.mock.calls[0][0]).signal).toBeUndefined();
+    });
+
     test('should reject on API error', async () => {
       const https = require('https');
 
@@ -112,6 +134,7 @@ describe('Grok', () => {
 
       const grok = new Grok('invalid-key');
       await expect(grok.chat('Hello')).rejects.toThrow('Grok API Error');
+      await expect(grok.chat('Hello')).rejects.toMatchObject({ statusCode: 401 });
     });
   });
 
@@ -0,0 +1,96 @@
+const http = require('http');
+const { httpTool, matchHost } = require('../src/httpTool');
+
+describe('httpTool', () => {
+  let server;
+  let base;
+  let requests;
+
+  beforeEach(async () => {
+    requests = [];
+    server = http.createServer((req, res) => {
+      let body = '';
+      req.on('data', (chunk) => {
+        body += chunk;
+      });
+      req.on('end', () => {
+        requests.push({ method: req.method, url: req.url, headers: req.headers, body });
+        if (req.url === '/big') {
+          res.end('x'.repeat(1000));
+          return;
+        }
+        if (req.url === '/redirect') {
+          res.writeHead(302, { Location: 'http://elsewhere.example/' });
+          res.end();
+          return;
+        }
+        res.writeHead(200, { 'Content-Type': 'application/json' });
+        res.end(JSON.stringify({ ok: true }));
+      });
+    });
+    await new Promise((resolve) => server.listen(0, '127.0.0.1', resolve));
+    base = `http://127.0.0.1:${server.address().port}`;
+  });
+
+  afterEach(() => {
+    server.close();
+  });
+
+  test('should match exact hosts and subdomain wildcards', () => {
+    expect(matchHost('API.example.com', ['api.example.com'])).toBe('api.example.com');
+    expect(matchHost('a.status.example', ['*.status.example'])).toBe('*.status.example');
+    expect(matchHost('status.example', ['*.status.example'])).toBeNull();
+    expect(matchHost('evilstatus.example', ['*.status.example'])).toBeNull();
+  });
+
+  test('should require an allow-list', () => {
+    expect(() => httpTool()).toThrow('at least one allowed host');
+  });
+
+  test('should send requests to allowed hosts with templated auth', async () => {
+    const tool = httpTool({
+      allowedHosts: ['127.0.0.1'],
+      allowHttp: true,
+      auth: { '127.0.0.1': { Authorization: 'Bearer {{API_TOKEN}}' } },
+      secrets: { API_TOKEN: 'secret' },
+    });
+
+    const result = await tool.handler({ method: 'POST', url: `${base}/items?x=1`, body: { name: 'a' } });
+
+    expect(result).toEqual({ status: 200, contentType: 'application/json', location: null, body: '{"ok":true}', truncated: false });
+    expect(requests[0]).toMatchObject({ method: 'POST', url: '/items?x=1', body: '{"name":"a"}' });
+    expect(requests[0].headers.authorization).toBe('Bearer secret');
+  });
+
+  test('should refuse hosts, protocols and methods outside the policy', async () => {
+    const tool = httpTool({ allowedHosts: ['127.0.0.1'] });
+
+    await expect(tool.handler({ method: 'GET', url: `${base}/` })).rejects.toThrow('Protocol http: is not allowed');
+    await expect(tool.handler({ method: 'GET', url: 'https://example.com/' })).rejects.toThrow('Host example.com is not allowed');
+    await expect(tool.handler({ method: 'DELETE', url: 'https://127.0.0.1/' })).rejects.toThrow('Unsupported method: DELETE');
+    expect(requests).toHaveLength(0);
+  });
+
+  test('should cap responses and not follow redirects', async () => {
+    const tool = httpTool({ allowedHosts: ['127.0.0.1'], allowHttp: true, maxResponseBytes: 100 });
+
+    const big = await tool.handler({ method: 'GET', url: `${base}/big` });
+    const redirect = await tool.handler({ method: 'GET', url: `${base}/redirect` });
+
+    expect(big).toMatchObject({ body: 'x'.repeat(100), truncated: true });
+    expect(redirect).toMatchObject({ status: 302, location: 'http://elsewhere.example/' });
+    expect(requests).toHaveLength(2);
+  });
+
+  test('should refuse to send requests with missing secrets', async () => {
+    const tool = httpTool({
+      allowedHosts: ['127.0.0.1'],
+      allowHttp: true,
+      auth: { '127.0.0.1': { Authorization: 'Bearer {{MISSING_TOKEN}}' } },
+      secrets: {},
+    });
+
+    await expect(tool.handler({ method: 'GET', url: `${base}/` })).rejects.toThrow('Missing secret MISSING_TOKEN for HTTP tool auth');
+    expect(requests).toHaveLength(0);
+  });
+});
@@ -0,0 +1,51 @@
+const { MAX_IMAGE_BYTES, detectMimeType, imagePart, visionMessage } = require('../src/images');
+
+const PNG = Buffer.from([0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00]);
+const JPEG = Buffer.from([0xff, 0xd8, 0xff, 0xe0]);
+
+describe('images', () => {
+  describe('detectMimeType', () => {
+    test('should recognize common formats', () => {
+      expect(detectMimeType(PNG)).toBe('image/png');
+      expect(detectMimeType(JPEG)).toBe('image/jpeg');
+      expect(detectMimeType(Buffer.from('GIF89a...'))).toBe('image/gif');
+      expect(detectMimeType(Buffer.from('RIFF\0\0\0\0WEBPVP8 '))).toBe('image/webp');
+      expect(detectMimeType(Buffer.from('hello'))).toBeNull();
+    });
+  });
+
+  describe('imagePart', () => {
+    test('should pass URLs through', () => {
+      expect(imagePart('https://images.example/a.png')).toEqual({
+        type: 'image_url',
+        image_url: { url: 'https://images.example/a.png' },
+      });
+      expect(() => imagePart('/tmp/a.png')).toThrow('must be http(s) URLs or image data URLs');
+    });
+
+    test('should base64-encode bytes into a data URL', () => {
+      const part = imagePart(PNG, { detail: 'high' });
+      expect(part.image_url).toEqual({ url: `data:image/png;base64,${PNG.toString('base64')}`, detail: 'high' });
+
+      expect(imagePart({ data: Buffer.from('raw'), mimeType: 'image/jpeg' }).image_url.url)
+        .toBe(`data:image/jpeg;base64,${Buffer.from('raw').toString('base64')}`);
+    });
+
+    test('should validate size and format', () => {
+      expect(() => imagePart(Buffer.from('not an image'))).toThrow('Unsupported image type: unknown');
+      expect(() => imagePart({ data: PNG, mimeType: 'image/tiff' })).toThrow('Unsupported image type: image/tiff');
+      expect(() => imagePart(Buffer.alloc(MAX_IMAGE_BYTES + 1))).toThrow('the limit is');
+      expect(() => imagePart(42)).toThrow('Images must be a URL, a Buffer');
+    });
+  });
+
+  describe('visionMessage', () => {
+    test('should combine text and images in a user message', () => {
+      const message = visionMessage('Compare these', ['https://a.example/1.png', JPEG]);
+
+      expect(message.role).toBe('user');
+      expect(message.content.map((part) => part.type)).toEqual(['text', 'image_url', 'image_url']);
+      expect(message.content[2].image_url.url.startsWith('data:image/jpeg;base64,')).toBe(true);
+    });
+  });
+});
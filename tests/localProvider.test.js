@@ -0,0 +1,142 @@
+const LocalProvider = require('../src/localProvider');
+const { ToolRegistry } = require('../src/tools');
+
+jest.mock('http');
+
+const mockHttp = (statusCode, body) => {
+  const http = require('http');
+  const captured = {};
+  const mockReq = {
+    write: jest.fn((data) => { captured.body = JSON.parse(data); }),
+    end: jest.fn(),
+    on: jest.fn(),
+  };
+  const mockRes = {
+    statusCode,
+    on: jest.fn((event, cb) => {
+      if (event === 'data') cb(JSON.stringify(body));
+      if (event === 'end') cb();
+    }),
+  };
+
+  http.request = jest.fn((options, cb) => {
+    captured.options = options;
+    cb(mockRes);
+    return mockReq;
+  });
+  return captured;
+};
+
+describe('LocalProvider', () => {
+  let originalEnv;
+
+  beforeEach(() => {
+    originalEnv = process.env.LOCAL_MODEL_URL;
+    delete process.env.LOCAL_MODEL_URL;
+    jest.clearAllMocks();
+  });
+
+  afterEach(() => {
+    if (originalEnv === undefined) {
+      delete process.env.LOCAL_MODEL_URL;
+    } else {
+      process.env.LOCAL_MODEL_URL = originalEnv;
+    }
+  });
+
+  describe('Constructor', () => {
+    test('should default to a local Ollama server without an API key', () => {
+      const local = new LocalProvider();
+      expect(local.baseUrl).toBe('http://localhost:11434');
+      expect(local.flavor).toBe('ollama');
+      expect(local.model).toBe('llama3');
+    });
+
+    test('should use LOCAL_MODEL_URL and strip trailing slashes', () => {
+      process.env.LOCAL_MODEL_URL = 'http://gpu-box:8000/';
+      expect(new LocalProvider().baseUrl).toBe('http://gpu-box:8000');
+    });
+
+    test('should reject unknown flavors', () => {
+      expect(() => new LocalProvider(null, { flavor: 'tgi' })).toThrow('Unsupported local server flavor: tgi');
+    });
+  });
+
+  describe('chat', () => {
+    test('should call the OpenAI-compatible completions endpoint', async () => {
+      const captured = mockHttp(200, { choices: [{ message: { content: 'Local response' } }] });
+
+      const local = new LocalProvider(null, { model: 'mistral' });
+      const response = await local.chat('Hello');
+
+      expect(response).toBe('Local response');
+      expect(captured.options).toMatchObject({ hostname: 'localhost', port: '11434', path: '/v1/chat/completions' });
+      expect(captured.options.headers.Authorization).toBeUndefined();
+      expect(captured.body).toMatchObject({ model: 'mistral', messages: [{ role: 'user', content: 'Hello' }] });
+    });
+
+    test('should send a bearer token when configured', async () => {
+      const captured = mockHttp(200, { choices: [{ message: { content: 'ok' } }] });
+
+      await new LocalProvider('http://localhost:8000', { flavor: 'openai', apiKey: 'secret' }).chat('Hi');
+
+      expect(captured.options.headers.Authorization).toBe('Bearer secret');
+    });
+
+    test('should reject on server error', async () => {
+      mockHttp(404, { error: 'model "llama9" not found, try pulling it first' });
+
+      const local = new LocalProvider(null, { model: 'llama9' });
+      await expect(local.chat('Hi')).rejects.toThrow('Local Model Error: 404 - model "llama9" not found');
+    });
+  });
+
+  describe('chatWithTools', () => {
+    test('should run the tool loop', async () => {
+      const local = new LocalProvider();
+      jest.spyOn(local, '_request')
+        .mockResolvedValueOnce({
+          choices: [{
+            message: {
+              role: 'assistant',
+              content: null,
+              tool_calls: [{ id: 'c1', type: 'function', function: { name: 'now', arguments: '{}' } }],
+            },
+          }],
+        })
+        .mockResolvedValueOnce({ choices: [{ message: { role: 'assistant', content: 'It is noon.' } }] });
+      const tools = new ToolRegistry().register({ name: 'now', handler: () => '12:00' });
+
+      const result = await local.chatWithTools([{ role: 'user', content: 'Time?' }], tools);
+
+      expect(result.content).toBe('It is noon.');
+      expect(local._request.mock.calls[1][2].messages[2]).toEqual({ role: 'tool', tool_call_id: 'c1', content: '12:00' });
+    });
+  });
+
+  describe('model management', () => {
+    test('should list Ollama models', async () => {
+      const captured = mockHttp(200, { models: [{ name: 'llama3:latest' }, { name: 'mistral:7b' }] });
+
+      expect(await new LocalProvider().listModels()).toEqual(['llama3:latest', 'mistral:7b']);
+      expect(captured.options.path).toBe('/api/tags');
+    });
+
+    test('should list models from OpenAI-compatible servers', async () => {
+      const captured = mockHttp(200, { data: [{ id: 'qwen2' }] });
+
+      expect(await new LocalProvider('http://localhost:8000', { flavor: 'openai' }).listModels()).toEqual(['qwen2']);
+      expect(captured.options.path).toBe('/v1/models');
+    });
+
+    test('should pull models on Ollama only', async () => {
+      const captured = mockHttp(200, { status: 'success' });
+
+      expect(await new LocalProvider().pullModel('mistral')).toEqual({ status: 'success' });
+      expect(captured.body).toEqual({ name: 'mistral', stream: false });
+
+      await expect(new LocalProvider(null, { flavor: 'openai' }).pullModel('mistral'))
+        .rejects.toThrow('only supported by Ollama');
+    });
+  });
+});
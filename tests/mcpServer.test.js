@@ -0,0 +1,144 @@
+const http = require('http');
+const McpServer = require('../src/mcpServer');
+const PolicyEngine = require('../src/policyEngine');
+const AuditLog = require('../src/auditLog');
+const { ToolRegistry } = require('../src/tools');
+
+function post(port, body, headers = { 'Content-Type': 'application/json' }) {
+  return new Promise((resolve, reject) => {
+    const req = http.request({ host: '127.0.0.1', port, path: '/', method: 'POST', headers }, (res) => {
+      let data = '';
+      res.on('data', (chunk) => {
+        data += chunk;
+      });
+      res.on('end', () => resolve({ statusCode: res.statusCode, body: data ? JSON.parse(data) : null }));
+    });
+    req.on('error', reject);
+    req.end(body);
+  });
+}
+
+describe('McpServer', () => {
+  let tools;
+
+  beforeEach(() => {
+    tools = new ToolRegistry()
+      .register({
+        name: 'add',
+        description: 'Add two numbers',
+        parameters: { type: 'object', properties: { a: { type: 'number' }, b: { type: 'number' } } },
+        handler: ({ a, b }) => ({ sum: a + b }),
+      })
+      .register({ name: 'fail', handler: () => { throw new Error('boom'); } });
+  });
+
+  test('should require a tools registry', () => {
+    expect(() => new McpServer()).toThrow('requires a tools registry');
+  });
+
+  test('should answer initialize and list tools', async () => {
+    const server = new McpServer({ tools });
+
+    const init = await server.handle({ jsonrpc: '2.0', id: 1, method: 'initialize', params: {} });
+    expect(init.result.capabilities).toEqual({ tools: {} });
+    expect(init.result.serverInfo.name).toBe('ai-time-machines');
+
+    expect(await server.handle({ jsonrpc: '2.0', method: 'notifications/initialized' })).toBeNull();
+
+    const list = await server.handle({ jsonrpc: '2.0', id: 2, method: 'tools/list' });
+    expect(list.result.tools[0]).toEqual({
+      name: 'add',
+      description: 'Add two numbers',
+      inputSchema: { type: 'object', properties: { a: { type: 'number' }, b: { type: 'number' } } },
+    });
+  });
+
+  test('should call tools and report failures as tool errors', async () => {
+    const server = new McpServer({ tools });
+
+    const ok = await server.handle({ jsonrpc: '2.0', id: 3, method: 'tools/call', params: { name: 'add', arguments: { a: 2, b: 3 } } });
+    expect(ok.result).toEqual({ content: [{ type: 'text', text: '{"sum":5}' }], isError: false });
+
+    const failed = await server.handle({ jsonrpc: '2.0', id: 4, method: 'tools/call', params: { name: 'fail' } });
+    expect(failed.result).toEqual({ content: [{ type: 'text', text: 'Error: boom' }], isError: true });
+
+    const unknown = await server.handle({ jsonrpc: '2.0', id: 5, method: 'tools/call', params: { name: 'shell' } });
+    expect(unknown.error).toEqual({ code: -32602, message: 'Unknown tool: shell' });
+  });
+
+  test('should return JSON-RPC errors for bad messages', async () => {
+    const server = new McpServer({ tools });
+
+    expect((await server.handle({ id: 1, method: 'ping' })).error.code).toBe(-32600);
+    expect((await server.handle({ jsonrpc: '2.0', id: 2, method: 'resources/list' })).error.code).toBe(-32601);
+    expect(await server.handle({ jsonrpc: '2.0', id: 3, method: 'ping' })).toEqual({ jsonrpc: '2.0', id: 3, result: {} });
+  });
+
+  test('should enforce the policy and audit calls', async () => {
+    const auditLog = new AuditLog();
+    const policy = new PolicyEngine({ rules: { deniedTools: ['add'] } });
+    const server = new McpServer({ tools, policy, auditLog });
+
+    const response = await server.handle({ jsonrpc: '2.0', id: 1, method: 'tools/call', params: { name: 'add', arguments: { a: 1, b: 1 } } });
+
+    expect(response.result.isError).toBe(true);
+    expect(response.result.content[0].text).toBe('Error: denied by policy: tool add is not allowed');
+    const { items: [entry] } = await auditLog.query({ component: 'mcp' });
+    expect(entry).toMatchObject({ action: 'tools/call', tags: ['add'], data: { tool: 'add', isError: true } });
+  });
+
+  test('should serve newline-delimited messages over stdio', async () => {
+    const server = new McpServer({ tools });
+    const written = [];
+
+    await server.serve([
+      '{"jsonrpc":"2.0","id":1,"method":"ping"}',
+      '',
+      'not json',
+      '{"jsonrpc":"2.0","method":"notifications/initialized"}',
+    ], { write: (chunk) => written.push(JSON.parse(chunk)) });
+
+    expect(written).toHaveLength(2);
+    expect(written).toContainEqual({ jsonrpc: '2.0', id: 1, result: {} });
+    expect(written).toContainEqual({ jsonrpc: '2.0', id: null, error: { code: -32700, message: 'Parse error' } });
+  });
+
+  test('should serve JSON-RPC over HTTP', async () => {
+    const server = await new McpServer({ tools }).listen(0);
+    const { port } = server.address();
+
+    try {
+      const call = await post(port, JSON.stringify({ jsonrpc: '2.0', id: 7, method: 'tools/call', params: { name: 'add', arguments: { a: 1, b: 2 } } }));
+      expect(call.statusCode).toBe(200);
+      expect(call.body.result.content[0].text).toBe('{"sum":3}');
+
+      const notification = await post(port, JSON.stringify({ jsonrpc: '2.0', method: 'notifications/initialized' }));
+      expect(notification.statusCode).toBe(202);
+    } finally {
+      server.close();
+    }
+  });
+
+  test('should reject cross-origin, unauthenticated and non-JSON HTTP requests', async () => {
+    const handler = jest.fn(() => ({ ok: true }));
+    tools.register({ name: 'effect', description: 'Side effect', parameters: { type: 'object', properties: {} }, handler });
+    const server = await new McpServer({ tools, token: 'secret', allowedOrigins: ['http://localhost:5173'] }).listen(0);
+    const { port } = server.address();
+    const call = JSON.stringify({ jsonrpc: '2.0', id: 1, method: 'tools/call', params: { name: 'effect' } });
+    const json = { 'Content-Type': 'application/json', Authorization: 'Bearer secret' };
+
+    try {
+      expect((await post(port, call, { ...json, Origin: 'https://evil.example' })).statusCode).toBe(403);
+      expect((await post(port, call, { 'Content-Type': 'application/json' })).statusCode).toBe(401);
+      expect((await post(port, call, { ...json, Authorization: 'Bearer wrong' })).statusCode).toBe(401);
+      expect((await post(port, call, { ...json, 'Content-Type': 'text/plain' })).statusCode).toBe(415);
+      expect(handler).not.toHaveBeenCalled();
+
+      expect((await post(port, call, { ...json, Origin: 'http://localhost:5173' })).statusCode).toBe(200);
+      expect((await post(port, call, json)).statusCode).toBe(200);
+      expect(handler).toHaveBeenCalledTimes(2);
+    } finally {
+      server.close();
+    }
+  });
+});
@@ -0,0 +1,91 @@
+const http = require('http');
+const Metrics = require('../src/metrics');
+
+describe('Metrics', () => {
+  const event = { provider: 'openai', operation: 'chat.completions', model: 'gpt-4' };
+
+  describe('observer hooks', () => {
+    test('should count requests and errors', () => {
+      const metrics = new Metrics();
+      metrics.onRequest(event);
+      metrics.onRequest(event);
+      metrics.onError({ ...event, latencyMs: 20, error: new Error('boom') });
+
+      const text = metrics.toPrometheus();
+      expect(text).toContain('ai_time_machines_requests_total{provider="openai",model="gpt-4"} 2');
+      expect(text).toContain('ai_time_machines_request_errors_total{provider="openai",model="gpt-4"} 1');
+    });
+
+    test('should accumulate token usage', () => {
+      const metrics = new Metrics();
+      const usage = { prompt_tokens: 10, completion_tokens: 4 };
+      metrics.onResponse({ ...event, latencyMs: 100, usage });
+      metrics.onResponse({ ...event, latencyMs: 100, usage });
+
+      const text = metrics.toPrometheus();
+      expect(text).toContain('ai_time_machines_tokens_total{provider="openai",model="gpt-4",type="prompt"} 20');
+      expect(text).toContain('ai_time_machines_tokens_total{provider="openai",model="gpt-4",type="completion"} 8');
+    });
+
+    test('should bucket latency into a histogram', () => {
+      const metrics = new Metrics({ buckets: [0.5, 1] });
+      metrics.onResponse({ ...event, latencyMs: 200 });
+      metrics.onResponse({ ...event, latencyMs: 800 });
+      metrics.onResponse({ ...event, latencyMs: 3000 });
+
+      const text = metrics.toPrometheus();
+      expect(text).toContain('ai_time_machines_request_duration_seconds_bucket{provider="openai",model="gpt-4",le="0.5"} 1');
+      expect(text).toContain('ai_time_machines_request_duration_seconds_bucket{provider="openai",model="gpt-4",le="1"} 2');
+      expect(text).toContain('ai_time_machines_request_duration_seconds_bucket{provider="openai",model="gpt-4",le="+Inf"} 3');
+      expect(text).toContain('ai_time_machines_request_duration_seconds_count{provider="openai",model="gpt-4"} 3');
+    });
+
+    test('should keep the most recent errors', () => {
+      const metrics = new Metrics({ maxRecentErrors: 2 });
+      ['first', 'second', 'third'].forEach((message) => metrics.onError({ ...event, error: new Error(message) }));
+
+      expect(metrics.recentErrors.map((error) => error.message)).toEqual(['second', 'third']);
+      expect(metrics.recentErrors[0]).toMatchObject({ provider: 'openai', model: 'gpt-4', operation: 'chat.completions' });
+    });
+  });
+
+  describe('toPrometheus', () => {
+    test('should include type annotations and honour the prefix', () => {
+      const metrics = new Metrics({ prefix: 'custom' });
+      const text = metrics.toPrometheus();
+      expect(text).toContain('# TYPE custom_requests_total counter');
+      expect(text).toContain('# TYPE custom_request_duration_seconds histogram');
+    });
+
+    test('should escape label values', () => {
+      const metrics = new Metrics();
+      metrics.onRequest({ provider: 'a"b', model: 'c' });
+      expect(metrics.toPrometheus()).toContain('provider="a\\"b"');
+    });
+  });
+
+  describe('listen', () => {
+    test('should serve metrics over HTTP', async () => {
+      const metrics = new Metrics();
+      metrics.onRequest(event);
+      const server = await metrics.listen(0);
+
+      try {
+        const { port } = server.address();
+        const body = await new Promise((resolve, reject) => {
+          http.get(`http://127.0.0.1:${port}/metrics`, (res) => {
+            let data = '';
+            res.on('data', (chunk) => {
+              data += chunk;
+            });
+            res.on('end', () => resolve(data));
+          }).on('error', reject);
+        });
+
+        expect(body).toContain('ai_time_machines_requests_total');
+      } finally {
+        server.close();
+      }
+    });
+  });
+});
@@ -0,0 +1,95 @@
+const Moderator = require('../src/moderator');
+const { MockAIProvider } = require('../src/testing');
+
+const classifier = async (text) => (
+  text.includes('attack')
+    ? { flagged: true, categories: ['violence'] }
+    : { flagged: false, categories: [] }
+);
+
+describe('Moderator', () => {
+  test('should require a classifier or a moderation client', () => {
+    expect(() => new Moderator()).toThrow('requires a client with moderate(text) or a classifier');
+    expect(() => new Moderator({ classifier, action: 'redact' })).toThrow('Unsupported moderation action: redact');
+  });
+
+  test('should use a client moderate method', async () => {
+    const client = { moderate: jest.fn().mockResolvedValue({ flagged: true, categories: ['hate'], scores: { hate: 0.9 } }) };
+    const verdict = await new Moderator({ client }).moderate('text');
+
+    expect(client.moderate).toHaveBeenCalledWith('text');
+    expect(verdict).toEqual({ flagged: true, categories: ['hate'], scores: { hate: 0.9 } });
+  });
+
+  describe('wrap', () => {
+    test('should block unsafe prompts before they reach the model', async () => {
+      const client = new MockAIProvider();
+      const moderated = new Moderator({ classifier }).wrap(client);
+
+      await expect(moderated.chat('plan an attack')).rejects.toThrow('Moderation blocked input: violence');
+      expect(client.calls).toHaveLength(0);
+    });
+
+    test('should block unsafe responses', async () => {
+      const client = new MockAIProvider({ responses: ['here is how to attack'] });
+      const moderated = new Moderator({ classifier }).wrap(client);
+
+      await expect(moderated.conversation([{ role: 'user', content: 'hello' }])).rejects.toThrow('Moderation blocked output');
+    });
+
+    test('should flag without blocking in flag mode', async () => {
+      const onFlag = jest.fn();
+      const client = new MockAIProvider({ responses: ['fine'] });
+      const moderated = new Moderator({ classifier, action: 'flag', onFlag }).wrap(client);
+
+      expect(await moderated.chat('attack plan for chess')).toBe('fine');
+      expect(onFlag).toHaveBeenCalledWith({
+        stage: 'input',
+        text: 'attack plan for chess',
+        verdict: { flagged: true, categories: ['violence'], scores: {} },
+      });
+    });
+
+    test('should check the latest user text of multimodal and tool conversations', async () => {
+      const client = new MockAIProvider();
+      const moderated = new Moderator({ classifier }).wrap(client, { output: false });
+      const messages = [
+        { role: 'user', content: 'hello' },
+        { role: 'assistant', content: 'hi' },
+        { role: 'user', content: [{ type: 'text', text: 'attack this' }, { type: 'image_url', image_url: { url: 'https://x/y.png' } }] },
+      ];
+
+      await expect(moderated.chatWithTools(messages, { definitions: () => [] })).rejects.toThrow('blocked input');
+    });
+
+    test('should keep the rest of the client reachable', () => {
+      const client = new MockAIProvider({ model: 'mock-1' });
+      const moderated = new Moderator({ classifier }).wrap(client);
+
+      moderated.setModel('mock-2');
+
+      expect(moderated.model).toBe('mock-2');
+      expect(client.model).toBe('mock-2');
+      expect(moderated.calls).toBe(client.calls);
+    });
+
+    test('should moderate streams', async () => {
+      const client = {
+        async *stream() {
+          yield 'launch the ';
+          yield 'attack';
+        },
+      };
+      const moderated = new Moderator({ classifier }).wrap(client);
+      const chunks = [];
+
+      await expect(moderated.stream([{ role: 'user', content: 'attack' }]).next()).rejects.toThrow('blocked input');
+      await expect((async () => {
+        for await (const chunk of moderated.stream([{ role: 'user', content: 'hello' }])) {
+          chunks.push(chunk);
+        }
+      })()).rejects.toThrow('blocked output');
+      expect(chunks).toEqual(['launch the ', 'attack']);
+    });
+  });
+});
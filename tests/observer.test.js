@@ -0,0 +1,80 @@
+const { observe } = require('../src/observer');
+const ChatGPT = require('../src/chatgpt');
+
+jest.mock('openai');
+
+describe('observe', () => {
+  const context = { provider: 'openai', operation: 'chat.completions', model: 'gpt-4' };
+
+  test('should run the task without an observer', async () => {
+    const result = await observe(null, context, async () => 'value');
+    expect(result).toBe('value');
+  });
+
+  test('should report request and response events', async () => {
+    const observer = {
+      onRequest: jest.fn(),
+      onResponse: jest.fn(),
+    };
+
+    const usage = { prompt_tokens: 10, completion_tokens: 5, total_tokens: 15 };
+    await observe(observer, context, async () => ({ usage }));
+
+    expect(observer.onRequest).toHaveBeenCalledWith(expect.objectContaining(context));
+    const event = observer.onResponse.mock.calls[0][0];
+    expect(event).toMatchObject({ ...context, usage });
+    expect(typeof event.latencyMs).toBe('number');
+  });
+
+  test('should report errors and rethrow them', async () => {
+    const observer = { onError: jest.fn() };
+    const failure = new Error('timeout');
+
+    await expect(observe(observer, context, async () => {
+      throw failure;
+    })).rejects.toThrow('timeout');
+
+    expect(observer.onError).toHaveBeenCalledWith(expect.objectContaining({ ...context, error: failure }));
+  });
+
+  test('should ignore failures inside observer hooks', async () => {
+    const observer = {
+      onResponse: () => {
+        throw new Error('telemetry down');
+      },
+    };
+    jest.spyOn(console, 'error').mockImplementation(() => {});
+
+    const result = await observe(observer, context, async () => 'ok');
+
+    expect(result).toBe('ok');
+    console.error.mockRestore();
+  });
+
+  test('should observe ChatGPT completions', async () => {
+    const OpenAI = require('openai');
+    const usage = { prompt_tokens: 3, completion_tokens: 7, total_tokens: 10 };
+
+    OpenAI.mockImplementation(() => ({
+      chat: {
+        completions: {
+          create: jest.fn().mockResolvedValue({
+            choices: [{ message: { content: 'Observed' } }],
+            usage,
+          }),
+        },
+      },
+    }));
+
+    const observer = { onResponse: jest.fn() };
+    const chatgpt = new ChatGPT('test-key', { observer });
+    const response = await chatgpt.chat('Hello');
+
+    expect(response).toBe('Observed');
+    expect(observer.onResponse).toHaveBeenCalledWith(expect.objectContaining({
+      provider: 'openai',
+      model: 'gpt-4',
+      usage,
+    }));
+  });
+});
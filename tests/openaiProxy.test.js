@@ -0,0 +1,147 @@
+const http = require('http');
+const OpenAIProxy = require('../src/openaiProxy');
+const { MockAIProvider } = require('../src/testing');
+
+const AUTH = { Authorization: 'Bearer secret', 'Content-Type': 'application/json' };
+
+function request(port, method, path, body, headers = AUTH) {
+  return new Promise((resolve, reject) => {
+    const req = http.request({ host: '127.0.0.1', port, path, method, headers }, (res) => {
+      let data = '';
+      res.on('data', (chunk) => {
+        data += chunk;
+      });
+      res.on('end', () => resolve({ statusCode: res.statusCode, headers: res.headers, text: data }));
+    });
+    req.on('error', reject);
+    req.end(body === undefined ? undefined : JSON.stringify(body));
+  });
+}
+
+describe('OpenAIProxy', () => {
+  let server;
+  let port;
+  let client;
+
+  const start = async (options = {}) => {
+    server = await new OpenAIProxy({ client, apiKeys: ['secret'], ...options }).listen(0);
+    port = server.address().port;
+  };
+
+  beforeEach(() => {
+    client = new MockAIProvider().reply('Hello from the proxy');
+  });
+
+  afterEach(() => {
+    if (server) {
+      server.close();
+      server = null;
+    }
+  });
+
+  test('should require a client with conversation', () => {
+    expect(() => new OpenAIProxy({ client: {}, apiKeys: ['secret'] })).toThrow('requires a client');
+  });
+
+  test('should require an API key', () => {
+    expect(() => new OpenAIProxy({ client })).toThrow('requires at least one API key');
+    expect(() => new OpenAIProxy({ client, apiKeys: [undefined] })).toThrow('requires at least one API key');
+  });
+
+  test('should answer chat completions in the OpenAI format', async () => {
+    const conversation = jest.spyOn(client, 'conversation');
+    const proxy = new OpenAIProxy({ client, apiKeys: ['secret'] });
+
+    const completion = await proxy.complete({
+      model: 'gpt-4o',
+      messages: [{ role: 'user', content: 'Hi', name: 'ignored' }],
+      temperature: 0.2,
+      user: 'ignored',
+    });
+
+    expect(conversation).toHaveBeenCalledWith([{ role: 'user', content: 'Hi' }], { temperature: 0.2 });
+    expect(completion).toMatchObject({
+      object: 'chat.completion',
+      model: 'gpt-4o',
+      choices: [{ index: 0, message: { role: 'assistant', content: 'Hello from the proxy' }, finish_reason: 'stop' }],
+    });
+    expect(completion.id).toMatch(/^chatcmpl-/);
+  });
+
+  test('should forward the model only when asked to', async () => {
+    const conversation = jest.spyOn(client, 'conversation');
+
+    await new OpenAIProxy({ client, apiKeys: ['secret'], forwardModel: true }).complete({ model: 'gpt-4o', messages: [{ role: 'user', content: 'Hi' }] });
+
+    expect(conversation).toHaveBeenCalledWith([{ role: 'user', content: 'Hi' }], { model: 'gpt-4o' });
+  });
+
+  test('should serve completions over HTTP', async () => {
+    await start();
+
+    const response = await request(port, 'POST', '/v1/chat/completions', { messages: [{ role: 'user', content: 'Hi' }] });
+
+    expect(response.statusCode).toBe(200);
+    expect(JSON.parse(response.text).choices[0].message.content).toBe('Hello from the proxy');
+  });
+
+  test('should stream the reply as server-sent events', async () => {
+    await start();
+
+    const response = await request(port, 'POST', '/v1/chat/completions', { stream: true, messages: [{ role: 'user', content: 'Hi' }] });
+
+    expect(response.headers['content-type']).toBe('text/event-stream');
+    const events = response.text.trim().split('\n\n').map((event) => event.replace(/^data: /, ''));
+    expect(events).toHaveLength(3);
+    expect(JSON.parse(events[0]).choices[0].delta).toEqual({ role: 'assistant', content: 'Hello from the proxy' });
+    expect(JSON.parse(events[1]).choices[0].finish_reason).toBe('stop');
+    expect(events[2]).toBe('[DONE]');
+  });
+
+  test('should check bearer tokens', async () => {
+    await start();
+    const body = { messages: [{ role: 'user', content: 'Hi' }] };
+
+    expect((await request(port, 'POST', '/v1/chat/completions', body, { 'Content-Type': 'application/json' })).statusCode).toBe(401);
+    expect((await request(port, 'POST', '/v1/chat/completions', body, { ...AUTH, Authorization: 'Bearer wrong' })).statusCode).toBe(401);
+    expect((await request(port, 'POST', '/v1/chat/completions', body)).statusCode).toBe(200);
+  });
+
+  test('should reject bodies that are not JSON', async () => {
+    client = { conversation: jest.fn() };
+    await start();
+
+    const response = await request(port, 'POST', '/v1/chat/completions', { messages: [{ role: 'user', content: 'Hi' }] }, { ...AUTH, 'Content-Type': 'text/plain' });
+
+    expect(response.statusCode).toBe(415);
+    expect(client.conversation).not.toHaveBeenCalled();
+  });
+
+  test('should map errors to OpenAI-style responses', async () => {
+    client = { conversation: jest.fn() };
+    await start();
+    const body = { messages: [{ role: 'user', content: 'Hi' }] };
+
+    client.conversation.mockRejectedValueOnce(Object.assign(new Error('Claude API Error: 429 - rate limited'), { statusCode: 429 }));
+    const limited = await request(port, 'POST', '/v1/chat/completions', body);
+    expect(limited.statusCode).toBe(429);
+    expect(JSON.parse(limited.text).error.message).toBe('Claude API Error: 429 - rate limited');
+
+    client.conversation.mockRejectedValueOnce(Object.assign(new Error('Moderation blocked input: violence'), { statusCode: 400 }));
+    expect((await request(port, 'POST', '/v1/chat/completions', body)).statusCode).toBe(400);
+
+    client.conversation.mockRejectedValueOnce(new Error('All providers failed: a: down'));
+    expect((await request(port, 'POST', '/v1/chat/completions', body)).statusCode).toBe(502);
+
+    expect((await request(port, 'POST', '/v1/chat/completions', { messages: [] })).statusCode).toBe(400);
+    expect((await request(port, 'GET', '/v1/embeddings')).statusCode).toBe(404);
+  });
+
+  test('should list the proxy model', async () => {
+    await start({ model: 'team-default' });
+
+    const response = await request(port, 'GET', '/v1/models');
+
+    expect(JSON.parse(response.text).data).toEqual([{ id: 'team-default', object: 'model', owned_by: 'aitm' }]);
+  });
+});
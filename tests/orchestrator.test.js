@@ -0,0 +1,158 @@
+const Orchestrator = require('../src/orchestrator');
+const { ToolRegistry } = require('../src/tools');
+
+const sleep = (ms) => new Promise((resolve) => setTimeout(resolve, ms));
+
+/**
+ * Minimal agent that echoes its task after a delay
+ */
+const echoAgent = (delay = 0) => ({
+  tools: new ToolRegistry(),
+  run: jest.fn(async (task) => {
+    await sleep(delay);
+    return { output: `echo: ${task}` };
+  }),
+});
+
+describe('Orchestrator', () => {
+  describe('addAgent', () => {
+    test('should reject duplicate or invalid agents', () => {
+      const orchestrator = new Orchestrator().addAgent('a', echoAgent());
+      expect(() => orchestrator.addAgent('a', echoAgent())).toThrow('Agent already registered: a');
+      expect(() => orchestrator.addAgent('b', {})).toThrow('must have a run');
+    });
+  });
+
+  describe('send', () => {
+    test('should deliver a task and return the result', async () => {
+      const orchestrator = new Orchestrator().addAgent('analyst', echoAgent());
+      const result = await orchestrator.send('analyst', 'check sales', { from: 'planner' });
+      expect(result.output).toBe('echo: [from planner] check sales');
+    });
+
+    test('should throw error for unknown agents', async () => {
+      await expect(new Orchestrator().send('ghost', 'boo')).rejects.toThrow('Unknown agent: ghost');
+    });
+
+    test('should process one task at a time per agent', async () => {
+      let running = 0;
+      let peak = 0;
+      const agent = {
+        run: async (task) => {
+          running++;
+          peak = Math.max(peak, running);
+          await sleep(5);
+          running--;
+          return { output: task };
+        },
+      };
+      const orchestrator = new Orchestrator().addAgent('solo', agent);
+
+      await Promise.all(['a', 'b', 'c'].map((task) => orchestrator.send('solo', task)));
+
+      expect(peak).toBe(1);
+      expect(orchestrator.getStats().solo).toEqual({ queued: 0, busy: false, completed: 3, failed: 0 });
+    });
+
+    test('should apply backpressure when an inbox is full', async () => {
+      const orchestrator = new Orchestrator({ maxQueue: 1 }).addAgent('slow', echoAgent(10));
+
+      const first = orchestrator.send('slow', 'one');
+      const second = orchestrator.send('slow', 'two');
+      const third = orchestrator.send('slow', 'three');
+
+      await sleep(0);
+      expect(orchestrator.getStats().slow.queued).toBe(1);
+
+      const results = await Promise.all([first, second, third]);
+      expect(results.map((r) => r.output)).toEqual(['echo: one', 'echo: two', 'echo: three']);
+    });
+
+    test('should time out slow agents and signal cancellation', async () => {
+      let signal;
+      const agent = {
+        run: async (_task, options) => {
+          signal = options.signal;
+          await sleep(50);
+          return { output: 'late' };
+        },
+      };
+      const orchestrator = new Orchestrator().addAgent('slow', agent, { timeout: 10 });
+
+      await expect(orchestrator.send('slow', 'task')).rejects.toThrow('Agent slow timed out after 10ms');
+      expect(signal.aborted).toBe(true);
+      expect(orchestrator.getStats().slow.failed).toBe(1);
+    });
+
+    test('should wait for a timed-out run to settle before the next task', async () => {
+      let active = 0;
+      let overlapped = false;
+      const agent = {
+        run: async (task) => {
+          active++;
+          overlapped = overlapped || active > 1;
+          // Ignores the abort signal and finishes late
+          await new Promise((resolve) => setTimeout(resolve, task === 'slow' ? 40 : 1));
+          active--;
+          return { output: task };
+        },
+      };
+      const orchestrator = new Orchestrator().addAgent('a', agent, { timeout: 10 });
+
+      const slow = orchestrator.send('a', 'slow');
+      const next = orchestrator.send('a', 'next');
+
+      await expect(slow).rejects.toThrow('timed out after 10ms');
+      expect(await next).toEqual({ output: 'next' });
+      expect(overlapped).toBe(false);
+    });
+  });
+
+  describe('runAll', () => {
+    test('should run agents concurrently and aggregate outcomes', async () => {
+      const failing = { run: async () => { throw new Error('no data'); } };
+      const orchestrator = new Orchestrator()
+        .addAgent('a', echoAgent(30))
+        .addAgent('b', echoAgent(30))
+        .addAgent('c', failing);
+
+      const start = Date.now();
+      const results = await orchestrator.runAll({ a: 'x', b: 'y', c: 'z' });
+
+      // Sequential execution would take at least 60ms
+      expect(Date.now() - start).toBeLessThan(60);
+      expect(results).toEqual({
+        a: { status: 'fulfilled', output: 'echo: x' },
+        b: { status: 'fulfilled', output: 'echo: y' },
+        c: { status: 'rejected', error: 'no data' },
+      });
+    });
+  });
+
+  describe('supervise', () => {
+    test('should register a delegate tool that routes to workers', async () => {
+      const supervisor = echoAgent();
+      const worker = echoAgent();
+      const orchestrator = new Orchestrator()
+        .addAgent('lead', supervisor)
+        .addAgent('worker', worker)
+        .supervise('lead', ['worker']);
+
+      expect(supervisor.tools.has('delegate')).toBe(true);
+
+      const result = await supervisor.tools.execute({
+        id: 'c1',
+        function: { name: 'delegate', arguments: JSON.stringify({ worker: 'worker', task: 'summarize' }) },
+      });
+
+      expect(result.content).toBe('echo: [from lead] summarize');
+    });
+
+    test('should throw error when the supervisor has no tools', () => {
+      const orchestrator = new Orchestrator()
+        .addAgent('lead', { run: async () => ({}) })
+        .addAgent('worker', echoAgent());
+      expect(() => orchestrator.supervise('lead', ['worker'])).toThrow('has no tool registry');
+    });
+  });
+});
@@ -0,0 +1,48 @@
+const {
+  DEFAULT_LIMIT, MAX_LIMIT, normalizeLimit, encodeCursor, decodeCursor, paginate, iterateAll,
+} = require('../src/pagination');
+
+describe('pagination', () => {
+  test('should default and cap limits', () => {
+    expect(normalizeLimit()).toBe(DEFAULT_LIMIT);
+    expect(normalizeLimit(10)).toBe(10);
+    expect(normalizeLimit(1000)).toBe(MAX_LIMIT);
+    expect(() => normalizeLimit(0)).toThrow('Page limit must be a positive integer, got 0');
+  });
+
+  test('should round-trip opaque cursors', () => {
+    const cursor = encodeCursor({ offset: 20 });
+    expect(cursor).not.toContain('offset');
+    expect(decodeCursor(cursor)).toEqual({ offset: 20 });
+    expect(decodeCursor(null)).toBeNull();
+    expect(() => decodeCursor('%%%')).toThrow('Invalid page cursor');
+  });
+
+  test('should page through a list', () => {
+    const items = [1, 2, 3, 4, 5];
+    const first = paginate(items, { limit: 2 });
+    const second = paginate(items, { limit: 2, cursor: first.nextCursor });
+    const last = paginate(items, { limit: 2, cursor: second.nextCursor });
+
+    expect(first.items).toEqual([1, 2]);
+    expect(second.items).toEqual([3, 4]);
+    expect(last).toEqual({ items: [5], nextCursor: null });
+  });
+
+  test('should reject cursors with bad positions', () => {
+    expect(() => paginate([1], { cursor: encodeCursor({ offset: -1 }) })).toThrow('Invalid page cursor');
+  });
+
+  test('should iterate over all pages', async () => {
+    const items = Array.from({ length: 7 }, (_, i) => i);
+    const fetchPage = jest.fn(async (cursor) => paginate(items, { cursor, limit: 3 }));
+
+    const seen = [];
+    for await (const item of iterateAll(fetchPage)) {
+      seen.push(item);
+    }
+
+    expect(seen).toEqual(items);
+    expect(fetchPage).toHaveBeenCalledTimes(3);
+  });
+});
@@ -0,0 +1,174 @@
+const Pipeline = require('../src/pipeline');
+const ProgressHandle = require('../src/progressHandle');
+
+const sleep = (ms) => new Promise((resolve) => setTimeout(resolve, ms));
+
+describe('Pipeline', () => {
+  const actions = {
+    double: async ({ input }) => input * 2,
+    add: async ({ results, params }) => Object.values(results).reduce((a, b) => a + b, 0) + (params.extra || 0),
+    classify: async ({ results }) => ({ priority: results.total > 10 ? 'high' : 'low' }),
+    notify: jest.fn(async () => 'sent'),
+  };
+
+  beforeEach(() => {
+    actions.notify.mockClear();
+  });
+
+  describe('validation', () => {
+    test('should reject unknown actions and dependencies', () => {
+      expect(() => new Pipeline({ steps: [{ id: 'a', action: 'missing' }] }, actions))
+        .toThrow('uses unknown action: missing');
+      expect(() => new Pipeline({ steps: [{ id: 'a', action: 'double', dependsOn: ['b'] }] }, actions))
+        .toThrow('references unknown step: b');
+    });
+
+    test('should reject duplicate ids and cycles', () => {
+      expect(() => new Pipeline({
+        steps: [{ id: 'a', action: 'double' }, { id: 'a', action: 'double' }],
+      }, actions)).toThrow('duplicate step id: a');
+
+      expect(() => new Pipeline({
+        steps: [
+          { id: 'a', action: 'double', dependsOn: ['b'] },
+          { id: 'b', action: 'double', dependsOn: ['a'] },
+        ],
+      }, actions)).toThrow('dependency cycle');
+    });
+
+    test('should require conditional steps to depend on their condition step', () => {
+      expect(() => new Pipeline({
+        steps: [
+          { id: 'a', action: 'double' },
+          { id: 'b', action: 'notify', when: { step: 'a', equals: 2 } },
+        ],
+      }, actions)).toThrow('must depend on its condition step a');
+    });
+  });
+
+  describe('run', () => {
+    test('should pass dependency outputs along the graph', async () => {
+      const pipeline = new Pipeline({
+        name: 'math',
+        steps: [
+          { id: 'left', action: 'double' },
+          { id: 'right', action: 'double' },
+          { id: 'total', action: 'add', dependsOn: ['left', 'right'], params: { extra: 1 } },
+        ],
+      }, actions);
+
+      const { outputs, status } = await pipeline.run(3);
+
+      expect(outputs).toEqual({ left: 6, right: 6, total: 13 });
+      expect(status).toEqual({ left: 'completed', right: 'completed', total: 'completed' });
+    });
+
+    test('should fan out independent steps in parallel', async () => {
+      const slow = async () => {
+        await sleep(30);
+        return true;
+      };
+      const pipeline = new Pipeline({
+        steps: [
+          { id: 'a', action: 'slow' },
+          { id: 'b', action: 'slow' },
+          { id: 'c', action: 'slow' },
+        ],
+      }, { slow });
+
+      const start = Date.now();
+      await pipeline.run();
+
+      // Sequential execution would take at least 90ms
+      expect(Date.now() - start).toBeLessThan(90);
+    });
+
+    test('should branch on a condition and skip dependants of skipped steps', async () => {
+      const definition = {
+        steps: [
+          { id: 'total', action: 'double' },
+          { id: 'classify', action: 'classify', dependsOn: ['total'] },
+          { id: 'alert', action: 'notify', dependsOn: ['classify'], when: { step: 'classify', path: 'priority', equals: 'high' } },
+          { id: 'followUp', action: 'notify', dependsOn: ['alert'] },
+        ],
+      };
+
+      const low = await new Pipeline(definition, actions).run(2);
+      expect(low.status.alert).toBe('skipped');
+      expect(low.status.followUp).toBe('skipped');
+      expect(actions.notify).not.toHaveBeenCalled();
+
+      const high = await new Pipeline(definition, actions).run(20);
+      expect(high.status.alert).toBe('completed');
+      expect(high.status.followUp).toBe('completed');
+    });
+
+    test('should retry failed steps', async () => {
+      const flaky = jest.fn()
+        .mockRejectedValueOnce(new Error('timeout'))
+        .mockResolvedValueOnce('ok');
+      const onStep = jest.fn();
+      const pipeline = new Pipeline({ steps: [{ id: 'call', action: 'flaky', retries: 1 }] }, { flaky });
+
+      const { outputs } = await pipeline.run(null, { onStep });
+
+      expect(outputs.call).toBe('ok');
+      expect(onStep).toHaveBeenCalledWith({ id: 'call', status: 'completed', output: 'ok', attempts: 2 });
+    });
+
+    test('should fail with the step id once retries are exhausted', async () => {
+      const broken = jest.fn().mockRejectedValue(new Error('bad gateway'));
+      const pipeline = new Pipeline({
+        name: 'ingest',
+        steps: [{ id: 'upsert', action: 'broken', retries: 2 }],
+      }, { broken });
+
+      await expect(pipeline.run()).rejects.toThrow('Pipeline ingest failed at step upsert: bad gateway');
+      expect(broken).toHaveBeenCalledTimes(3);
+    });
+
+    test('should report progress and stop when cancelled', async () => {
+      const progress = new ProgressHandle();
+      const stages = [];
+      progress.onProgress((state) => stages.push(state.stage));
+
+      const pipeline = new Pipeline({
+        steps: [
+          { id: 'a', action: 'double' },
+          { id: 'b', action: 'cancel', dependsOn: ['a'] },
+          { id: 'c', action: 'notify', dependsOn: ['b'] },
+        ],
+      }, { ...actions, cancel: async () => progress.cancel('operator') });
+
+      await expect(pipeline.run(1, { progress })).rejects.toThrow('Operation cancelled: operator');
+      expect(actions.notify).not.toHaveBeenCalled();
+      expect(progress.toJSON()).toMatchObject({ status: 'cancelled', completed: 2, total: 3 });
+      expect(stages).toContain('b');
+    });
+
+    test('should complete the progress handle', async () => {
+      const progress = new ProgressHandle();
+      const pipeline = new Pipeline({ steps: [{ id: 'a', action: 'double' }] }, actions);
+
+      await pipeline.run(2, { progress });
+      expect(progress.toJSON()).toMatchObject({ status: 'completed', percent: 100 });
+    });
+  });
+
+  describe('serialization', () => {
+    test('should round-trip through JSON', async () => {
+      const pipeline = new Pipeline({
+        name: 'stored',
+        steps: [
+          { id: 'a', action: 'double' },
+          { id: 'b', action: 'add', dependsOn: ['a'] },
+        ],
+      }, actions);
+
+      const restored = Pipeline.fromJSON(JSON.stringify(pipeline), actions);
+
+      expect(restored.toJSON()).toEqual(pipeline.toJSON());
+      expect((await restored.run(5)).outputs.b).toBe(10);
+    });
+  });
+});
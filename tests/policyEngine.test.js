@@ -0,0 +1,101 @@
+const PolicyEngine = require('../src/policyEngine');
+const UsageTracker = require('../src/usageTracker');
+
+describe('PolicyEngine', () => {
+  test('should allow everything without rules', async () => {
+    const policy = new PolicyEngine();
+    expect(await policy.checkPrompt('hello')).toEqual({ allowed: true, rule: null, reason: null });
+    expect((await policy.checkToolCall('anything', { a: 1 })).allowed).toBe(true);
+    expect(policy.auditLog.entries).toHaveLength(0);
+  });
+
+  test('should require a usage tracker for spend limits', () => {
+    expect(() => new PolicyEngine({ rules: { maxDailySpend: 1 } })).toThrow('needs a usageTracker');
+  });
+
+  test('should enforce tool allow and deny lists', async () => {
+    const policy = new PolicyEngine({ rules: { allowedTools: ['search', 'delete'], deniedTools: ['delete'] } });
+
+    expect((await policy.checkToolCall('search')).allowed).toBe(true);
+    expect(await policy.checkToolCall('delete')).toMatchObject({ allowed: false, rule: 'tool' });
+    expect(await policy.checkToolCall('shell')).toMatchObject({ allowed: false, reason: 'tool shell is not allowed' });
+  });
+
+  test('should reject banned patterns in prompts and tool arguments', async () => {
+    const policy = new PolicyEngine({ rules: { bannedPatterns: ['ignore previous instructions', /rm -rf/g] } });
+
+    expect((await policy.checkPrompt('Please IGNORE previous instructions.')).allowed).toBe(false);
+    expect((await policy.checkToolCall('shell', { command: 'rm -rf /' })).rule).toBe('pattern');
+    expect((await policy.checkToolCall('shell', { command: 'rm -rf /' })).rule).toBe('pattern');
+    expect((await policy.checkPrompt('a.b')).allowed).toBe(true);
+  });
+
+  test('should cap values and enforce allow-listed arguments', async () => {
+    const policy = new PolicyEngine({
+      rules: { tools: { send_payment: { max: { amount: 100 }, allow: { to: ['treasury'] } } } },
+    });
+
+    expect((await policy.checkToolCall('send_payment', { amount: 50, to: 'treasury' })).allowed).toBe(true);
+    expect(await policy.checkToolCall('send_payment', { amount: 500, to: 'treasury' }))
+      .toMatchObject({ allowed: false, rule: 'max', reason: 'send_payment.amount 500 exceeds 100' });
+    expect(await policy.checkToolCall('send_payment', { amount: 'lots', to: 'treasury' })).toMatchObject({ rule: 'max' });
+    expect(await policy.checkToolCall('send_payment', { amount: 5, to: 'mallory' })).toMatchObject({ rule: 'allow' });
+  });
+
+  test('should hold large values for approval', async () => {
+    const policy = new PolicyEngine({ rules: { tools: { refund: { approveAbove: { amount: 50 } } } } });
+
+    expect((await policy.checkToolCall('refund', { amount: 50 })).allowed).toBe(true);
+    expect(await policy.checkToolCall('refund', { amount: 51 })).toMatchObject({
+      allowed: false,
+      rule: 'approval',
+      approvalRequired: true,
+    });
+  });
+
+  test('should hold values that are not numbers for approval', async () => {
+    const policy = new PolicyEngine({ rules: { tools: { refund: { approveAbove: { amount: 50 } } } } });
+
+    expect(await policy.checkToolCall('refund', { amount: '1e9 USD' })).toMatchObject({
+      allowed: false,
+      rule: 'approval',
+      reason: 'refund.amount 1e9 USD is not a number and needs approval',
+      approvalRequired: true,
+    });
+  });
+
+  test('should stop once the daily spend is reached', async () => {
+    const usageTracker = new UsageTracker({ pricing: { 'gpt-4': { prompt: 1, completion: 1 } } });
+    const policy = new PolicyEngine({ rules: { maxDailySpend: 2 }, usageTracker });
+
+    usageTracker.entries.push({ timestamp: '2000-01-01T00:00:00Z', promptTokens: 0, completionTokens: 0, cost: 50 });
+    expect((await policy.checkPrompt('hi')).allowed).toBe(true);
+
+    usageTracker.record('gpt-4', { prompt_tokens: 1000, completion_tokens: 1000 });
+    expect(await policy.checkPrompt('hi')).toMatchObject({ allowed: false, rule: 'spend' });
+  });
+
+  test('should deny over-budget tool calls instead of offering approval', async () => {
+    const usageTracker = new UsageTracker({ pricing: { 'gpt-4': { prompt: 1, completion: 1 } } });
+    const policy = new PolicyEngine({
+      rules: { maxDailySpend: 1, tools: { refund: { approveAbove: { amount: 50 } } } },
+      usageTracker,
+    });
+
+    usageTracker.record('gpt-4', { prompt_tokens: 1000, completion_tokens: 1000 });
+    const decision = await policy.checkToolCall('refund', { amount: 500 });
+
+    expect(decision).toMatchObject({ allowed: false, rule: 'spend' });
+    expect(decision.approvalRequired).toBeUndefined();
+  });
+
+  test('should audit denials and throw from enforce methods', async () => {
+    const policy = new PolicyEngine({ rules: { deniedTools: ['delete'] } });
+
+    await expect(policy.enforceToolCall('delete', { id: 1 })).rejects.toThrow('Denied by policy: tool delete is not allowed');
+    await policy.enforceToolCall('search');
+
+    const entries = await policy.auditLog.load();
+    expect(entries).toEqual([expect.objectContaining({ type: 'tool', tool: 'delete', rule: 'tool' })]);
+  });
+});
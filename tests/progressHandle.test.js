@@ -0,0 +1,70 @@
+const ProgressHandle = require('../src/progressHandle');
+
+describe('ProgressHandle', () => {
+  let time;
+  const now = () => time;
+
+  beforeEach(() => {
+    time = 1000;
+  });
+
+  test('should report percent and ETA from the rate so far', () => {
+    const progress = new ProgressHandle({ total: 4, stage: 'ingest', now });
+
+    expect(progress.percent).toBe(0);
+    expect(progress.eta).toBeNull();
+
+    time = 3000;
+    progress.advance();
+    expect(progress.percent).toBe(25);
+    expect(progress.eta).toBe(6000);
+    expect(progress.toJSON()).toMatchObject({ status: 'running', stage: 'ingest', completed: 1, elapsedMs: 2000 });
+  });
+
+  test('should leave percent unknown without a total', () => {
+    const progress = new ProgressHandle({ now });
+    progress.advance(10, 'scan');
+
+    expect(progress.percent).toBeNull();
+    expect(progress.eta).toBeNull();
+    expect(progress.stage).toBe('scan');
+
+    progress.complete();
+    expect(progress.percent).toBe(100);
+    expect(progress.eta).toBe(0);
+  });
+
+  test('should notify listeners until unsubscribed', () => {
+    const progress = new ProgressHandle({ total: 2, now });
+    const events = [];
+    const unsubscribe = progress.onProgress((state) => events.push(state.completed));
+
+    progress.advance();
+    unsubscribe();
+    progress.advance();
+
+    expect(events).toEqual([1]);
+  });
+
+  test('should cancel at the next check', () => {
+    const progress = new ProgressHandle({ now });
+    progress.throwIfCancelled();
+
+    progress.cancel('user request');
+    expect(progress.cancelled).toBe(true);
+    expect(progress.status).toBe('running');
+    expect(() => progress.throwIfCancelled()).toThrow('Operation cancelled: user request');
+    expect(progress.status).toBe('cancelled');
+  });
+
+  test('should record failures and ignore later state changes', () => {
+    const progress = new ProgressHandle({ now });
+    time = 1500;
+    progress.fail(new Error('disk full'));
+    time = 9000;
+    progress.complete();
+    progress.cancel();
+
+    expect(progress.toJSON()).toMatchObject({ status: 'failed', error: 'disk full', elapsedMs: 500, cancelReason: null });
+  });
+});
@@ -0,0 +1,121 @@
+const fs = require('fs');
+const os = require('os');
+const path = require('path');
+const { PromptTemplate, PromptRegistry } = require('../src/prompts');
+
+describe('PromptTemplate', () => {
+  describe('render', () => {
+    test('should substitute variables', () => {
+      const prompt = new PromptTemplate('Summarize {{doc}} for {{ audience }}');
+      expect(prompt.render({ doc: 'the report', audience: 'executives' }))
+        .toBe('Summarize the report for executives');
+    });
+
+    test('should read nested values with dotted names', () => {
+      const prompt = new PromptTemplate('Hello {{user.name}}, horizon {{horizon}}');
+      expect(prompt.render({ user: { name: 'Ada' }, horizon: 7 })).toBe('Hello Ada, horizon 7');
+    });
+
+    test('should throw error for missing variables', () => {
+      const prompt = new PromptTemplate('Summarize {{doc}}');
+      expect(() => prompt.render({})).toThrow('Missing value for prompt variable: doc');
+    });
+
+    test('should reject values that are not strings, numbers or booleans', () => {
+      const prompt = new PromptTemplate('Data: {{data}}');
+      expect(() => prompt.render({ data: { a: 1 } })).toThrow('must be a string, number or boolean');
+    });
+
+    test('should not re-expand placeholders inside values', () => {
+      const prompt = new PromptTemplate('Echo {{text}}');
+      expect(prompt.render({ text: '{{secret}}' })).toBe('Echo {{secret}}');
+    });
+  });
+
+  describe('variables', () => {
+    test('should list variables including those from partials', () => {
+      const prompt = new PromptTemplate('{{> intro}} Analyze {{series}}', {
+        partials: { intro: 'You are {{role}}.' },
+      });
+      expect(prompt.variables).toEqual(['role', 'series']);
+    });
+  });
+
+  describe('partials', () => {
+    test('should expand partials', () => {
+      const prompt = new PromptTemplate('{{> tone}}\n{{question}}', {
+        partials: { tone: 'Answer briefly.' },
+      });
+      expect(prompt.render({ question: 'Why?' })).toBe('Answer briefly.\nWhy?');
+    });
+
+    test('should throw error for unknown or cyclic partials', () => {
+      expect(() => new PromptTemplate('{{> missing}}').render()).toThrow('Unknown prompt partial: missing');
+
+      const cyclic = new PromptTemplate('{{> a}}', { partials: { a: '{{> b}}', b: '{{> a}}' } });
+      expect(() => cyclic.render()).toThrow('nested too deeply');
+    });
+  });
+
+  describe('examples', () => {
+    const examples = [
+      { input: '1, 2, 3', output: 'rising' },
+      { input: '3, 2, 1', output: 'falling' },
+    ];
+
+    test('should insert examples at the examples placeholder', () => {
+      const prompt = new PromptTemplate('Classify the trend.\n{{examples}}\nInput: {{series}}', { examples });
+      expect(prompt.render({ series: '5, 5, 5' })).toBe(
+        'Classify the trend.\nInput: 1, 2, 3\nOutput: rising\n\nInput: 3, 2, 1\nOutput: falling\nInput: 5, 5, 5'
+      );
+    });
+
+    test('should place examples before the template without a placeholder', () => {
+      const prompt = new PromptTemplate('Q: {{q}}', {
+        exampleTemplate: 'Q: {{q}}\nA: {{a}}',
+        exampleSeparator: '\n',
+      }).withExamples([{ q: 'ping', a: 'pong' }]);
+
+      expect(prompt.render({ q: 'hello' })).toBe('Q: ping\nA: pong\nQ: hello');
+    });
+  });
+});
+
+describe('PromptRegistry', () => {
+  test('should register and render templates with shared partials', () => {
+    const registry = new PromptRegistry();
+    registry.register('summary', '{{> system}} Summarize {{doc}}');
+    registry.registerPartial('system', 'You are concise.');
+
+    expect(registry.has('summary')).toBe(true);
+    expect(registry.list().items).toEqual(['summary']);
+    expect(registry.render('summary', { doc: 'X' })).toBe('You are concise. Summarize X');
+  });
+
+  test('should throw error for unknown templates', () => {
+    const registry = new PromptRegistry();
+    expect(() => registry.get('nope')).toThrow('Unknown prompt template: nope');
+  });
+
+  test('should load templates and partials from a directory', () => {
+    const dir = fs.mkdtempSync(path.join(os.tmpdir(), 'prompts-'));
+
+    try {
+      fs.writeFileSync(path.join(dir, 'forecast.prompt'), '{{> tone}} Forecast {{metric}}');
+      fs.writeFileSync(path.join(dir, '_tone.txt'), 'Be precise.');
+      fs.writeFileSync(path.join(dir, 'trend.json'), JSON.stringify({
+        template: 'Trend of {{series}}',
+        examples: [{ input: 'a', output: 'b' }],
+      }));
+      fs.writeFileSync(path.join(dir, 'README.md'), 'ignored');
+
+      const registry = new PromptRegistry().loadFromDirectory(dir);
+
+      expect(registry.list().items.sort()).toEqual(['forecast', 'trend']);
+      expect(registry.render('forecast', { metric: 'sales' })).toBe('Be precise. Forecast sales');
+      expect(registry.render('trend', { series: 'x' })).toBe('Input: a\nOutput: b\n\nTrend of x');
+    } finally {
+      fs.rmSync(dir, { recursive: true, force: true });
+    }
+  });
+});
@@ -0,0 +1,108 @@
+const RateLimiter = require('../src/rateLimiter');
+const Grok = require('../src/grok');
+
+jest.mock('https');
+
+const sleep = (ms) => new Promise((resolve) => setTimeout(resolve, ms));
+
+describe('RateLimiter', () => {
+  describe('Constructor', () => {
+    test('should throw error for non-positive limits', () => {
+      expect(() => new RateLimiter({ requestsPerMinute: 0 })).toThrow('requestsPerMinute must be a positive number');
+      expect(() => new RateLimiter({ maxConcurrent: -1 })).toThrow('maxConcurrent must be a positive number');
+    });
+
+    test('should allow unlimited configuration', async () => {
+      const limiter = new RateLimiter();
+      const result = await limiter.schedule(async () => 'done');
+      expect(result).toBe('done');
+      expect(limiter.getStats()).toEqual({ inFlight: 0, queued: 0, availableTokens: null });
+    });
+  });
+
+  describe('maxConcurrent', () => {
+    test('should cap the number of in-flight tasks', async () => {
+      const limiter = new RateLimiter({ maxConcurrent: 2 });
+      let running = 0;
+      let peak = 0;
+
+      const task = async () => {
+        running++;
+        peak = Math.max(peak, running);
+        await sleep(10);
+        running--;
+      };
+
+      await Promise.all([1, 2, 3, 4, 5].map(() => limiter.schedule(task)));
+
+      expect(peak).toBe(2);
+      expect(limiter.getStats().inFlight).toBe(0);
+    });
+
+    test('should release the slot when a task fails', async () => {
+      const limiter = new RateLimiter({ maxConcurrent: 1 });
+
+      await expect(limiter.schedule(async () => {
+        throw new Error('boom');
+      })).rejects.toThrow('boom');
+
+      const result = await limiter.schedule(async () => 'next');
+      expect(result).toBe('next');
+    });
+  });
+
+  describe('requestsPerMinute', () => {
+    test('should allow a burst then wait for refill', async () => {
+      // 1200 rpm = one token every 50ms
+      const limiter = new RateLimiter({ requestsPerMinute: 1200, burst: 2 });
+      const start = Date.now();
+
+      await Promise.all([1, 2, 3].map(() => limiter.schedule(async () => null)));
+
+      expect(Date.now() - start).toBeGreaterThanOrEqual(40);
+    });
+
+    test('should report available tokens', async () => {
+      const limiter = new RateLimiter({ requestsPerMinute: 60, burst: 5 });
+      await limiter.schedule(async () => null);
+      expect(limiter.getStats().availableTokens).toBe(4);
+    });
+  });
+
+  describe('shared limiter', () => {
+    test('should apply one limit across multiple clients', async () => {
+      const https = require('https');
+      const limiter = new RateLimiter({ maxConcurrent: 1 });
+      let running = 0;
+      let peak = 0;
+
+      https.request = jest.fn((options, cb) => {
+        running++;
+        peak = Math.max(peak, running);
+        const mockRes = {
+          statusCode: 200,
+          on: jest.fn((event, handler) => {
+            if (event === 'data') handler(JSON.stringify({ choices: [{ message: { content: 'ok' } }] }));
+            if (event === 'end') {
+              running--;
+              handler();
+            }
+          }),
+        };
+        return {
+          write: jest.fn(),
+          on: jest.fn(),
+          end: jest.fn(() => setTimeout(() => cb(mockRes), 5)),
+        };
+      });
+
+      const first = new Grok('key-1', { rateLimiter: limiter });
+      const second = new Grok('key-2', { rateLimiter: limiter });
+
+      const responses = await Promise.all([first.chat('a'), second.chat('b'), first.chat('c')]);
+
+      expect(responses).toEqual(['ok', 'ok', 'ok']);
+      expect(peak).toBe(1);
+    });
+  });
+});
@@ -0,0 +1,165 @@
+const fs = require('fs');
+const os = require('os');
+const path = require('path');
+const Recorder = require('../src/recorder');
+const { MemoryJournal, FileJournal } = require('../src/recorder');
+const Grok = require('../src/grok');
+const OpenClaw = require('../src/openclaw');
+
+describe('Recorder', () => {
+  test('should reject unknown modes', () => {
+    expect(() => new Recorder({ mode: 'rewind' })).toThrow('Unsupported recorder mode: rewind');
+  });
+
+  test('should record responses and errors', async () => {
+    const journal = new MemoryJournal();
+    const recorder = new Recorder({ journal });
+
+    await recorder.capture('xai', 'POST /chat/completions', { q: 1 }, async () => ({ answer: 42 }));
+    await expect(
+      recorder.capture('xai', 'POST /chat/completions', { q: 2 }, async () => {
+        throw new Error('rate limited');
+      })
+    ).rejects.toThrow('rate limited');
+
+    const entries = await journal.load();
+    expect(entries).toHaveLength(2);
+    expect(entries[0]).toMatchObject({ provider: 'xai', request: { q: 1 }, response: { answer: 42 } });
+    expect(entries[1]).toMatchObject({ request: { q: 2 }, error: 'rate limited' });
+  });
+
+  test('should replay recordings in order without calling the task', async () => {
+    const journal = new MemoryJournal();
+    const recorder = new Recorder({ journal });
+    await recorder.capture('openai', 'chat.completions', { n: 1 }, async () => 'first');
+    await recorder.capture('openai', 'chat.completions', { n: 1 }, async () => 'second');
+
+    recorder.setMode('replay');
+    const task = jest.fn();
+
+    expect(await recorder.capture('openai', 'chat.completions', { n: 1 }, task)).toBe('first');
+    expect(await recorder.capture('openai', 'chat.completions', { n: 1 }, task)).toBe('second');
+    expect(await recorder.capture('openai', 'chat.completions', { n: 1 }, task)).toBe('second');
+    expect(task).not.toHaveBeenCalled();
+  });
+
+  test('should match requests regardless of key order', async () => {
+    const recorder = new Recorder({
+      mode: 'replay',
+      journal: new MemoryJournal(),
+    });
+    const key = recorder.keyFor('openai', 'chat.completions', { a: 1, b: 2 });
+
+    expect(recorder.keyFor('openai', 'chat.completions', { b: 2, a: 1 })).toBe(key);
+    expect(recorder.keyFor('xai', 'chat.completions', { a: 1, b: 2 })).not.toBe(key);
+  });
+
+  test('should apply a custom normalizer when matching', async () => {
+    const journal = new MemoryJournal();
+    const normalize = (request) => ({ ...request, nonce: null });
+    await new Recorder({ journal, normalize }).capture('p', 'op', { q: 1, nonce: 'a' }, async () => 'first');
+
+    const replay = new Recorder({ mode: 'replay', journal, normalize });
+
+    expect(await replay.capture('p', 'op', { q: 1, nonce: 'b' }, async () => 'live')).toBe('first');
+  });
+
+  test('should not record requests the caller cancelled', async () => {
+    const journal = new MemoryJournal();
+    const controller = new AbortController();
+    controller.abort();
+
+    await expect(new Recorder({ journal }).capture('p', 'op', {}, async () => {
+      throw new Error('aborted');
+    }, { signal: controller.signal })).rejects.toThrow('aborted');
+
+    expect(await journal.load()).toEqual([]);
+  });
+
+  test('should throw on replay of an unrecorded request', async () => {
+    const recorder = new Recorder({ mode: 'replay' });
+    await expect(recorder.capture('xai', 'GET /models', null, jest.fn())).rejects.toThrow('No recording for xai GET /models');
+  });
+
+  test('should rethrow recorded errors on replay', async () => {
+    const journal = new MemoryJournal();
+    const recorder = new Recorder({ journal });
+    await recorder.capture('xai', 'GET /models', null, async () => {
+      throw new Error('Grok API Error: 500 - boom');
+    }).catch(() => {});
+
+    recorder.setMode('replay');
+    await expect(recorder.capture('xai', 'GET /models', null, jest.fn())).rejects.toThrow('Grok API Error: 500 - boom');
+  });
+
+  test('should call through without recording when off', async () => {
+    const journal = new MemoryJournal();
+    const recorder = new Recorder({ mode: 'off', journal });
+
+    expect(await recorder.capture('xai', 'GET /models', null, async () => 'live')).toBe('live');
+    expect(await journal.load()).toEqual([]);
+  });
+});
+
+describe('FileJournal', () => {
+  let dir;
+
+  beforeEach(() => {
+    dir = fs.mkdtempSync(path.join(os.tmpdir(), 'recorder-'));
+  });
+
+  afterEach(() => {
+    fs.rmSync(dir, { recursive: true, force: true });
+  });
+
+  test('should return no entries for a missing file', async () => {
+    expect(await new FileJournal(path.join(dir, 'missing.jsonl')).load()).toEqual([]);
+  });
+
+  test('should replay a session recorded by another recorder', async () => {
+    const file = path.join(dir, 'session.jsonl');
+    await new Recorder({ journal: new FileJournal(file) })
+      .capture('xai', 'POST /chat/completions', { model: 'grok-beta' }, async () => ({ ok: true }));
+
+    expect(fs.readFileSync(file, 'utf8').trim().split('\n')).toHaveLength(1);
+
+    const replay = new Recorder({ mode: 'replay', journal: new FileJournal(file) });
+    expect(await replay.capture('xai', 'POST /chat/completions', { model: 'grok-beta' }, jest.fn())).toEqual({ ok: true });
+  });
+});
+
+describe('client integration', () => {
+  test('should serve Grok completions from a recording', async () => {
+    const journal = new MemoryJournal();
+    const live = new Grok('test-key', { recorder: new Recorder({ journal }) });
+    jest.spyOn(live, '_httpRequest').mockResolvedValue({
+      choices: [{ message: { content: 'recorded answer' } }],
+    });
+    expect(await live.chat('Hello')).toBe('recorded answer');
+
+    const offline = new Grok('test-key', { recorder: new Recorder({ mode: 'replay', journal }) });
+    const httpRequest = jest.spyOn(offline, '_httpRequest');
+
+    expect(await offline.chat('Hello')).toBe('recorded answer');
+    expect(httpRequest).not.toHaveBeenCalled();
+  });
+
+  test('should replay OpenClaw events despite their timestamps', async () => {
+    const journal = new MemoryJournal();
+    const live = new OpenClaw('test-key', { recorder: new Recorder({ journal }) });
+    jest.spyOn(live, '_httpRequest').mockResolvedValue({ accepted: true });
+    const toISOString = jest.spyOn(Date.prototype, 'toISOString').mockReturnValue('2026-01-01T00:00:00.000Z');
+    expect(await live.sendEvent('prediction.created', { id: 1 })).toEqual({ accepted: true });
+
+    toISOString.mockReturnValue('2026-01-02T12:00:00.000Z');
+    const offline = new OpenClaw('test-key', { recorder: new Recorder({ mode: 'replay', journal }) });
+    const httpRequest = jest.spyOn(offline, '_httpRequest');
+
+    try {
+      expect(await offline.sendEvent('prediction.created', { id: 1 })).toEqual({ accepted: true });
+      expect(httpRequest).not.toHaveBeenCalled();
+    } finally {
+      toISOString.mockRestore();
+    }
+  });
+});
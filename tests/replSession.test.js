@@ -0,0 +1,62 @@
+const fs = require('fs');
+const os = require('os');
+const path = require('path');
+const Agent = require('../src/agent');
+const ReplSession = require('../src/replSession');
+const UsageTracker = require('../src/usageTracker');
+const { MockAIProvider } = require('../src/testing');
+
+describe('ReplSession', () => {
+  let client;
+  let usageTracker;
+  let session;
+
+  beforeEach(() => {
+    client = new MockAIProvider().reply('Hello!', 'Again!');
+    usageTracker = new UsageTracker({ pricing: { mock: { prompt: 1, completion: 1 } } });
+    session = new ReplSession({ agent: new Agent({ client, memory: true }), usageTracker });
+  });
+
+  test('should require an agent', () => {
+    expect(() => new ReplSession()).toThrow('ReplSession requires an agent');
+  });
+
+  test('should send plain lines to the agent and remember them', async () => {
+    expect(await session.handle('Hi')).toBe('Hello!\n');
+    expect(await session.handle('  ')).toBe('');
+
+    const memory = await session.handle('/memory');
+    expect(memory).toBe('[user] Hi\n[assistant] Hello!\n');
+  });
+
+  test('should report usage', async () => {
+    usageTracker.record('mock', { prompt_tokens: 1000, completion_tokens: 500 });
+    expect(await session.handle('/usage')).toBe('1 requests, 1500 tokens, $1.5000\n');
+
+    const untracked = new ReplSession({ agent: new Agent({ client }) });
+    expect(await untracked.handle('/usage')).toBe('Usage tracking is not enabled.\n');
+  });
+
+  test('should save and restore the session', async () => {
+    const file = path.join(fs.mkdtempSync(path.join(os.tmpdir(), 'repl-')), 'session.json');
+    await session.handle('Hi');
+    usageTracker.record('mock', { prompt_tokens: 10, completion_tokens: 10 });
+
+    expect(await session.handle(`/save ${file}`)).toBe(`Saved session to ${file}\n`);
+    expect(await session.handle('/reset')).toBe('Conversation cleared.\n');
+    usageTracker.reset();
+
+    expect(await session.handle(`/load ${file}`)).toContain(`Restored session from ${file}`);
+    expect(session.agent.messages).toHaveLength(2);
+    expect(usageTracker.getTotals().requests).toBe(1);
+  });
+
+  test('should handle help, unknown commands and exit', async () => {
+    expect(await session.handle('/help')).toContain('/memory');
+    expect(await session.handle('/save')).toBe('Usage: /save <file>\n');
+    expect(await session.handle('/teleport')).toBe('Unknown command: /teleport. Type /help for commands.\n');
+    expect(session.closed).toBe(false);
+    await session.handle('/exit');
+    expect(session.closed).toBe(true);
+  });
+});
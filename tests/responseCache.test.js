@@ -0,0 +1,102 @@
+const ResponseCache = require('../src/responseCache');
+const { MemoryStore } = require('../src/responseCache');
+const ChatGPT = require('../src/chatgpt');
+
+jest.mock('openai');
+
+const sleep = (ms) => new Promise((resolve) => setTimeout(resolve, ms));
+
+describe('ResponseCache', () => {
+  describe('keyFor', () => {
+    test('should produce the same key regardless of parameter order', () => {
+      const cache = new ResponseCache();
+      const a = cache.keyFor('openai', { model: 'gpt-4', temperature: 0.7, messages: [{ role: 'user', content: 'hi' }] });
+      const b = cache.keyFor('openai', { messages: [{ content: 'hi', role: 'user' }], temperature: 0.7, model: 'gpt-4' });
+      expect(a).toBe(b);
+      expect(a.startsWith('openai:gpt-4:')).toBe(true);
+    });
+
+    test('should produce different keys for different params', () => {
+      const cache = new ResponseCache();
+      const a = cache.keyFor('openai', { model: 'gpt-4', temperature: 0.7 });
+      const b = cache.keyFor('openai', { model: 'gpt-4', temperature: 0.2 });
+      expect(a).not.toBe(b);
+    });
+  });
+
+  describe('get and set', () => {
+    test('should track hits and misses', async () => {
+      const cache = new ResponseCache();
+      expect(await cache.get('k')).toBeUndefined();
+      await cache.set('k', 'value');
+      expect(await cache.get('k')).toBe('value');
+      expect(cache.getStats()).toEqual({ hits: 1, misses: 1 });
+    });
+
+    test('should expire entries after the ttl', async () => {
+      const cache = new ResponseCache({ ttl: 10 });
+      await cache.set('k', 'value');
+      await sleep(20);
+      expect(await cache.get('k')).toBeUndefined();
+    });
+
+    test('should use a custom store', async () => {
+      const store = {
+        get: jest.fn().mockResolvedValue('stored'),
+        set: jest.fn().mockResolvedValue(),
+        clear: jest.fn().mockResolvedValue(),
+      };
+      const cache = new ResponseCache({ store, ttl: 500 });
+
+      await cache.set('k', 'v');
+      expect(store.set).toHaveBeenCalledWith('k', 'v', 500);
+      expect(await cache.get('k')).toBe('stored');
+    });
+  });
+
+  describe('MemoryStore', () => {
+    test('should evict the least recently used entry', async () => {
+      const store = new MemoryStore(2);
+      await store.set('a', 1);
+      await store.set('b', 2);
+      await store.get('a');
+      await store.set('c', 3);
+
+      expect(await store.get('a')).toBe(1);
+      expect(await store.get('b')).toBeUndefined();
+      expect(await store.get('c')).toBe(3);
+    });
+  });
+
+  describe('client integration', () => {
+    let mockCreate;
+
+    beforeEach(() => {
+      const OpenAI = require('openai');
+      mockCreate = jest.fn().mockResolvedValue({
+        choices: [{ message: { content: 'Cached answer' } }],
+      });
+      OpenAI.mockImplementation(() => ({
+        chat: { completions: { create: mockCreate } },
+      }));
+    });
+
+    test('should serve repeated requests from the cache', async () => {
+      const chatgpt = new ChatGPT('test-key', { cache: new ResponseCache() });
+
+      expect(await chatgpt.chat('Hello')).toBe('Cached answer');
+      expect(await chatgpt.chat('Hello')).toBe('Cached answer');
+      expect(mockCreate).toHaveBeenCalledTimes(1);
+    });
+
+    test('should bypass the cache when cache is false', async () => {
+      const chatgpt = new ChatGPT('test-key', { cache: new ResponseCache() });
+
+      await chatgpt.chat('Hello');
+      await chatgpt.chat('Hello', { cache: false });
+
+      expect(mockCreate).toHaveBeenCalledTimes(2);
+      expect(mockCreate.mock.calls[1][0].cache).toBeUndefined();
+    });
+  });
+});
@@ -0,0 +1,65 @@
+const Saga = require('../src/saga');
+
+describe('Saga', () => {
+  test('should reject duplicate steps and missing actions', () => {
+    const saga = new Saga('publish').step('upload', async () => 'ok');
+    expect(() => saga.step('upload', async () => 'again')).toThrow('Saga publish has duplicate step: upload');
+    expect(() => saga.step('record')).toThrow('Saga publish step record needs an action function');
+  });
+
+  test('should run steps in order and pass earlier results along', async () => {
+    const saga = new Saga()
+      .step('upload', async ({ input }) => ({ key: `artifacts/${input}` }))
+      .step('record', async ({ results }) => ({ id: 7, key: results.upload.key }));
+
+    const { results } = await saga.run('report.pdf');
+    expect(results).toEqual({
+      upload: { key: 'artifacts/report.pdf' },
+      record: { id: 7, key: 'artifacts/report.pdf' },
+    });
+  });
+
+  test('should compensate completed steps in reverse order on failure', async () => {
+    const undone = [];
+    const events = [];
+    const saga = new Saga('publish')
+      .step('upload', async () => ({ key: 'a' }), async ({ output }) => undone.push(`upload:${output.key}`))
+      .step('log', async () => 'logged')
+      .step('record', async () => ({ id: 1 }), async ({ output }) => undone.push(`record:${output.id}`))
+      .step('notify', async () => {
+        throw new Error('chat unavailable');
+      }, async () => undone.push('notify'));
+
+    const error = await saga.run(null, { onStep: (event) => events.push(`${event.name}:${event.status}`) })
+      .catch((e) => e);
+
+    expect(error.message).toBe('Saga publish failed at step notify: chat unavailable');
+    expect(error.step).toBe('notify');
+    expect(error.compensated).toEqual(['record', 'upload']);
+    expect(undone).toEqual(['record:1', 'upload:a']);
+    expect(events).toEqual([
+      'upload:completed', 'log:completed', 'record:completed',
+      'notify:failed', 'record:compensated', 'upload:compensated',
+    ]);
+  });
+
+  test('should keep compensating when a compensation fails', async () => {
+    const undo = jest.fn();
+    const saga = new Saga()
+      .step('upload', async () => 'a', undo)
+      .step('record', async () => 'b', async () => {
+        throw new Error('db down');
+      })
+      .step('vectors', async () => {
+        throw new Error('timeout');
+      });
+
+    const error = await saga.run().catch((e) => e);
+
+    expect(undo).toHaveBeenCalledWith({ input: undefined, results: { upload: 'a', record: 'b' }, output: 'a' });
+    expect(error.compensated).toEqual(['upload']);
+    expect(error.compensationErrors).toHaveLength(1);
+    expect(error.compensationErrors[0].step).toBe('record');
+    expect(error.compensationErrors[0].error.message).toBe('db down');
+  });
+});
@@ -0,0 +1,167 @@
+const Scheduler = require('../src/scheduler');
+const { parseCron, nextCronTime } = require('../src/scheduler');
+const { MemoryStore } = require('../src/responseCache');
+
+// Local-time timestamp helper (cron is evaluated in local time)
+const at = (year, month, day, hour = 0, minute = 0) => new Date(year, month - 1, day, hour, minute).getTime();
+
+describe('parseCron', () => {
+  test('should expand lists, ranges and steps', () => {
+    const cron = parseCron('*/15 9-11 1,15 * 1-5');
+    expect(Array.from(cron.minutes)).toEqual([0, 15, 30, 45]);
+    expect(Array.from(cron.hours)).toEqual([9, 10, 11]);
+    expect(Array.from(cron.daysOfMonth)).toEqual([1, 15]);
+    expect(Array.from(cron.daysOfWeek)).toEqual([1, 2, 3, 4, 5]);
+  });
+
+  test('should treat day of week 7 as Sunday', () => {
+    expect(Array.from(parseCron('0 0 * * 7').daysOfWeek)).toEqual([0]);
+  });
+
+  test('should throw error for invalid expressions', () => {
+    expect(() => parseCron('* * *')).toThrow('expected 5 fields');
+    expect(() => parseCron('60 * * * *')).toThrow('bad minute field');
+    expect(() => parseCron('*/0 * * * *')).toThrow('bad minute field');
+    expect(() => parseCron('1,,2 * * * *')).toThrow('bad minute field');
+    expect(() => parseCron('-5 * * * *')).toThrow('bad minute field');
+    expect(() => parseCron('0 0 * * 1,')).toThrow('bad day of week field');
+  });
+});
+
+describe('nextCronTime', () => {
+  test('should find the next matching minute', () => {
+    expect(nextCronTime('30 9 * * *', at(2024, 3, 4, 8, 0))).toBe(at(2024, 3, 4, 9, 30));
+    expect(nextCronTime('30 9 * * *', at(2024, 3, 4, 9, 30))).toBe(at(2024, 3, 5, 9, 30));
+  });
+
+  test('should respect weekdays and months', () => {
+    // 2024-03-09 is a Saturday; next weekday 09:00 is Monday 2024-03-11
+    expect(nextCronTime('0 9 * * 1-5', at(2024, 3, 9, 12))).toBe(at(2024, 3, 11, 9));
+    expect(nextCronTime('0 0 1 1 *', at(2024, 6, 1))).toBe(at(2025, 1, 1));
+  });
+
+  test('should match either day field when both are restricted', () => {
+    // 2024-03-04 is a Monday: "15th or any Sunday" should land on Sunday 2024-03-10
+    expect(nextCronTime('0 0 15 * 0', at(2024, 3, 4))).toBe(at(2024, 3, 10));
+  });
+
+  test('should treat a stepped * day field as unrestricted', () => {
+    // "the 15th, if it is a Sunday, Tuesday, Thursday or Saturday": 2024-03-15 is a Friday, 2024-06-15 a Saturday
+    expect(nextCronTime('0 0 15 * */2', at(2024, 3, 4))).toBe(at(2024, 6, 15));
+  });
+});
+
+describe('Scheduler', () => {
+  let now;
+  const clock = () => now;
+
+  beforeEach(() => {
+    now = at(2024, 3, 4, 8, 0);
+  });
+
+  describe('schedule', () => {
+    test('should reject invalid jobs', () => {
+      const scheduler = new Scheduler();
+      scheduler.schedule('a', { every: 1000 }, async () => {});
+      expect(() => scheduler.schedule('a', { every: 1000 }, async () => {})).toThrow('Job already scheduled: a');
+      expect(() => scheduler.schedule('b', {}, async () => {})).toThrow('needs a cron expression');
+      expect(() => scheduler.schedule('c', { every: 10 }, null)).toThrow('must be a function');
+    });
+  });
+
+  describe('tick', () => {
+    test('should run interval jobs when due', async () => {
+      const job = jest.fn().mockResolvedValue();
+      const scheduler = new Scheduler({ now: clock }).schedule('poll', { every: 60000 }, job);
+
+      await scheduler.start();
+      scheduler.stop();
+      expect(job).not.toHaveBeenCalled();
+
+      now += 60000;
+      await scheduler.tick();
+      expect(job).toHaveBeenCalledTimes(1);
+      expect(scheduler.list().items[0].nextRun).toBe(now + 60000);
+    });
+
+    test('should run cron jobs at their next fire time', async () => {
+      const job = jest.fn().mockResolvedValue();
+      const scheduler = new Scheduler({ now: clock }).schedule('digest', { cron: '0 9 * * *' }, job);
+
+      await scheduler.start();
+      scheduler.stop();
+      expect(scheduler.list().items[0].nextRun).toBe(at(2024, 3, 4, 9));
+
+      now = at(2024, 3, 4, 9, 0);
+      await scheduler.tick();
+      expect(job).toHaveBeenCalledTimes(1);
+      expect(scheduler.list().items[0].nextRun).toBe(at(2024, 3, 5, 9));
+    });
+
+    test('should record job failures and keep scheduling', async () => {
+      jest.spyOn(console, 'error').mockImplementation(() => {});
+      const scheduler = new Scheduler({ now: clock })
+        .schedule('flaky', { every: 1000 }, async () => {
+          throw new Error('network down');
+        });
+
+      await scheduler.runNow('flaky');
+
+      const [job] = scheduler.list().items;
+      expect(job.lastError).toBe('network down');
+      expect(job.nextRun).toBe(now + 1000);
+      console.error.mockRestore();
+    });
+
+    test('should not start a job that is already running', async () => {
+      let finish;
+      const job = jest.fn(() => new Promise((resolve) => {
+        finish = resolve;
+      }));
+      const scheduler = new Scheduler({ now: clock }).schedule('report', { every: 1000 }, job);
+
+      const first = scheduler.runNow('report');
+      await expect(scheduler.runNow('report')).rejects.toThrow('Job is already running: report');
+      finish();
+      await first;
+
+      expect(job).toHaveBeenCalledTimes(1);
+    });
+  });
+
+  describe('persistence', () => {
+    test('should catch up once on missed runs after a restart', async () => {
+      const store = new MemoryStore();
+      const first = new Scheduler({ store, now: clock }).schedule('digest', { cron: '0 9 * * *' }, jest.fn());
+      await first.start();
+      first.stop();
+
+      // Restart two days later: the 9:00 runs were missed
+      now = at(2024, 3, 6, 12, 0);
+      const job = jest.fn().mockResolvedValue();
+      const second = new Scheduler({ store, now: clock }).schedule('digest', { cron: '0 9 * * *' }, job);
+      await second.start();
+      second.stop();
+
+      expect(job).toHaveBeenCalledTimes(1);
+      expect(second.list().items[0].nextRun).toBe(at(2024, 3, 7, 9));
+    });
+
+    test('should skip missed runs when catchUp is disabled', async () => {
+      const store = new MemoryStore();
+      const first = new Scheduler({ store, now: clock }).schedule('digest', { cron: '0 9 * * *' }, jest.fn());
+      await first.start();
+      first.stop();
+
+      now = at(2024, 3, 6, 12, 0);
+      const job = jest.fn();
+      const second = new Scheduler({ store, now: clock, catchUp: false })
+        .schedule('digest', { cron: '0 9 * * *' }, job);
+      await second.start();
+      second.stop();
+
+      expect(job).not.toHaveBeenCalled();
+      expect(second.list().items[0].nextRun).toBe(at(2024, 3, 7, 9));
+    });
+  });
+});
@@ -0,0 +1,97 @@
+const SemanticCache = require('../src/semanticCache');
+const { cosineSimilarity } = require('../src/semanticCache');
+const { MockAIProvider } = require('../src/testing');
+
+// Toy embedding: counts of a few keywords, enough to tell paraphrases from other questions
+const KEYWORDS = ['reset', 'password', 'refund', 'order', 'cancel'];
+const embed = async (text) => KEYWORDS.map((word) => (text.toLowerCase().includes(word) ? 1 : 0));
+
+describe('SemanticCache', () => {
+  test('should require an embedder', () => {
+    expect(() => new SemanticCache()).toThrow('requires an embedder');
+    expect(() => new SemanticCache({ embedder: { embed } })).not.toThrow();
+  });
+
+  test('should compute cosine similarity', () => {
+    expect(cosineSimilarity([1, 0], [1, 0])).toBe(1);
+    expect(cosineSimilarity([1, 0], [0, 1])).toBe(0);
+    expect(cosineSimilarity([0, 0], [1, 0])).toBe(0);
+    expect(() => cosineSimilarity([1], [1, 2])).toThrow('Embedding dimensions differ');
+  });
+
+  test('should return answers for similar prompts within the same scope', async () => {
+    const cache = new SemanticCache({ embed, threshold: 0.9 });
+
+    await cache.store('How do I reset my password?', 'Use the reset link.');
+
+    expect(await cache.lookup('password reset please')).toMatchObject({ response: 'Use the reset link.', similarity: 1 });
+    expect(await cache.lookup('How do I cancel my order?')).toBeUndefined();
+    expect(await cache.lookup('How do I reset my password?', 'other-scope')).toBeUndefined();
+    expect(cache.getStats()).toEqual({ hits: 1, misses: 2, entries: 1 });
+  });
+
+  test('should evict the least recently used entry and expire old ones', async () => {
+    const cache = new SemanticCache({ embed, maxEntries: 2 });
+
+    await cache.store('reset password', 'A');
+    await cache.store('refund', 'B');
+    await cache.lookup('reset password');
+    await cache.store('cancel order', 'C');
+
+    expect(await cache.lookup('refund')).toBeUndefined();
+    expect((await cache.lookup('reset password')).response).toBe('A');
+
+    const expiring = new SemanticCache({ embed, ttl: 1 });
+    await expiring.store('refund', 'B');
+    await new Promise((resolve) => setTimeout(resolve, 5));
+    expect(await expiring.lookup('refund')).toBeUndefined();
+    expect(expiring.getStats().entries).toBe(0);
+  });
+
+  test('should wrap a client and skip the model on near-duplicates', async () => {
+    const client = new MockAIProvider().reply('Use the reset link.', 'Different model answer.');
+    const bot = new SemanticCache({ embed }).wrap(client);
+
+    expect(await bot.chat('How do I reset my password?')).toBe('Use the reset link.');
+    expect(await bot.chat('reset password')).toBe('Use the reset link.');
+    expect(client.calls).toHaveLength(1);
+
+    expect(await bot.chat('reset password', { model: 'gpt-4o' })).toBe('Different model answer.');
+    expect(client.calls).toHaveLength(2);
+  });
+
+  test('should scope conversations by their earlier messages', async () => {
+    const client = new MockAIProvider().reply('Answer for support', 'Answer for sales');
+    const bot = new SemanticCache({ embed }).wrap(client);
+    const ask = (system, content) => bot.conversation([{ role: 'system', content: system }, { role: 'user', content }]);
+
+    expect(await ask('support', 'refund?')).toBe('Answer for support');
+    expect(await ask('sales', 'refund?')).toBe('Answer for sales');
+    expect(await ask('support', 'a refund')).toBe('Answer for support');
+    expect(client.calls).toHaveLength(2);
+  });
+
+  test('should keep answers of clients wrapped on one cache apart', async () => {
+    const cache = new SemanticCache({ embed });
+    const gpt = new MockAIProvider({ defaultResponse: 'from gpt', model: 'gpt-4o' });
+    const claude = Object.assign(new MockAIProvider({ defaultResponse: 'from claude', model: 'claude-x' }), { provider: 'anthropic' });
+    const local = new MockAIProvider({ defaultResponse: 'from local', model: 'gpt-4o' });
+    local.provider = 'local';
+
+    expect(await cache.wrap(gpt).chat('reset password')).toBe('from gpt');
+    expect(await cache.wrap(claude).chat('reset password')).toBe('from claude');
+    expect(await cache.wrap(local).chat('reset password')).toBe('from local');
+    expect(await cache.wrap(gpt).chat('reset my password')).toBe('from gpt');
+  });
+
+  test('should keep the rest of the client', async () => {
+    const client = new MockAIProvider({ model: 'a' });
+    const bot = new SemanticCache({ embed }).wrap(client);
+
+    bot.setModel('b');
+
+    expect(bot.model).toBe('b');
+    expect(client.model).toBe('b');
+    expect(typeof bot.chatWithTools).toBe('function');
+  });
+});
@@ -0,0 +1,54 @@
+const { shellTool } = require('../src/shellTool');
+
+describe('shellTool', () => {
+  const node = process.execPath;
+  let auditLog;
+  let events;
+
+  beforeEach(() => {
+    events = [];
+    auditLog = { record: jest.fn(async (event) => events.push(event)) };
+  });
+
+  test('should require allowed commands and an audit log', () => {
+    expect(() => shellTool({ auditLog })).toThrow('at least one allowed command');
+    expect(() => shellTool({ commands: { ls: {} } })).toThrow('requires an auditLog');
+  });
+
+  test('should run allowed commands and audit them', async () => {
+    const tool = shellTool({ commands: { [node]: { args: ['-e', /^console\.log\(\d+\)$/] } }, auditLog });
+
+    const result = await tool.handler({ command: node, args: ['-e', 'console.log(42)'] });
+
+    expect(result).toMatchObject({ exitCode: 0, stdout: '42\n', truncated: false, timedOut: false });
+    const [entry] = events;
+    expect(entry).toMatchObject({ component: 'shell', action: 'run', tags: [node] });
+    expect(entry.data).toMatchObject({ command: node, args: ['-e', 'console.log(42)'], exitCode: 0 });
+  });
+
+  test('should refuse and audit commands and arguments outside the allow-list', async () => {
+    const tool = shellTool({ commands: { [node]: { args: ['--version'] } }, auditLog });
+
+    await expect(tool.handler({ command: 'rm', args: ['-rf', '/'] })).rejects.toThrow('Denied: command rm is not allowed');
+    await expect(tool.handler({ command: node, args: ['-e', 'process.exit(1)'] }))
+      .rejects.toThrow('Denied: argument "-e" is not allowed');
+
+    expect(events.map((entry) => entry.action)).toEqual(['denied', 'denied']);
+    expect(events.map((entry) => entry.data.command)).toEqual(['rm', node]);
+  });
+
+  test('should kill slow commands and truncate long output', async () => {
+    const tool = shellTool({
+      commands: { [node]: { args: ['-e', 'setTimeout(() => {}, 5000)', "console.log('x'.repeat(100))"] } },
+      auditLog,
+      timeout: 200,
+      maxOutputBytes: 10,
+    });
+
+    const slow = await tool.handler({ command: node, args: ['-e', 'setTimeout(() => {}, 5000)'] });
+    const noisy = await tool.handler({ command: node, args: ['-e', "console.log('x'.repeat(100))"] });
+
+    expect(slow).toMatchObject({ timedOut: true, signal: 'SIGKILL' });
+    expect(noisy).toMatchObject({ stdout: 'xxxxxxxxxx', truncated: true });
+  });
+});
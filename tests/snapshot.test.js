@@ -0,0 +1,108 @@
+const Snapshot = require('../src/snapshot');
+const Conversation = require('../src/conversation');
+const Agent = require('../src/agent');
+const UsageTracker = require('../src/usageTracker');
+const Scheduler = require('../src/scheduler');
+const { MemoryStore } = require('../src/responseCache');
+const { PromptRegistry } = require('../src/prompts');
+
+describe('Snapshot', () => {
+  const client = {
+    conversation: jest.fn().mockResolvedValue('ok'),
+    chatWithTools: jest.fn(),
+  };
+
+  test('should require snapshot and restore methods', async () => {
+    expect(() => Snapshot.capture({ bad: {} })).toThrow('Snapshot component bad must have a snapshot() method');
+
+    const snapshot = Snapshot.capture({ half: { snapshot: () => ({}) } });
+    await expect(snapshot.restore({ half: {} })).rejects.toThrow('must have a restore(state) method');
+  });
+
+  test('should roll conversation and usage back to the captured point', async () => {
+    const conversation = new Conversation(client, { systemPrompt: 'Be brief.' });
+    const usage = new UsageTracker();
+    const tagged = usage.withTag('chat');
+
+    await conversation.send('first');
+    tagged.record('gpt-4', { prompt_tokens: 100, completion_tokens: 50 });
+    const snapshot = Snapshot.capture({ conversation, usage });
+
+    await conversation.send('second');
+    tagged.record('gpt-4', { prompt_tokens: 100, completion_tokens: 50 });
+
+    const restored = await snapshot.restore({ conversation, usage });
+
+    expect(restored).toEqual(['conversation', 'usage']);
+    expect(conversation.messages.map((m) => m.content)).toEqual(['Be brief.', 'first', 'ok']);
+    expect(usage.getTotals().requests).toBe(1);
+    expect(tagged.getBreakdown('tag').chat.requests).toBe(1);
+  });
+
+  test('should not be affected by changes after capture', () => {
+    const agent = new Agent({ client, memory: true });
+    agent.messages.push({ role: 'user', content: 'hello' });
+
+    const snapshot = Snapshot.capture({ agent });
+    agent.messages[0].content = 'changed';
+
+    expect(snapshot.state.agent.messages[0].content).toBe('hello');
+  });
+
+  test('should restore scheduler run times', async () => {
+    let now = 1000;
+    const scheduler = new Scheduler({ now: () => now }).schedule('poll', { every: 500 }, jest.fn());
+    await scheduler.start();
+    scheduler.stop();
+    const snapshot = Snapshot.capture({ scheduler });
+
+    now = 1500;
+    await scheduler.tick();
+    expect(scheduler.list().items[0].nextRun).toBe(2000);
+
+    await snapshot.restore({ scheduler });
+    expect(scheduler.list().items[0]).toMatchObject({ lastRun: null, nextRun: 1500 });
+  });
+
+  test('should restore prompt templates and partials', async () => {
+    const prompts = new PromptRegistry();
+    prompts.registerPartial('tone', 'Be concise.');
+    prompts.register('forecast', '{{> tone}} Forecast {{series}}.', { examples: [{ input: 'a', output: 'b' }] });
+    const snapshot = Snapshot.capture({ prompts });
+
+    prompts.registerPartial('tone', 'Be verbose.');
+    prompts.register('extra', 'Unused');
+
+    await snapshot.restore({ prompts });
+
+    expect(prompts.list().items).toEqual(['forecast']);
+    expect(prompts.render('forecast', { series: 'sales' })).toBe('Input: a\nOutput: b\n\nBe concise. Forecast sales.');
+  });
+
+  test('should leave components missing from the snapshot untouched', async () => {
+    const usage = new UsageTracker();
+    usage.record('gpt-4', { prompt_tokens: 10, completion_tokens: 10 });
+
+    const restored = await new Snapshot({}).restore({ usage });
+
+    expect(restored).toEqual([]);
+    expect(usage.getTotals().requests).toBe(1);
+  });
+
+  test('should save to and load from a store', async () => {
+    const store = new MemoryStore();
+    const usage = new UsageTracker();
+    usage.record('gpt-4', { prompt_tokens: 10, completion_tokens: 10 });
+
+    const snapshot = Snapshot.capture({ usage });
+    await snapshot.save(store, 'nightly');
+    usage.reset();
+
+    const loaded = await Snapshot.load(store, 'nightly');
+    expect(loaded.takenAt).toBe(snapshot.takenAt);
+    await loaded.restore({ usage });
+    expect(usage.getTotals().requests).toBe(1);
+
+    expect(await Snapshot.load(store, 'missing')).toBeNull();
+  });
+});
@@ -0,0 +1,65 @@
+const StreamTee = require('../src/streamTee');
+const { MemoryJournal } = require('../src/recorder');
+
+async function* tokens(list, failAt = -1) {
+  for (let i = 0; i < list.length; i++) {
+    if (i === failAt) {
+      throw new Error('stream reset');
+    }
+    yield list[i];
+  }
+}
+
+describe('StreamTee', () => {
+  test('should require a journal', () => {
+    expect(() => new StreamTee(tokens([]))).toThrow('StreamTee requires a journal');
+  });
+
+  test('should journal every chunk before yielding it', async () => {
+    const journal = new MemoryJournal();
+    const tee = new StreamTee(tokens(['Hel', 'lo']), { journal, runId: 'run-1' });
+
+    const seen = [];
+    for await (const text of tee) {
+      seen.push([text, journal.entries.length]);
+    }
+
+    expect(seen).toEqual([['Hel', 1], ['lo', 2]]);
+    expect(await tee.finished).toEqual({ runId: 'run-1', type: 'end', status: 'completed', text: 'Hello' });
+    expect(journal.entries[1]).toEqual({ runId: 'run-1', type: 'chunk', index: 1, text: 'lo' });
+  });
+
+  test('should finish reading the stream after the consumer disconnects', async () => {
+    const journal = new MemoryJournal();
+    const tee = new StreamTee(tokens(['a', 'b', 'c']), { journal, runId: 'run-2' });
+
+    for await (const text of tee) {
+      expect(text).toBe('a');
+      break;
+    }
+
+    const record = await tee.finished;
+    expect(record).toMatchObject({ status: 'disconnected', text: 'abc' });
+    expect(journal.entries.filter((entry) => entry.type === 'chunk')).toHaveLength(3);
+  });
+
+  test('should record failures and rethrow them', async () => {
+    const journal = new MemoryJournal();
+    const tee = new StreamTee(tokens(['a', 'b'], 1), { journal });
+
+    const consume = async () => {
+      for await (const text of tee) {
+        expect(text).toBe('a');
+      }
+    };
+
+    await expect(consume()).rejects.toThrow('stream reset');
+    expect(await tee.finished).toMatchObject({ status: 'failed', text: 'a', error: 'stream reset' });
+  });
+
+  test('should only be iterated once', async () => {
+    const tee = new StreamTee(tokens([]), { journal: new MemoryJournal() });
+    tee[Symbol.asyncIterator]();
+    expect(() => tee[Symbol.asyncIterator]()).toThrow('StreamTee can only be iterated once');
+  });
+});
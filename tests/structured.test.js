@@ -0,0 +1,92 @@
+const { completeJSON, validateSchema, parseJSONReply } = require('../src/structured');
+const { MockAIProvider } = require('../src/testing');
+
+const forecastSchema = {
+  type: 'object',
+  properties: {
+    trend: { type: 'string', enum: ['up', 'down', 'flat'] },
+    confidence: { type: 'number', minimum: 0, maximum: 1 },
+    values: { type: 'array', items: { type: 'integer' }, minItems: 1 },
+  },
+  required: ['trend', 'confidence'],
+  additionalProperties: false,
+};
+
+describe('validateSchema', () => {
+  test('should accept matching values', () => {
+    expect(validateSchema({ trend: 'up', confidence: 0.8, values: [1, 2] }, forecastSchema)).toEqual([]);
+  });
+
+  test('should report every problem with its path', () => {
+    const errors = validateSchema({ trend: 'sideways', values: [1, 2.5], extra: true }, forecastSchema);
+
+    expect(errors).toEqual([
+      '$.confidence is required',
+      '$.trend should be one of ["up","down","flat"]',
+      '$.values[1] should be integer, got number',
+      '$.extra is not allowed',
+    ]);
+  });
+
+  test('should check types, ranges and lengths', () => {
+    expect(validateSchema('x', { type: ['number', 'null'] })).toEqual(['$ should be number or null, got string']);
+    expect(validateSchema(null, { type: ['number', 'null'] })).toEqual([]);
+    expect(validateSchema(2, { type: 'number', maximum: 1 })).toEqual(['$ should be <= 1']);
+    expect(validateSchema('', { type: 'string', minLength: 1 })).toEqual(['$ should have at least 1 characters']);
+    expect(validateSchema([], { type: 'array', minItems: 1 })).toEqual(['$ should have at least 1 items']);
+  });
+});
+
+describe('parseJSONReply', () => {
+  test('should strip Markdown code fences', () => {
+    expect(parseJSONReply('```json\n{"a": 1}\n```')).toEqual({ a: 1 });
+    expect(parseJSONReply(' [1, 2] ')).toEqual([1, 2]);
+  });
+});
+
+describe('completeJSON', () => {
+  test('should inject the schema and return the parsed value', async () => {
+    const client = new MockAIProvider({ responses: ['{"trend": "up", "confidence": 0.9}'] });
+
+    const value = await completeJSON(client, 'Summarize the forecast', forecastSchema);
+
+    expect(value).toEqual({ trend: 'up', confidence: 0.9 });
+    expect(client.calls[0].messages[0].role).toBe('system');
+    expect(client.calls[0].messages[0].content).toContain('"required":["trend","confidence"]');
+    expect(client.calls[0].messages[1]).toEqual({ role: 'user', content: 'Summarize the forecast' });
+  });
+
+  test('should send repair prompts until the reply is valid', async () => {
+    const client = new MockAIProvider({
+      responses: ['Sure! The trend is up.', '{"trend": "up", "confidence": 3}', '{"trend": "up", "confidence": 0.7}'],
+    });
+
+    const value = await completeJSON(client, 'Summarize', forecastSchema);
+
+    expect(value).toEqual({ trend: 'up', confidence: 0.7 });
+    expect(client.calls).toHaveLength(3);
+    expect(client.calls[1].messages[3].content).toContain('invalid JSON');
+    expect(client.calls[2].messages[5].content).toContain('$.confidence should be <= 1');
+  });
+
+  test('should give up after the configured retries', async () => {
+    const client = new MockAIProvider({ defaultResponse: '{"trend": "up"}' });
+
+    await expect(completeJSON(client, 'Summarize', forecastSchema, { retries: 1 }))
+      .rejects.toThrow('Structured output failed after 2 attempts: $.confidence is required');
+    expect(client.calls).toHaveLength(2);
+  });
+
+  test('should request native JSON modes when asked', async () => {
+    const client = new MockAIProvider({ defaultResponse: '{"trend": "flat", "confidence": 0.5}' });
+
+    await completeJSON(client, 'a', forecastSchema, { responseFormat: 'json_object', requestOptions: { model: 'gpt-4o' } });
+    await completeJSON(client, 'b', forecastSchema, { responseFormat: 'json_schema', name: 'forecast' });
+
+    expect(client.calls[0].options).toEqual({ model: 'gpt-4o', response_format: { type: 'json_object' } });
+    expect(client.calls[1].options.response_format).toEqual({
+      type: 'json_schema',
+      json_schema: { name: 'forecast', schema: forecastSchema },
+    });
+  });
+});
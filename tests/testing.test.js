@@ -0,0 +1,75 @@
+const { MockAIProvider } = require('../src/testing');
+const { ToolRegistry } = require('../src/tools');
+const Agent = require('../src/agent');
+const Conversation = require('../src/conversation');
+
+describe('MockAIProvider', () => {
+  test('should return scripted replies in order, then the default', async () => {
+    const mock = new MockAIProvider({ responses: ['first'], defaultResponse: 'fallback' });
+    mock.reply('second');
+
+    expect(await mock.chat('a')).toBe('first');
+    expect(await mock.chat('b')).toBe('second');
+    expect(await mock.chat('c')).toBe('fallback');
+  });
+
+  test('should record calls with messages and options', async () => {
+    const mock = new MockAIProvider();
+    await mock.chat('Hello', { temperature: 0.2 });
+    await mock.conversation([{ role: 'user', content: 'Hi' }]);
+
+    expect(mock.calls).toEqual([
+      { method: 'chat', messages: [{ role: 'user', content: 'Hello' }], options: { temperature: 0.2 } },
+      { method: 'conversation', messages: [{ role: 'user', content: 'Hi' }], options: {} },
+    ]);
+  });
+
+  test('should inject failures', async () => {
+    const mock = new MockAIProvider({ responses: ['ok'] });
+    mock.failNext('rate limited');
+
+    await expect(mock.chat('a')).rejects.toThrow('rate limited');
+    expect(await mock.chat('b')).toBe('ok');
+
+    mock.reply(new Error('timeout'));
+    await expect(mock.chat('c')).rejects.toThrow('timeout');
+  });
+
+  test('should compute replies from functions', async () => {
+    const mock = new MockAIProvider().reply((messages) => `echo: ${messages[messages.length - 1].content}`);
+    expect(await mock.chat('ping')).toBe('echo: ping');
+  });
+
+  test('should drive tool calling', async () => {
+    const tools = new ToolRegistry().register({ name: 'add', handler: ({ a, b }) => a + b });
+    const mock = new MockAIProvider().reply(MockAIProvider.toolCall('add', { a: 2, b: 3 }), 'The sum is 5.');
+
+    const result = await mock.chatWithTools([{ role: 'user', content: 'Add 2 and 3' }], tools);
+
+    expect(result.content).toBe('The sum is 5.');
+    expect(result.messages[2]).toEqual({ role: 'tool', tool_call_id: 'call_add', content: '5' });
+    expect(mock.calls[1].messages).toHaveLength(3);
+  });
+
+  test('should work as the client of an Agent and a Conversation', async () => {
+    const tools = new ToolRegistry().register({ name: 'lookup', handler: () => 'found' });
+    const agent = new Agent({
+      client: new MockAIProvider().reply(MockAIProvider.toolCall('lookup'), 'All done'),
+      tools,
+    });
+    expect((await agent.run('Find it')).output).toBe('All done');
+
+    const conversation = new Conversation(new MockAIProvider({ defaultResponse: 'Sure.' }));
+    expect(await conversation.send('Help?')).toBe('Sure.');
+  });
+
+  test('should reset script and calls', async () => {
+    const mock = new MockAIProvider({ responses: ['scripted'] });
+    await mock.chat('a');
+    mock.reply('queued');
+    mock.reset();
+
+    expect(mock.calls).toEqual([]);
+    expect(await mock.chat('b')).toBe('mock response');
+  });
+});
@@ -0,0 +1,126 @@
+const { ToolRegistry, runWithTools } = require('../src/tools');
+const ChatGPT = require('../src/chatgpt');
+
+jest.mock('openai');
+
+const toolCall = (id, name, args) => ({
+  id,
+  type: 'function',
+  function: { name, arguments: JSON.stringify(args) },
+});
+
+describe('ToolRegistry', () => {
+  describe('register', () => {
+    test('should throw error for invalid tools', () => {
+      const tools = new ToolRegistry();
+      expect(() => tools.register({ name: 'bad name', handler: () => {} })).toThrow('Invalid tool name');
+      expect(() => tools.register({ name: 'ok' })).toThrow('requires a handler function');
+    });
+
+    test('should expose definitions in chat completions format', () => {
+      const tools = new ToolRegistry().register({
+        name: 'get_forecast',
+        description: 'Get the latest forecast',
+        parameters: { type: 'object', properties: { series: { type: 'string' } }, required: ['series'] },
+        handler: () => [],
+      });
+
+      expect(tools.has('get_forecast')).toBe(true);
+      expect(tools.definitions()).toEqual([
+        {
+          type: 'function',
+          function: {
+            name: 'get_forecast',
+            description: 'Get the latest forecast',
+            parameters: { type: 'object', properties: { series: { type: 'string' } }, required: ['series'] },
+          },
+        },
+      ]);
+    });
+  });
+
+  describe('execute', () => {
+    test('should run the handler with parsed arguments', async () => {
+      const handler = jest.fn().mockResolvedValue({ value: 42 });
+      const tools = new ToolRegistry().register({ name: 'lookup', handler });
+
+      const result = await tools.execute(toolCall('call_1', 'lookup', { key: 'a' }));
+
+      expect(handler).toHaveBeenCalledWith({ key: 'a' });
+      expect(result).toEqual({ role: 'tool', tool_call_id: 'call_1', content: '{"value":42}' });
+    });
+
+    test('should report unknown tools and handler errors to the model', async () => {
+      const tools = new ToolRegistry().register({
+        name: 'fail',
+        handler: () => {
+          throw new Error('not available');
+        },
+      });
+
+      expect((await tools.execute(toolCall('1', 'missing', {}))).content).toBe('Error: unknown tool missing');
+      expect((await tools.execute(toolCall('2', 'fail', {}))).content).toBe('Error: not available');
+    });
+  });
+});
+
+describe('runWithTools', () => {
+  test('should execute tool calls until the model answers', async () => {
+    const tools = new ToolRegistry().register({ name: 'add', handler: ({ a, b }) => String(a + b) });
+    const send = jest.fn()
+      .mockResolvedValueOnce({ role: 'assistant', content: null, tool_calls: [toolCall('c1', 'add', { a: 2, b: 3 })] })
+      .mockResolvedValueOnce({ role: 'assistant', content: 'The sum is 5' });
+
+    const result = await runWithTools(send, [{ role: 'user', content: '2+3?' }], tools);
+
+    expect(result.content).toBe('The sum is 5');
+    expect(send).toHaveBeenCalledTimes(2);
+    expect(send.mock.calls[1][0][2]).toEqual({ role: 'tool', tool_call_id: 'c1', content: '5' });
+    expect(result.messages).toHaveLength(4);
+  });
+
+  test('should return tool calls without executing when autoExecute is false', async () => {
+    const handler = jest.fn();
+    const tools = new ToolRegistry().register({ name: 'noop', handler });
+    const calls = [toolCall('c1', 'noop', {})];
+    const send = jest.fn().mockResolvedValue({ role: 'assistant', content: null, tool_calls: calls });
+
+    const result = await runWithTools(send, [], tools, { autoExecute: false });
+
+    expect(result.toolCalls).toEqual(calls);
+    expect(handler).not.toHaveBeenCalled();
+  });
+
+  test('should stop after maxIterations', async () => {
+    const tools = new ToolRegistry().register({ name: 'loop', handler: () => 'again' });
+    const send = jest.fn().mockResolvedValue({ role: 'assistant', content: null, tool_calls: [toolCall('c', 'loop', {})] });
+
+    await expect(runWithTools(send, [], tools, { maxIterations: 3 })).rejects.toThrow('did not finish within 3 iterations');
+    expect(send).toHaveBeenCalledTimes(3);
+  });
+});
+
+describe('ChatGPT chatWithTools', () => {
+  test('should send tool definitions and loop through tool calls', async () => {
+    const OpenAI = require('openai');
+    const mockCreate = jest.fn()
+      .mockResolvedValueOnce({
+        choices: [{ message: { role: 'assistant', content: null, tool_calls: [toolCall('c1', 'get_time', {})] } }],
+      })
+      .mockResolvedValueOnce({
+        choices: [{ message: { role: 'assistant', content: 'It is noon.' } }],
+      });
+
+    OpenAI.mockImplementation(() => ({
+      chat: { completions: { create: mockCreate } },
+    }));
+
+    const tools = new ToolRegistry().register({ name: 'get_time', handler: () => '12:00' });
+    const chatgpt = new ChatGPT('test-key');
+    const result = await chatgpt.chatWithTools([{ role: 'user', content: 'Time?' }], tools);
+
+    expect(result.content).toBe('It is noon.');
+    expect(mockCreate.mock.calls[0][0].tools).toEqual(tools.definitions());
+    expect(mockCreate.mock.calls[1][0].messages[2]).toEqual({ role: 'tool', tool_call_id: 'c1', content: '12:00' });
+  });
+});
@@ -0,0 +1,132 @@
+const UsageTracker = require('../src/usageTracker');
+const ChatGPT = require('../src/chatgpt');
+
+jest.mock('openai');
+
+describe('UsageTracker', () => {
+  describe('record', () => {
+    test('should record tokens and cost for a known model', () => {
+      const tracker = new UsageTracker();
+      const entry = tracker.record('gpt-4', { prompt_tokens: 1000, completion_tokens: 500 });
+
+      expect(entry.promptTokens).toBe(1000);
+      expect(entry.completionTokens).toBe(500);
+      expect(entry.cost).toBeCloseTo(0.06);
+    });
+
+    test('should ignore requests without usage', () => {
+      const tracker = new UsageTracker();
+      expect(tracker.record('gpt-4', undefined)).toBeNull();
+      expect(tracker.getTotals().requests).toBe(0);
+    });
+
+    test('should price versioned model names by their base model', () => {
+      const tracker = new UsageTracker();
+      expect(tracker.estimateCost('gpt-3.5-turbo-0125', 1000, 1000)).toBeCloseTo(0.002);
+      expect(tracker.estimateCost('gpt-3.5-turbo-16k-0613', 1000, 1000)).toBeCloseTo(0.007);
+    });
+
+    test('should use custom pricing and treat unknown models as free', () => {
+      const tracker = new UsageTracker({ pricing: { 'my-model': { prompt: 1, completion: 2 } } });
+      expect(tracker.estimateCost('my-model', 1000, 1000)).toBeCloseTo(3);
+      expect(tracker.estimateCost('unknown', 1000, 1000)).toBe(0);
+    });
+  });
+
+  describe('totals and breakdowns', () => {
+    test('should aggregate totals across requests', () => {
+      const tracker = new UsageTracker();
+      tracker.record('gpt-4', { prompt_tokens: 100, completion_tokens: 50 });
+      tracker.record('gpt-3.5-turbo', { prompt_tokens: 200, completion_tokens: 100 });
+
+      const totals = tracker.getTotals();
+      expect(totals.requests).toBe(2);
+      expect(totals.promptTokens).toBe(300);
+      expect(totals.completionTokens).toBe(150);
+      expect(totals.totalTokens).toBe(450);
+    });
+
+    test('should limit totals to requests since a given time', () => {
+      const tracker = new UsageTracker();
+      tracker.record('gpt-4', { prompt_tokens: 100, completion_tokens: 50 });
+      tracker.entries[0].timestamp = '2020-01-01T00:00:00.000Z';
+      tracker.record('gpt-4', { prompt_tokens: 10, completion_tokens: 5 });
+
+      expect(tracker.getTotals('2024-01-01').requests).toBe(1);
+      expect(tracker.getTotals(new Date('2019-01-01')).requests).toBe(2);
+    });
+
+    test('should break down usage by tag through tagged views', () => {
+      const tracker = new UsageTracker();
+      tracker.withTag('ingestion').record('gpt-4', { prompt_tokens: 100, completion_tokens: 0 });
+      tracker.withTag('chat').record('gpt-4', { prompt_tokens: 10, completion_tokens: 0 });
+      tracker.withTag('chat').record('gpt-4', { prompt_tokens: 20, completion_tokens: 0 });
+
+      const breakdown = tracker.getBreakdown('tag');
+      expect(breakdown.ingestion.promptTokens).toBe(100);
+      expect(breakdown.chat.requests).toBe(2);
+      expect(tracker.getTotals().requests).toBe(3);
+    });
+
+    test('should attribute usage to tenants and notify listeners', () => {
+      const tracker = new UsageTracker();
+      const listener = jest.fn();
+      const unsubscribe = tracker.onRecord(listener);
+
+      const entry = tracker.withTag('chat').withTenant('acme').record('gpt-4', { prompt_tokens: 1, completion_tokens: 1 });
+      unsubscribe();
+      tracker.record('gpt-4', { prompt_tokens: 1, completion_tokens: 1 });
+
+      expect(entry).toMatchObject({ tenant: 'acme', tags: ['chat'] });
+      expect(listener).toHaveBeenCalledTimes(1);
+      expect(listener).toHaveBeenCalledWith(entry);
+    });
+
+    test('should break down usage by model', () => {
+      const tracker = new UsageTracker();
+      tracker.record('gpt-4', { prompt_tokens: 1, completion_tokens: 1 });
+      tracker.record('grok-beta', { prompt_tokens: 1, completion_tokens: 1 });
+
+      expect(Object.keys(tracker.getBreakdown('model'))).toEqual(['gpt-4', 'grok-beta']);
+      expect(() => tracker.getBreakdown('day')).toThrow('Unsupported breakdown');
+    });
+  });
+
+  describe('checkBudget', () => {
+    test('should throw once spend reaches maxSpend', () => {
+      const tracker = new UsageTracker({ maxSpend: 0.05 });
+      tracker.checkBudget();
+      tracker.record('gpt-4', { prompt_tokens: 1000, completion_tokens: 500 });
+      expect(() => tracker.checkBudget()).toThrow('Usage budget exceeded');
+    });
+
+    test('should share the budget with tagged views', () => {
+      const tracker = new UsageTracker({ maxSpend: 0.01 });
+      tracker.withTag('ingestion').record('gpt-4', { prompt_tokens: 1000, completion_tokens: 0 });
+      expect(() => tracker.withTag('chat').checkBudget()).toThrow('Usage budget exceeded');
+    });
+  });
+
+  describe('client integration', () => {
+    test('should record usage and refuse requests over budget', async () => {
+      const OpenAI = require('openai');
+      const mockCreate = jest.fn().mockResolvedValue({
+        choices: [{ message: { content: 'ok' } }],
+        usage: { prompt_tokens: 1000, completion_tokens: 1000 },
+      });
+
+      OpenAI.mockImplementation(() => ({
+        chat: { completions: { create: mockCreate } },
+      }));
+
+      const tracker = new UsageTracker({ maxSpend: 0.05 });
+      const chatgpt = new ChatGPT('test-key', { usageTracker: tracker });
+
+      await chatgpt.chat('first');
+      expect(tracker.getTotals().cost).toBeCloseTo(0.09);
+
+      await expect(chatgpt.chat('second')).rejects.toThrow('Usage budget exceeded');
+      expect(mockCreate).toHaveBeenCalledTimes(1);
+    });
+  });
+});
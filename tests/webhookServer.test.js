@@ -0,0 +1,179 @@
+const crypto = require('crypto');
+const http = require('http');
+const WebhookServer = require('../src/webhookServer');
+
+function post(port, path, body, headers = {}) {
+  return new Promise((resolve, reject) => {
+    const req = http.request({ host: '127.0.0.1', port, path, method: 'POST', headers }, (res) => {
+      let data = '';
+      res.on('data', (chunk) => {
+        data += chunk;
+      });
+      res.on('end', () => resolve({ statusCode: res.statusCode, body: data ? JSON.parse(data) : null }));
+    });
+    req.on('error', reject);
+    req.end(body);
+  });
+}
+
+describe('WebhookServer', () => {
+  const now = () => 1700000000000;
+  const timestamp = '1700000000';
+  let server;
+  let port;
+
+  const start = async (webhooks) => {
+    server = await webhooks.listen(0);
+    port = server.address().port;
+  };
+
+  afterEach(() => {
+    if (server) {
+      server.close();
+      server = null;
+    }
+  });
+
+  test('should reject unknown sources', () => {
+    expect(() => new WebhookServer().on('irc', jest.fn())).toThrow('Unsupported webhook source: irc');
+  });
+
+  test('should only serve configured sources', async () => {
+    await start(new WebhookServer({ secret: 's' }));
+    expect((await post(port, '/slack', '{}')).statusCode).toBe(404);
+  });
+
+  describe('generic webhooks', () => {
+    const sign = (body, ts = timestamp) => `sha256=${crypto.createHmac('sha256', 'shh').update(`${ts}.${body}`).digest('hex')}`;
+    const headers = (body) => ({ 'X-Webhook-Timestamp': timestamp, 'X-Signature-256': sign(body) });
+
+    test('should dispatch signed payloads and return the handler result', async () => {
+      const webhooks = new WebhookServer({ secret: 'shh', now });
+      const handler = jest.fn(async (payload) => ({ received: payload.id }));
+      webhooks.on('webhook', handler);
+      await start(webhooks);
+
+      const body = JSON.stringify({ id: 7 });
+      const response = await post(port, '/webhook', body, headers(body));
+
+      expect(response).toEqual({ statusCode: 200, body: { received: 7 } });
+      expect(handler).toHaveBeenCalledWith({ id: 7 }, expect.objectContaining({ headers: expect.any(Object) }));
+    });
+
+    test('should reject bad signatures without calling handlers', async () => {
+      const handler = jest.fn();
+      await start(new WebhookServer({ secret: 'shh', now }).on('webhook', handler));
+
+      const response = await post(port, '/webhook', '{"id":1}', headers('{"id":2}'));
+
+      expect(response.statusCode).toBe(401);
+      expect(handler).not.toHaveBeenCalled();
+    });
+
+    test('should report handler failures and oversized bodies', async () => {
+      const webhooks = new WebhookServer({ secret: 'shh', maxBodyBytes: 20, now }).on('webhook', async () => {
+        throw new Error('agent down');
+      });
+      await start(webhooks);
+
+      expect((await post(port, '/webhook', '{}', headers('{}'))).statusCode).toBe(500);
+      expect((await post(port, '/webhook', JSON.stringify({ text: 'x'.repeat(50) }))).statusCode).toBe(413);
+    });
+
+    test('should stop reading an oversized streamed body and close the connection', async () => {
+      await start(new WebhookServer({ secret: 'shh', maxBodyBytes: 20, now }));
+
+      const response = await new Promise((resolve, reject) => {
+        const req = http.request({ host: '127.0.0.1', port, path: '/webhook', method: 'POST' }, resolve);
+        req.on('error', reject);
+        // No Content-Length, so the limit is only found while reading
+        req.write('x'.repeat(64));
+      });
+
+      expect(response.statusCode).toBe(413);
+      expect(response.headers.connection).toBe('close');
+      await new Promise((resolve) => response.on('close', resolve).resume());
+    });
+
+    test('should reject replayed requests with stale or missing timestamps', () => {
+      const webhooks = new WebhookServer({ secret: 'shh', now });
+      const old = String(Number(timestamp) - 600);
+
+      expect(webhooks.verifyWebhook('{}', { 'x-webhook-timestamp': old, 'x-signature-256': sign('{}', old) })).toBe(false);
+      expect(webhooks.verifyWebhook('{}', { 'x-signature-256': sign('{}') })).toBe(false);
+      expect(webhooks.verifyWebhook('{}', { 'x-webhook-timestamp': timestamp, 'x-signature-256': sign('{}') })).toBe(true);
+    });
+  });
+
+  describe('Slack', () => {
+    const sign = (body, ts = timestamp) => `v0=${crypto.createHmac('sha256', 'slack-secret').update(`v0:${ts}:${body}`).digest('hex')}`;
+
+    test('should answer URL verification challenges', async () => {
+      await start(new WebhookServer({ slackSigningSecret: 'slack-secret', now }));
+      const body = JSON.stringify({ type: 'url_verification', challenge: 'abc' });
+
+      const response = await post(port, '/slack', body, {
+        'X-Slack-Request-Timestamp': timestamp,
+        'X-Slack-Signature': sign(body),
+      });
+
+      expect(response.body).toEqual({ challenge: 'abc' });
+    });
+
+    test('should acknowledge events before their handlers finish', async () => {
+      let release;
+      const handler = jest.fn(() => new Promise((resolve) => { release = resolve; }));
+      const webhooks = new WebhookServer({ slackSigningSecret: 'slack-secret', now }).on('slack', handler);
+      await start(webhooks);
+      const body = JSON.stringify({ type: 'event_callback', event: { text: 'hi' } });
+
+      const response = await post(port, '/slack', body, {
+        'X-Slack-Request-Timestamp': timestamp,
+        'X-Slack-Signature': sign(body),
+      });
+
+      expect(response).toEqual({ statusCode: 200, body: { ok: true } });
+      expect(handler).toHaveBeenCalledTimes(1);
+      expect(webhooks.pending.size).toBe(1);
+      release();
+      await webhooks.idle();
+      expect(webhooks.pending.size).toBe(0);
+    });
+
+    test('should reject stale timestamps', () => {
+      const webhooks = new WebhookServer({ slackSigningSecret: 'slack-secret', now });
+      const old = String(Number(timestamp) - 600);
+      const headers = { 'x-slack-request-timestamp': old, 'x-slack-signature': sign('{}', old) };
+
+      expect(webhooks.verifySlack('{}', headers)).toBe(false);
+      expect(webhooks.verifySlack('{}', { 'x-slack-request-timestamp': timestamp, 'x-slack-signature': sign('{}') })).toBe(true);
+    });
+  });
+
+  describe('Discord', () => {
+    const { publicKey, privateKey } = crypto.generateKeyPairSync('ed25519');
+    const rawPublicKey = publicKey.export({ format: 'der', type: 'spki' }).subarray(-32).toString('hex');
+    const sign = (body) => crypto.sign(null, Buffer.from(timestamp + body), privateKey).toString('hex');
+
+    test('should answer pings and dispatch interactions', async () => {
+      const handler = jest.fn(async () => ({ type: 4, data: { content: 'hi' } }));
+      await start(new WebhookServer({ discordPublicKey: rawPublicKey, now }).on('discord', handler));
+
+      const ping = JSON.stringify({ type: 1 });
+      const command = JSON.stringify({ type: 2, data: { name: 'ask' } });
+      const headers = (body) => ({ 'X-Signature-Timestamp': timestamp, 'X-Signature-Ed25519': sign(body) });
+
+      expect((await post(port, '/discord', ping, headers(ping))).body).toEqual({ type: 1 });
+      expect((await post(port, '/discord', command, headers(command))).body).toEqual({ type: 4, data: { content: 'hi' } });
+      expect(handler).toHaveBeenCalledTimes(1);
+    });
+
+    test('should reject forged interactions', () => {
+      const webhooks = new WebhookServer({ discordPublicKey: rawPublicKey, now });
+      const headers = { 'x-signature-timestamp': timestamp, 'x-signature-ed25519': sign('{"type":2}') };
+
+      expect(webhooks.verifyDiscord('{"type":3}', headers)).toBe(false);
+      expect(webhooks.verifyDiscord('{"type":2}', headers)).toBe(true);
+    });
+  });
+});